@@ -1,23 +1,54 @@
-use crate::status::Status;
-use crate::parser::MarkdownParser;
+use crate::config::MetaGraphLayout;
+use crate::status::{Lifecycle, Priority, Status};
+use crate::template::DEFAULT_ACTION_TYPE;
+use crate::id::ActionId;
+use crate::parser::{MarkdownParser, ParseWarning, Sections, Subsections};
+use crate::utils;
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::fs;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
-use uuid::Uuid;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Action {
-    pub id: Uuid,
+    pub id: ActionId,
     pub title: String,
     pub project: String,
     pub status: Status,
-    pub priority: bool,
+    /// Blocked or cancelled actions keep their status but are out of the pipeline
+    pub lifecycle: Lifecycle,
+    pub priority: Priority,
+    /// Kind of action (see `ActionType`), stored as `type:` in the frontmatter
+    #[serde(rename = "type")]
+    pub action_type: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Deadline, stored as `due_at:` in the frontmatter (UTC)
+    pub due: Option<DateTime<Utc>>,
+    /// Who is working on the action, stored as `assignee:` in the frontmatter
+    pub assignee: Option<String>,
+    /// Expected effort, stored as `estimate:` like `2h30m`
+    #[serde(default, with = "duration_text")]
+    pub estimate: Option<Duration>,
+    /// Time logged so far, stored as `time_spent:` like `estimate`
+    #[serde(default, with = "duration_text")]
+    pub time_spent: Option<Duration>,
+    /// Actions this one waits on, as `project/title`; stored as a
+    /// comma-separated `depends_on:` list in the frontmatter
+    pub depends_on: Vec<String>,
+    /// Absolute, so left out of serialized output; `ActionView` reports it
+    /// relative to the workspace root instead
+    #[serde(skip_serializing, default)]
     pub file_path: PathBuf,
+    /// Content hash recorded the last time the action was synced externally
+    pub synced_hash: Option<String>,
+    /// Frontmatter keys outside `FRONTMATTER_KEYS`, written back in key order
+    pub extra_metadata: HashMap<String, String>,
     
     // Content sections
     pub notes: Option<String>,
@@ -25,34 +56,155 @@ pub struct Action {
     pub statement_of_inputs: Option<String>,
     pub statement_of_design: Option<String>,
     pub analysis_of_impact: Option<String>,
+    /// Links to child actions this action rolls up
+    pub children: Option<String>,
+    pub log: Option<String>,
+    /// `## ` sections outside `SECTION_NAMES`, as `(heading, body)` in the
+    /// order they appear in the file. Written back after the known sections.
+    pub extra_sections: Vec<(String, String)>,
+    /// Tags on the tag line under the title, besides the generated ones.
+    /// Not serialized: views report every user tag (`user_tags`) instead.
+    #[serde(skip)]
+    pub tag_line: Vec<String>,
+    
+    /// Recoverable problems noticed while parsing the file
+    #[serde(skip)]
+    pub parse_warnings: Vec<ParseWarning>,
+    /// Where this action's meta-graph directory lives, from the workspace config
+    #[serde(skip)]
+    pub meta_graph_layout: MetaGraphLayout,
+    /// The last status of the workspace's workflow, which marks the action as done
+    #[serde(skip)]
+    pub final_status: Status,
+}
+
+/// One `- YYYY-MM-DD (author): text` line of the `Log` section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogEntry {
+    pub date: NaiveDate,
+    pub author: String,
+    pub text: String,
+}
+
+/// A markdown link from an action to another `.md` file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ActionLink {
+    /// The link text
+    pub text: String,
+    /// The target exactly as written
+    pub target: String,
+    /// The action the target points at, when resolved against a workspace
+    pub resolved: Option<LinkTarget>,
 }
 
+/// The project and title of the action a link resolves to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkTarget {
+    pub project: String,
+    pub title: String,
+}
+
+/// Names of the sections an action knows about, in document order
+pub const SECTION_NAMES: &[&str] = &[
+    "Notes",
+    "Statement of Action",
+    "Statement of Inputs",
+    "Statement of Design",
+    "Analysis of Impact",
+    "Children",
+    "Log",
+];
+
+/// Parse a due date given as RFC 3339 or as a plain `YYYY-MM-DD`.
+///
+/// All dates are UTC. A plain date means the end of that day, so an action
+/// due on the 1st becomes overdue at midnight UTC going into the 2nd.
+pub fn parse_due(input: &str) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(input) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+    
+    let date = NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .with_context(|| format!("Invalid due date: {}. Use YYYY-MM-DD or RFC 3339", input))?;
+    let end_of_day = date.and_hms_opt(23, 59, 59).expect("23:59:59 is a valid time");
+    Ok(end_of_day.and_utc())
+}
+
+/// Frontmatter keys an action reads; anything else is reported when parsing
+pub const FRONTMATTER_KEYS: &[&str] = &[
+    "id",
+    "status",
+    "lifecycle",
+    "priority",
+    "type",
+    "created_at",
+    "updated_at",
+    "due_at",
+    "assignee",
+    "estimate",
+    "time_spent",
+    "depends_on",
+    "synced_hash",
+];
+
 impl Action {
-    pub fn new(project: String, title: String, priority: bool) -> Self {
+    pub fn new(project: String, title: String, priority: Priority) -> Self {
         let now = Utc::now();
         Self {
-            id: Uuid::new_v4(),
+            id: ActionId::new_uuid(),
             title,
             project,
             status: Status::default(),
+            lifecycle: Lifecycle::default(),
             priority,
+            action_type: DEFAULT_ACTION_TYPE.to_string(),
             created_at: now,
             updated_at: now,
+            due: None,
+            assignee: None,
+            estimate: None,
+            time_spent: None,
+            depends_on: Vec::new(),
             file_path: PathBuf::new(),
+            synced_hash: None,
+            extra_metadata: HashMap::new(),
             notes: None,
             statement_of_action: None,
             statement_of_inputs: None,
             statement_of_design: None,
             analysis_of_impact: None,
+            children: None,
+            log: None,
+            extra_sections: Vec::new(),
+            tag_line: Vec::new(),
+            parse_warnings: Vec::new(),
+            meta_graph_layout: MetaGraphLayout::default(),
+            final_status: Status::Published,
         }
     }
     
     pub fn from_file<P: AsRef<Path>>(file_path: P) -> Result<Self> {
+        Self::from_file_with(file_path, false)
+    }
+    
+    /// Load an action, failing on recoverable parse problems when `strict` is set
+    pub fn from_file_with<P: AsRef<Path>>(file_path: P, strict: bool) -> Result<Self> {
         let content = fs::read_to_string(&file_path)
             .with_context(|| format!("Failed to read action file: {}", file_path.as_ref().display()))?;
-            
-        let parser = MarkdownParser::new(&content);
-        let (metadata, sections) = parser.parse()?;
+        
+        Self::from_markdown_with(&content, file_path, strict)
+    }
+    
+    /// Parse an action from markdown content, deriving title and project from `file_path`
+    pub fn from_markdown<P: AsRef<Path>>(content: &str, file_path: P) -> Result<Self> {
+        Self::from_markdown_with(content, file_path, false)
+    }
+    
+    pub fn from_markdown_with<P: AsRef<Path>>(content: &str, file_path: P, strict: bool) -> Result<Self> {
+        let parser = MarkdownParser::new(content).known_keys(FRONTMATTER_KEYS);
+        let parser = if strict { parser } else { parser.lenient() };
+        let (metadata, sections, warnings) = parser.parse_with_warnings()?;
         
         let title = file_path.as_ref()
             .file_stem()
@@ -67,18 +219,24 @@ impl Action {
             .unwrap_or("unknown")
             .to_string();
         
-        Ok(Self {
+        let mut action = Self {
             id: metadata.get("id")
-                .and_then(|s| Uuid::parse_str(s).ok())
-                .unwrap_or_else(Uuid::new_v4),
+                .and_then(|s| ActionId::parse(s))
+                .unwrap_or_else(ActionId::new_uuid),
             title,
             project,
             status: metadata.get("status")
-                .and_then(|s| Status::from_str(s).ok())
+                .map(|s| Status::named(s))
+                .unwrap_or_default(),
+            lifecycle: metadata.get("lifecycle")
+                .and_then(|s| Lifecycle::from_str(s).ok())
                 .unwrap_or_default(),
             priority: metadata.get("priority")
-                .map(|s| s.to_lowercase() == "true")
-                .unwrap_or(false),
+                .and_then(|s| Priority::from_str(s).ok())
+                .unwrap_or_default(),
+            action_type: metadata.get("type")
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_ACTION_TYPE.to_string()),
             created_at: metadata.get("created_at")
                 .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
                 .map(|dt| dt.with_timezone(&Utc))
@@ -87,43 +245,155 @@ impl Action {
                 .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(Utc::now),
+            due: metadata.get("due_at")
+                .and_then(|s| parse_due(s).ok()),
+            assignee: metadata.get("assignee")
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            estimate: metadata.get("estimate")
+                .and_then(|s| utils::parse_duration(s).ok()),
+            time_spent: metadata.get("time_spent")
+                .and_then(|s| utils::parse_duration(s).ok()),
+            depends_on: metadata.get("depends_on")
+                .map(|s| s.split(',').map(|d| d.trim().to_string()).filter(|d| !d.is_empty()).collect())
+                .unwrap_or_default(),
             file_path: file_path.as_ref().to_path_buf(),
+            synced_hash: metadata.get("synced_hash").cloned(),
+            extra_metadata: metadata
+                .iter()
+                .filter(|(key, _)| !FRONTMATTER_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
             notes: sections.get("Notes").cloned(),
             statement_of_action: sections.get("Statement of Action").cloned(),
             statement_of_inputs: sections.get("Statement of Inputs").cloned(),
             statement_of_design: sections.get("Statement of Design").cloned(),
             analysis_of_impact: sections.get("Analysis of Impact").cloned(),
-        })
+            children: sections.get("Children").cloned(),
+            log: sections.get("Log").cloned(),
+            extra_sections: extra_sections(content, &sections),
+            tag_line: Vec::new(),
+            parse_warnings: warnings,
+            meta_graph_layout: MetaGraphLayout::default(),
+            final_status: Status::Published,
+        };
+        let mut header = header_tags(content);
+        if !metadata.contains_key("priority") && header.iter().any(|tag| tag == "priority") {
+            // Older files only carry the tag
+            action.priority = Priority::High;
+        }
+        // The generated prefix (`#project #<project> #<status>`, or `#project
+        // #action #<status>` in older files) can name a status the frontmatter
+        // has since moved on from, so it is dropped by position
+        if let Some(start) = header.iter().position(|tag| tag == "project") {
+            header.drain(start..(start + 3).min(header.len()));
+        }
+        action.tag_line = header
+            .into_iter()
+            .filter(|tag| !action.is_reserved_tag(tag))
+            .collect();
+        
+        Ok(action)
     }
     
     pub fn save(&mut self) -> Result<()> {
         self.updated_at = Utc::now();
         let content = self.to_markdown();
-        fs::write(&self.file_path, content)
+        utils::write_atomic(&self.file_path, content)
             .with_context(|| format!("Failed to write action file: {}", self.file_path.display()))?;
         Ok(())
     }
     
+    /// Write a new action file, failing with `ErrorKind::AlreadyExists` if the
+    /// file is already there. The check and the create are a single atomic step.
+    pub fn save_new(&mut self) -> io::Result<()> {
+        self.updated_at = Utc::now();
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&self.file_path)?;
+        file.write_all(self.to_markdown().as_bytes())
+    }
+    
     pub fn to_markdown(&self) -> String {
+        self.render(true)
+    }
+    
+    /// Stable SHA-256 of the normalized markdown.
+    ///
+    /// `updated_at` and `synced_hash` are excluded so that saving an unchanged
+    /// action or recording a sync does not alter the hash.
+    pub fn content_hash(&self) -> String {
+        let digest = Sha256::digest(self.render(false).as_bytes());
+        format!("{:x}", digest)
+    }
+    
+    /// Record the current content hash as the last-synced state
+    pub fn mark_synced(&mut self) {
+        self.synced_hash = Some(self.content_hash());
+    }
+    
+    /// Whether the action changed since it was last marked as synced
+    pub fn has_unsynced_changes(&self) -> bool {
+        self.synced_hash.as_deref() != Some(self.content_hash().as_str())
+    }
+    
+    fn render(&self, include_volatile: bool) -> String {
         let mut content = String::new();
         
         // Metadata
-        content.push_str(&format!("---\n"));
+        content.push_str("---\n");
         content.push_str(&format!("id: {}\n", self.id));
+        content.push_str(&format!("status: {}\n", self.status));
+        content.push_str(&format!("priority: {}\n", self.priority));
+        if self.action_type != DEFAULT_ACTION_TYPE {
+            content.push_str(&format!("type: {}\n", self.action_type));
+        }
+        if self.lifecycle != Lifecycle::Active {
+            content.push_str(&format!("lifecycle: {}\n", self.lifecycle));
+        }
         content.push_str(&format!("created_at: {}\n", self.created_at.to_rfc3339()));
-        content.push_str(&format!("updated_at: {}\n", self.updated_at.to_rfc3339()));
-        content.push_str(&format!("---\n\n"));
+        if let Some(due) = &self.due {
+            content.push_str(&format!("due_at: {}\n", due.to_rfc3339()));
+        }
+        if let Some(assignee) = &self.assignee {
+            content.push_str(&format!("assignee: {}\n", assignee));
+        }
+        if let Some(estimate) = self.estimate {
+            content.push_str(&format!("estimate: {}\n", utils::format_duration(estimate)));
+        }
+        if let Some(spent) = self.time_spent {
+            content.push_str(&format!("time_spent: {}\n", utils::format_duration(spent)));
+        }
+        if !self.depends_on.is_empty() {
+            content.push_str(&format!("depends_on: {}\n", self.depends_on.join(", ")));
+        }
+        if include_volatile {
+            content.push_str(&format!("updated_at: {}\n", self.updated_at.to_rfc3339()));
+            if let Some(hash) = &self.synced_hash {
+                content.push_str(&format!("synced_hash: {}\n", hash));
+            }
+        }
+        let mut extra: Vec<_> = self.extra_metadata.iter().collect();
+        extra.sort();
+        for (key, value) in extra {
+            content.push_str(&format!("{}: {}\n", key, value));
+        }
+        content.push_str("---\n\n");
         
         // Title and tags
         content.push_str(&format!("# {}\n\n", self.title));
         content.push_str(&format!("#project #{} #{}", 
             self.project.replace(" ", "-").to_lowercase(),
-            self.status.to_string()
+            self.status
         ));
         
-        if self.priority {
+        if self.priority.is_set() {
             content.push_str(" #priority");
         }
+        for tag in &self.tag_line {
+            content.push_str(&format!(" #{}", tag));
+        }
         content.push_str("\n\n");
         
         // Sections
@@ -157,6 +427,24 @@ impl Action {
             content.push_str("\n\n");
         }
         
+        if let Some(children) = &self.children {
+            content.push_str("## Children\n\n");
+            content.push_str(children);
+            content.push_str("\n\n");
+        }
+        
+        if let Some(log) = &self.log {
+            content.push_str("## Log\n\n");
+            content.push_str(log);
+            content.push_str("\n\n");
+        }
+        
+        for (heading, body) in &self.extra_sections {
+            content.push_str(&format!("## {}\n\n", heading));
+            content.push_str(body);
+            content.push_str("\n\n");
+        }
+        
         content
     }
     
@@ -164,12 +452,66 @@ impl Action {
     pub fn title(&self) -> &str { &self.title }
     pub fn project(&self) -> &str { &self.project }
     pub fn status(&self) -> &Status { &self.status }
-    pub fn is_priority(&self) -> bool { self.priority }
+    pub fn lifecycle(&self) -> Lifecycle { self.lifecycle }
+    pub fn is_active(&self) -> bool { self.lifecycle == Lifecycle::Active }
+    pub fn priority(&self) -> Priority { self.priority }
+    pub fn is_priority(&self) -> bool { self.priority.is_set() }
+    pub fn action_type(&self) -> &str { &self.action_type }
     pub fn notes(&self) -> Option<&String> { self.notes.as_ref() }
     pub fn statement_of_action(&self) -> Option<&String> { self.statement_of_action.as_ref() }
     pub fn statement_of_inputs(&self) -> Option<&String> { self.statement_of_inputs.as_ref() }
     pub fn statement_of_design(&self) -> Option<&String> { self.statement_of_design.as_ref() }
     pub fn analysis_of_impact(&self) -> Option<&String> { self.analysis_of_impact.as_ref() }
+    pub fn children(&self) -> Option<&String> { self.children.as_ref() }
+    pub fn log(&self) -> Option<&String> { self.log.as_ref() }
+    
+    /// Look up a section by name, case-insensitively
+    pub fn section(&self, name: &str) -> Option<&String> {
+        match name.to_lowercase().as_str() {
+            "notes" => self.notes(),
+            "statement of action" => self.statement_of_action(),
+            "statement of inputs" => self.statement_of_inputs(),
+            "statement of design" => self.statement_of_design(),
+            "analysis of impact" => self.analysis_of_impact(),
+            "children" => self.children(),
+            "log" => self.log(),
+            _ => self.extra_sections
+                .iter()
+                .find(|(heading, _)| heading.eq_ignore_ascii_case(name))
+                .map(|(_, body)| body),
+        }
+    }
+    
+    /// A section split into its `### ` sub-sections; `section` gives the flat text
+    pub fn subsections(&self, name: &str) -> Option<Subsections> {
+        self.section(name).map(|body| Subsections::parse(body))
+    }
+    
+    /// The `### ` and deeper sub-headings inside a section, as `(level, heading)`
+    /// pairs where `### ` is level 3. Headings inside fenced code blocks are ignored.
+    pub fn section_outline(&self, name: &str) -> Vec<(usize, String)> {
+        fn collect(body: &str, level: usize, outline: &mut Vec<(usize, String)>) {
+            if level > 6 {
+                return;
+            }
+            let subsections = Subsections::parse_level(body, level);
+            collect(&subsections.intro, level + 1, outline);
+            for (heading, content) in subsections.entries {
+                outline.push((level, heading));
+                collect(&content, level + 1, outline);
+            }
+        }
+        
+        let mut outline = Vec::new();
+        if let Some(body) = self.section(name) {
+            collect(body, 3, &mut outline);
+        }
+        outline
+    }
+    
+    pub fn is_known_section(name: &str) -> bool {
+        SECTION_NAMES.iter().any(|section| section.eq_ignore_ascii_case(name))
+    }
     
     // Setters
     pub fn set_status(&mut self, status: Status) {
@@ -177,7 +519,60 @@ impl Action {
         self.updated_at = Utc::now();
     }
     
-    pub fn set_priority(&mut self, priority: bool) {
+    pub fn set_lifecycle(&mut self, lifecycle: Lifecycle) {
+        self.lifecycle = lifecycle;
+        self.updated_at = Utc::now();
+    }
+    
+    /// Add `duration` to the time spent, failing if the total would overflow
+    pub fn log_time(&mut self, duration: Duration) -> Result<()> {
+        let total = self.time_spent
+            .unwrap_or_default()
+            .checked_add(&duration)
+            .with_context(|| format!("Time spent on '{}' is too large to add {}", self.title, utils::format_duration(duration)))?;
+        self.time_spent = Some(total);
+        Ok(())
+    }
+    
+    /// Set or clear the due date. Returns a warning, without refusing the
+    /// change, when the date has already passed for an action still in discovery.
+    pub fn set_due(&mut self, due: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<String> {
+        self.due = due;
+        self.updated_at = Utc::now();
+        
+        match due {
+            Some(due) if due < now && self.status == Status::Discovery => Some(format!(
+                "due date {} is already in the past and '{}' hasn't started yet",
+                due.format("%Y-%m-%d %H:%M UTC"),
+                self.title
+            )),
+            _ => None,
+        }
+    }
+    
+    /// Past its due date without reaching the final status. Cancelled actions are never overdue.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        match self.due {
+            Some(due) => now > due && self.status != self.final_status && self.lifecycle != Lifecycle::Cancelled,
+            None => false,
+        }
+    }
+    
+    /// Whether `who` is the assignee, ignoring case; `unassigned` matches
+    /// actions nobody is assigned to
+    pub fn is_assigned_to(&self, who: &str) -> bool {
+        match &self.assignee {
+            Some(assignee) => assignee.eq_ignore_ascii_case(who),
+            None => who.eq_ignore_ascii_case(crate::workspace::UNASSIGNED),
+        }
+    }
+    
+    /// Calendar days (UTC) from `now` until the due date; negative once it has passed
+    pub fn days_until_due(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.due.map(|due| (due.date_naive() - now.date_naive()).num_days())
+    }
+    
+    pub fn set_priority(&mut self, priority: Priority) {
         self.priority = priority;
         self.updated_at = Utc::now();
     }
@@ -189,17 +584,595 @@ impl Action {
             "statement of inputs" => self.statement_of_inputs = content,
             "statement of design" => self.statement_of_design = content,
             "analysis of impact" => self.analysis_of_impact = content,
-            _ => {}
+            "children" => self.children = content,
+            "log" => self.log = content,
+            _ => {
+                let position = self.extra_sections.iter().position(|(heading, _)| heading.eq_ignore_ascii_case(section));
+                match (position, content) {
+                    (Some(index), Some(content)) => self.extra_sections[index].1 = content,
+                    (Some(index), None) => {
+                        self.extra_sections.remove(index);
+                    }
+                    (None, Some(content)) => self.extra_sections.push((section.to_string(), content)),
+                    (None, None) => {}
+                }
+            }
         }
         self.updated_at = Utc::now();
     }
     
+    /// Append a dated, attributed entry to the `Log` section, creating it if needed
+    pub fn append_log(&mut self, author: &str, text: &str, at: DateTime<Utc>) {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let entry = format!("- {} ({}): {}", at.format("%Y-%m-%d"), author, text);
+        
+        self.log = Some(match self.log.take() {
+            Some(log) if !log.trim().is_empty() => format!("{}\n{}", log.trim_end(), entry),
+            _ => entry,
+        });
+        self.updated_at = Utc::now();
+    }
+    
+    /// Entries of the `Log` section written by `append_log`; other lines are skipped
+    pub fn log_entries(&self) -> Vec<LogEntry> {
+        let Some(log) = &self.log else {
+            return Vec::new();
+        };
+        
+        log.lines()
+            .filter_map(|line| {
+                let rest = line.trim().strip_prefix("- ")?;
+                let (date, rest) = rest.split_once(" (")?;
+                let (author, text) = rest.split_once("): ")?;
+                Some(LogEntry {
+                    date: NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?,
+                    author: author.to_string(),
+                    text: text.to_string(),
+                })
+            })
+            .collect()
+    }
+    
+    /// Links to `.md` files in the action's content, unresolved.
+    /// Use `Workspace::resolved_links` to find the actions they point at.
+    pub fn links(&self) -> Vec<ActionLink> {
+        MarkdownParser::new(&self.to_markdown())
+            .extract_labeled_links()
+            .into_iter()
+            .map(|(text, target)| ActionLink { text, target, resolved: None })
+            .collect()
+    }
+    
+    /// `#tags` in the action's content, without the `#`, deduplicated in order of appearance
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in MarkdownParser::new(&self.to_markdown()).extract_tags() {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+    
+    /// Links in the `Children` section, unresolved
+    pub fn child_links(&self) -> Vec<ActionLink> {
+        let Some(children) = &self.children else {
+            return Vec::new();
+        };
+        
+        MarkdownParser::new(children)
+            .extract_labeled_links()
+            .into_iter()
+            .map(|(text, target)| ActionLink { text, target, resolved: None })
+            .collect()
+    }
+    
+    /// Tags written by `to_markdown` itself: `#project`, the project, the status and `#priority`
+    pub fn generated_tags(&self) -> Vec<String> {
+        let mut tags = vec![
+            "project".to_string(),
+            self.project.replace(" ", "-").to_lowercase(),
+            self.status.to_string(),
+        ];
+        if self.priority.is_set() {
+            tags.push("priority".to_string());
+        }
+        tags
+    }
+    
+    /// Whether `tag` would clash with one the tool writes: a generated tag,
+    /// `#priority` (generated once a priority is set), or the `#action`
+    /// marker older templates wrote
+    pub fn is_reserved_tag(&self, tag: &str) -> bool {
+        tag == "action"
+            || tag == "priority"
+            || self.generated_tags().iter().any(|generated| generated == tag)
+    }
+    
+    /// Add a tag to the tag line. Returns false if it was already there.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        if self.tag_line.iter().any(|existing| existing == tag) {
+            return false;
+        }
+        self.tag_line.push(tag.to_string());
+        self.updated_at = Utc::now();
+        true
+    }
+    
+    /// Remove a tag from the tag line. Returns false if it wasn't there.
+    /// The same `#tag` written inside a section is left alone.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tag_line.len();
+        self.tag_line.retain(|existing| existing != tag);
+        if self.tag_line.len() == before {
+            return false;
+        }
+        self.updated_at = Utc::now();
+        true
+    }
+    
+    /// Record a dependency on `target` (`project/title`). Returns false if it was already there.
+    pub fn add_dependency(&mut self, target: &str) -> bool {
+        if self.depends_on.iter().any(|existing| existing == target) {
+            return false;
+        }
+        self.depends_on.push(target.to_string());
+        self.updated_at = Utc::now();
+        true
+    }
+    
+    /// Drop a dependency. Returns false if there was none on `target`.
+    pub fn remove_dependency(&mut self, target: &str) -> bool {
+        let before = self.depends_on.len();
+        self.depends_on.retain(|existing| existing != target);
+        if self.depends_on.len() == before {
+            return false;
+        }
+        self.updated_at = Utc::now();
+        true
+    }
+    
+    /// `tags` without the ones generated from the action's own fields
+    pub fn user_tags(&self) -> Vec<String> {
+        let generated = self.generated_tags();
+        self.tags()
+            .into_iter()
+            .filter(|tag| !generated.contains(tag))
+            .collect()
+    }
+    
     pub fn has_meta_graph(&self) -> bool {
-        let meta_graph_path = self.file_path.with_extension("");
-        meta_graph_path.exists() && meta_graph_path.is_dir()
+        self.meta_graph_path().is_dir()
     }
     
     pub fn meta_graph_path(&self) -> PathBuf {
-        self.file_path.with_extension("")
+        self.meta_graph_layout.path_for(&self.file_path)
+    }
+    
+    /// Files attached to the action: everything at the top of its meta-graph
+    /// except markdown, which holds sub-actions. Sorted by name.
+    pub fn attachments(&self) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(self.meta_graph_path()) else {
+            return Vec::new();
+        };
+        
+        let mut attachments: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().and_then(|s| s.to_str()) != Some("md"))
+            .collect();
+        attachments.sort();
+        attachments
+    }
+}
+
+/// Serde for optional durations as `utils::format_duration` text, matching
+/// the frontmatter
+mod duration_text {
+    use crate::utils;
+    use chrono::Duration;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    
+    pub fn serialize<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_some(&utils::format_duration(*duration)),
+            None => serializer.serialize_none(),
+        }
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| utils::parse_duration(&text).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Sections the action has no field for, in document order. A heading that
+/// repeats is kept once, at its first position, with the body the parser kept.
+fn extra_sections(content: &str, sections: &Sections) -> Vec<(String, String)> {
+    let mut extra: Vec<(String, String)> = Vec::new();
+    for heading in content.lines().filter_map(|line| line.strip_prefix("## ")) {
+        let heading = heading.trim();
+        if SECTION_NAMES.contains(&heading) || extra.iter().any(|(seen, _)| seen == heading) {
+            continue;
+        }
+        if let Some(body) = sections.get(heading) {
+            extra.push((heading.to_string(), body.clone()));
+        }
+    }
+    extra
+}
+
+/// `#tags` between the title and the first section
+fn header_tags(content: &str) -> Vec<String> {
+    let mut lines = content.lines().peekable();
+    if lines.peek().is_some_and(|line| line.trim() == "---") {
+        lines.next();
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+        }
+    }
+    
+    let header: Vec<&str> = lines
+        .take_while(|line| !line.starts_with("## "))
+        .filter(|line| !line.starts_with("# "))
+        .collect();
+    MarkdownParser::new(&header.join("\n")).extract_tags()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    
+    const CANONICAL: &str = "---
+id: 550e8400-e29b-41d4-a716-446655440000
+created_at: 2025-01-15T10:30:00+00:00
+updated_at: 2025-01-15T15:45:00+00:00
+---
+
+# setup
+
+#project #web #discovery
+
+## Notes
+
+Some notes
+
+## Statement of Action
+
+Install the tools
+";
+    
+    const REFORMATTED: &str = "---
+created_at: 2025-01-15T10:30:00+00:00
+id: 550e8400-e29b-41d4-a716-446655440000
+updated_at: 2025-02-01T09:00:00+00:00
+---
+# setup
+#project #web #discovery
+## Notes
+
+
+Some notes   
+
+## Statement of Action
+Install the tools
+
+
+";
+    
+    #[test]
+    fn test_content_hash_is_stable_across_formatting() {
+        let a = Action::from_markdown(CANONICAL, "web/setup.md").unwrap();
+        let b = Action::from_markdown(REFORMATTED, "web/setup.md").unwrap();
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_eq!(a.content_hash(), a.clone().content_hash());
+    }
+    
+    #[test]
+    fn test_content_hash_changes_with_content() {
+        let a = Action::from_markdown(CANONICAL, "web/setup.md").unwrap();
+        let mut b = a.clone();
+        b.update_section("notes", Some("Different notes".to_string()));
+        assert_ne!(a.content_hash(), b.content_hash());
+    }
+    
+    #[test]
+    fn test_synced_hash_round_trip() {
+        let mut action = Action::from_markdown(CANONICAL, "web/setup.md").unwrap();
+        assert!(action.has_unsynced_changes());
+        
+        action.mark_synced();
+        assert!(!action.has_unsynced_changes());
+        
+        let reloaded = Action::from_markdown(&action.to_markdown(), "web/setup.md").unwrap();
+        assert_eq!(reloaded.synced_hash, action.synced_hash);
+        assert!(!reloaded.has_unsynced_changes());
+        
+        action.update_section("notes", None);
+        assert!(action.has_unsynced_changes());
+    }
+    
+    #[test]
+    fn test_unterminated_frontmatter_strict_and_lenient() {
+        let content = "---\nid: 550e8400-e29b-41d4-a716-446655440000\n\n# setup\n\n## Notes\n\nstatus: not metadata\n";
+        
+        assert!(Action::from_markdown_with(content, "web/setup.md", true).is_err());
+        
+        let action = Action::from_markdown(content, "web/setup.md").unwrap();
+        assert_eq!(action.notes().unwrap(), "status: not metadata");
+        assert_eq!(action.status(), &Status::Discovery);
+        assert_eq!(action.parse_warnings.len(), 1);
+        assert!(action.parse_warnings[0].to_string().contains("line 1"));
+    }
+    
+    #[test]
+    fn test_section_outline() {
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        action.update_section("statement of design", Some(
+            "#### Context\n\n### Output\n\ntext\n\n#### Format\n\n```sh\n### not a heading\n#### nor this\n```\n\n###no space\n### Design\n".to_string()
+        ));
+        
+        assert_eq!(action.section_outline("Statement of Design"), [
+            (4, "Context".to_string()),
+            (3, "Output".to_string()),
+            (4, "Format".to_string()),
+            (3, "Design".to_string()),
+        ]);
+        assert!(action.section_outline("Notes").is_empty());
+    }
+    
+    #[test]
+    fn test_subsections_survive_save_and_reload() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        action.file_path = dir.path().join("setup.md");
+        
+        let mut design = Subsections::default();
+        design.entries.push(("Output".to_string(), "A static site".to_string()));
+        design.entries.push(("Design".to_string(), "Use a generator".to_string()));
+        action.update_section("statement of design", Some(design.to_markdown()));
+        action.save().unwrap();
+        
+        let reloaded = Action::from_file(&action.file_path).unwrap();
+        assert_eq!(reloaded.subsections("Statement of Design"), Some(design));
+        assert_eq!(reloaded.subsections("Analysis of Impact"), None);
+    }
+    
+    #[test]
+    fn test_due_dates_and_overdue_boundary() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        assert!(!action.is_overdue(at("2030-01-01T00:00:00Z")));
+        assert_eq!(action.days_until_due(at("2030-01-01T00:00:00Z")), None);
+        
+        let due = parse_due("2025-03-01").unwrap();
+        assert_eq!(due, at("2025-03-01T23:59:59Z"));
+        assert_eq!(parse_due("2025-03-01T09:00:00+02:00").unwrap(), at("2025-03-01T07:00:00Z"));
+        assert!(parse_due("March 1st").is_err());
+        
+        assert_eq!(action.set_due(Some(due), at("2025-02-01T00:00:00Z")), None);
+        assert!(!action.is_overdue(at("2025-03-01T23:59:59Z")));
+        assert!(action.is_overdue(at("2025-03-02T00:00:00Z")));
+        assert_eq!(action.days_until_due(at("2025-02-28T23:59:00Z")), Some(1));
+        assert_eq!(action.days_until_due(at("2025-03-01T00:00:00Z")), Some(0));
+        assert_eq!(action.days_until_due(at("2025-03-02T00:00:00Z")), Some(-1));
+        
+        action.set_status(Status::Published);
+        assert!(!action.is_overdue(at("2025-03-02T00:00:00Z")));
+        
+        let mut fresh = Action::new("web".to_string(), "new".to_string(), Priority::None);
+        let warning = fresh.set_due(Some(due), at("2025-03-05T00:00:00Z")).unwrap();
+        assert!(warning.contains("in the past"), "{}", warning);
+        assert_eq!(fresh.due, Some(due), "a past due date is still set");
+    }
+    
+    #[test]
+    fn test_due_date_round_trips_through_frontmatter() {
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        action.due = Some(parse_due("2025-03-01").unwrap());
+        let reloaded = Action::from_markdown(&action.to_markdown(), "web/setup.md").unwrap();
+        assert_eq!(reloaded.due, action.due);
+        assert!(reloaded.parse_warnings.is_empty());
+        
+        let content = "---\nid: 550e8400-e29b-41d4-a716-446655440000\ndue_at: 2025-04-10\n---\n";
+        let parsed = Action::from_markdown(content, "web/other.md").unwrap();
+        assert_eq!(parsed.due, Some(parse_due("2025-04-10").unwrap()));
+    }
+    
+    #[test]
+    fn test_depends_on_round_trips_through_frontmatter() {
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        assert!(action.add_dependency("api/auth"));
+        assert!(action.add_dependency("web/design doc"));
+        assert!(!action.add_dependency("api/auth"));
+        assert!(action.to_markdown().contains("depends_on: api/auth, web/design doc\n"));
+        
+        let mut reloaded = Action::from_markdown(&action.to_markdown(), "web/setup.md").unwrap();
+        assert_eq!(reloaded.depends_on, ["api/auth", "web/design doc"]);
+        assert!(reloaded.parse_warnings.is_empty());
+        
+        assert!(reloaded.remove_dependency("api/auth"));
+        assert!(!reloaded.remove_dependency("api/auth"));
+        assert_eq!(reloaded.depends_on, ["web/design doc"]);
+    }
+    
+    #[test]
+    fn test_append_log_keeps_entries_in_order() {
+        let mut action = Action::from_markdown(CANONICAL, "web/setup.md").unwrap();
+        let day = |d: u32| Utc.with_ymd_and_hms(2024, 1, d, 12, 0, 0).unwrap();
+        
+        action.append_log("alice", "Started on this", day(2));
+        action.append_log("bob", "Blocked on\nreview", day(3));
+        
+        let reloaded = Action::from_markdown(&action.to_markdown(), "web/setup.md").unwrap();
+        assert_eq!(
+            reloaded.log().unwrap(),
+            "- 2024-01-02 (alice): Started on this\n- 2024-01-03 (bob): Blocked on review"
+        );
+        
+        let mut reloaded = reloaded;
+        reloaded.append_log("carol", "Done", day(4));
+        assert_eq!(reloaded.log().unwrap().lines().count(), 3);
+        assert!(reloaded.log().unwrap().ends_with("- 2024-01-04 (carol): Done"));
+    }
+    
+    #[test]
+    fn test_tag_line_tags_round_trip() {
+        let mut action = Action::new("web app".to_string(), "setup".to_string(), Priority::High);
+        action.set_status(Status::Design);
+        assert!(action.add_tag("frontend"));
+        assert!(!action.add_tag("frontend"));
+        action.add_tag("q3");
+        action.update_section("notes", Some("Mentions #inline".to_string()));
+        
+        let markdown = action.to_markdown();
+        assert!(markdown.contains("#project #web-app #design #priority #frontend #q3\n"), "{}", markdown);
+        
+        let reloaded = Action::from_markdown(&markdown, "ws/web app/setup.md").unwrap();
+        assert_eq!(reloaded.tag_line, ["frontend", "q3"]);
+        
+        let mut reloaded = reloaded;
+        assert!(reloaded.remove_tag("frontend"));
+        assert!(!reloaded.remove_tag("inline"), "section tags are not on the tag line");
+        assert_eq!(reloaded.tag_line, ["q3"]);
+        
+        // Other status names are fine as tags; a stale status in the generated prefix isn't kept
+        assert!(!reloaded.is_reserved_tag("test"));
+        assert!(reloaded.is_reserved_tag("design"));
+        let stale = markdown.replace("#design #priority #frontend", "#implement #priority #test");
+        assert_eq!(Action::from_markdown(&stale, "ws/web app/setup.md").unwrap().tag_line, ["test", "q3"]);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_unknown_sections_survive_save() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("setup.md");
+        let risks = "## Risks\n\n- Vendor lock-in\n- **Timeline** slips\n\n";
+        let glossary = "## Glossary\n\nSSG: static site generator\n\n";
+        let content = format!(
+            "---\nid: 1\n---\n\n# setup\n\n#project #web #pending\n\n{}## Notes\n\nSome notes\n\n{}",
+            risks, glossary
+        );
+        fs::write(&path, content).unwrap();
+        
+        let mut action = Action::from_file(&path).unwrap();
+        assert_eq!(action.extra_sections.iter().map(|(h, _)| h.as_str()).collect::<Vec<_>>(), ["Risks", "Glossary"]);
+        action.set_status(Status::Design);
+        action.save().unwrap();
+        
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains(&format!("## Notes\n\nSome notes\n\n{}{}", risks, glossary)), "{}", saved);
+        let reloaded = Action::from_file(&path).unwrap();
+        assert_eq!(reloaded.extra_sections, action.extra_sections);
+    }
+    
+    #[test]
+    fn test_save_replaces_the_file_atomically() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("setup.md");
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        action.file_path = path.clone();
+        action.save().unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        
+        // A write killed halfway leaves a truncated temp file, not a truncated action
+        let stale = utils::atomic_temp_path(&path);
+        fs::write(&stale, &saved[..saved.len() / 2]).unwrap();
+        assert_eq!(Action::from_file(&path).unwrap().title, "setup");
+        
+        // The next save writes through a temp file of its own
+        action.set_priority(Priority::High);
+        action.save().unwrap();
+        assert_eq!(Action::from_file(&path).unwrap().priority(), Priority::High);
+        let mut left: Vec<PathBuf> = fs::read_dir(dir.path()).unwrap().map(|entry| entry.unwrap().path()).collect();
+        left.sort();
+        let mut expected = vec![path.clone(), stale];
+        expected.sort();
+        assert_eq!(left, expected);
+    }
+    
+    #[test]
+    fn test_time_tracking_round_trips() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("setup.md");
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        action.file_path = path.clone();
+        action.estimate = Some(Duration::hours(1));
+        action.log_time(Duration::minutes(20)).unwrap();
+        action.log_time(Duration::minutes(50)).unwrap();
+        action.save().unwrap();
+        
+        let mut full = Action::new("web".to_string(), "full".to_string(), Priority::None);
+        full.time_spent = Some(Duration::MAX);
+        assert!(full.log_time(Duration::minutes(1)).is_err());
+        assert_eq!(full.time_spent, Some(Duration::MAX));
+        
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("estimate: 1h\ntime_spent: 1h10m\n"), "{}", saved);
+        let mut reloaded = Action::from_file(&path).unwrap();
+        assert_eq!((reloaded.estimate, reloaded.time_spent), (Some(Duration::hours(1)), Some(Duration::minutes(70))));
+        assert!(reloaded.parse_warnings.is_empty());
+        reloaded.save().unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("estimate: 1h\ntime_spent: 1h10m\n"));
+        
+        let json = serde_json::to_value(&reloaded).unwrap();
+        assert_eq!((json["estimate"].as_str(), json["time_spent"].as_str()), (Some("1h"), Some("1h10m")));
+        let decoded: Action = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.estimate, Some(Duration::hours(1)));
+    }
+    
+    #[test]
+    fn test_unknown_frontmatter_keys_survive_save() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("setup.md");
+        fs::write(&path, "---\nid: 1\nepic: onboarding\nauthor: Jane Doe\n---\n\n# setup\n").unwrap();
+        
+        let mut action = Action::from_file(&path).unwrap();
+        assert_eq!(action.extra_metadata.get("author").map(String::as_str), Some("Jane Doe"));
+        action.set_priority(Priority::High);
+        action.save().unwrap();
+        
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("author: Jane Doe\nepic: onboarding\n---"), "{}", saved);
+        let reloaded = Action::from_file(&path).unwrap();
+        assert_eq!(reloaded.extra_metadata, action.extra_metadata);
+        assert!(!reloaded.extra_metadata.contains_key("id"));
+    }
+    
+    #[test]
+    fn test_status_and_priority_survive_reload() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::High);
+        action.file_path = dir.path().join("setup.md");
+        action.set_status(Status::Test);
+        action.save().unwrap();
+        
+        let reloaded = Action::from_file(&action.file_path).unwrap();
+        assert_eq!(reloaded.status(), &Status::Test);
+        assert!(reloaded.is_priority());
+        assert!(reloaded.parse_warnings.is_empty());
+    }
+    
+    #[test]
+    fn test_priority_levels_and_legacy_flag() {
+        let legacy = "---\nid: 1\npriority: true\n---\n\n# setup\n";
+        assert_eq!(Action::from_markdown(legacy, "ws/web/setup.md").unwrap().priority(), Priority::High);
+        let legacy = "---\nid: 1\npriority: false\n---\n\n# setup\n";
+        assert_eq!(Action::from_markdown(legacy, "ws/web/setup.md").unwrap().priority(), Priority::None);
+        let tag_only = "---\nid: 1\n---\n\n# setup\n\n#project #web #discovery #priority\n";
+        assert_eq!(Action::from_markdown(tag_only, "ws/web/setup.md").unwrap().priority(), Priority::High);
+        
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        action.set_priority(Priority::Medium);
+        let markdown = action.to_markdown();
+        assert!(markdown.contains("priority: medium\n"), "{}", markdown);
+        let reloaded = Action::from_markdown(&markdown, "ws/web/setup.md").unwrap();
+        assert_eq!(reloaded.priority(), Priority::Medium);
+        assert!(reloaded.is_priority());
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{CreateOptions, Priority, Workspace};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// One mutation in a batch, tagged by `op`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "op", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum BatchOp {
+    Create {
+        project: String,
+        title: String,
+        /// A level name, or `true`/`false` as before levels existed
+        #[serde(default)]
+        priority: Priority,
+        #[serde(default, rename = "type")]
+        action_type: Option<String>,
+    },
+    Status { project: String, title: String, status: String },
+    Priority { project: String, title: String, priority: Priority },
+    Delete { project: String, title: String },
+    /// Replace a section; a `null` or missing `content` removes it
+    SetSection {
+        project: String,
+        title: String,
+        section: String,
+        #[serde(default)]
+        content: Option<String>,
+    },
+}
+
+/// How an operation ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Ok,
+    Error,
+    /// Not attempted because an earlier operation failed
+    Skipped,
+    /// Applied, then undone because a later operation failed under `atomic`
+    RolledBack,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchResult {
+    pub index: usize,
+    pub outcome: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchOptions {
+    /// Keep going after a failed operation instead of skipping the rest
+    pub continue_on_error: bool,
+    /// Undo every applied operation if any fails
+    pub atomic: bool,
+}
+
+/// Parse a JSON array of operations. Nothing is applied if any is malformed.
+pub fn parse(json: &str) -> Result<Vec<BatchOp>> {
+    serde_json::from_str(json).with_context(|| "Invalid batch: expected a JSON array of operations")
+}
+
+/// Apply `ops` in order while holding the workspace lock, with one result per op.
+///
+/// With `atomic`, the workspace's files are snapshotted first and restored
+/// if any operation fails, after which `workspace` is reloaded from disk.
+pub fn apply(workspace: &mut Workspace, ops: &[BatchOp], options: BatchOptions) -> Result<Vec<BatchResult>> {
+    let _lock = workspace.lock()?;
+    let snapshot = if options.atomic { Some(Snapshot::take(workspace.root())?) } else { None };
+    
+    let mut results = Vec::with_capacity(ops.len());
+    let mut failed = false;
+    for (index, op) in ops.iter().enumerate() {
+        if failed && !options.continue_on_error {
+            results.push(BatchResult { index, outcome: Outcome::Skipped, error: None });
+            continue;
+        }
+        
+        match apply_one(workspace, op) {
+            Ok(()) => results.push(BatchResult { index, outcome: Outcome::Ok, error: None }),
+            Err(e) => {
+                failed = true;
+                results.push(BatchResult { index, outcome: Outcome::Error, error: Some(format!("{:#}", e)) });
+            }
+        }
+    }
+    
+    if let Some(snapshot) = snapshot.filter(|_| failed) {
+        snapshot.restore()?;
+        let read_only = workspace.is_read_only();
+        *workspace = Workspace::load(snapshot.root)?.with_read_only(read_only);
+        for result in results.iter_mut().filter(|result| result.outcome == Outcome::Ok) {
+            result.outcome = Outcome::RolledBack;
+        }
+    }
+    
+    Ok(results)
+}
+
+fn apply_one(workspace: &mut Workspace, op: &BatchOp) -> Result<()> {
+    match op {
+        BatchOp::Create { project, title, priority, action_type } => {
+            let options = CreateOptions { priority: *priority, action_type: action_type.clone(), ..Default::default() };
+            workspace.create_action_with(project, title, &options).map(|_| ())
+        }
+        BatchOp::Status { project, title, status } => {
+            let status = workspace.parse_status(status)?;
+            workspace.update_action_status(project, title, status)
+        }
+        BatchOp::Priority { project, title, priority } => workspace.set_action_priority(project, title, *priority),
+        BatchOp::Delete { project, title } => workspace.delete_action(project, title).map(|_| ()),
+        BatchOp::SetSection { project, title, section, content } => {
+            workspace.set_action_section(project, title, section, content.clone())
+        }
+    }
+}
+
+/// Every file in a workspace outside the state directory, for undoing a batch
+struct Snapshot {
+    root: PathBuf,
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl Snapshot {
+    fn take(root: &Path) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        for path in workspace_files(root) {
+            let content = fs::read(&path)
+                .with_context(|| format!("Failed to snapshot {}", path.display()))?;
+            files.insert(path, content);
+        }
+        Ok(Self { root: root.to_path_buf(), files })
+    }
+    
+    /// Put every snapshotted file back and remove files created since,
+    /// along with directories they leave empty
+    fn restore(&self) -> Result<()> {
+        for path in workspace_files(&self.root) {
+            if !self.files.contains_key(&path) {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to roll back {}", path.display()))?;
+                let mut dir = path.parent();
+                while let Some(parent) = dir.filter(|dir| *dir != self.root) {
+                    if fs::remove_dir(parent).is_err() {
+                        break;
+                    }
+                    dir = parent.parent();
+                }
+            }
+        }
+        
+        for (path, content) in &self.files {
+            if fs::read(path).is_ok_and(|current| &current == content) {
+                continue;
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content)
+                .with_context(|| format!("Failed to roll back {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Files a batch can touch: everything in the project directories, which
+/// holds the action files and their meta-graphs. Files at the top level,
+/// hidden directories like `.action-lite` and any `.git` are left out.
+fn workspace_files(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| match entry.depth() {
+            0 => true,
+            1 => entry.file_type().is_dir() && !entry.file_name().to_string_lossy().starts_with('.'),
+            _ => entry.file_name() != ".git",
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    fn seeded() -> (TempDir, Workspace) {
+        let dir = TempDir::new().unwrap();
+        let mut workspace = Workspace::init(dir.path()).unwrap();
+        workspace.create_action("web", "setup", Priority::None).unwrap();
+        (dir, workspace)
+    }
+    
+    fn outcomes(results: &[BatchResult]) -> Vec<Outcome> {
+        results.iter().map(|result| result.outcome).collect()
+    }
+    
+    const OPS: &str = r#"[
+        {"op": "create", "project": "api", "title": "auth", "priority": true},
+        {"op": "set-section", "project": "web", "title": "setup", "section": "Notes", "content": "Changed"},
+        {"op": "status", "project": "web", "title": "missing", "status": "design"},
+        {"op": "delete", "project": "web", "title": "setup"}
+    ]"#;
+    
+    #[test]
+    fn test_parse_rejects_unknown_ops() {
+        assert_eq!(parse(OPS).unwrap().len(), 4);
+        assert!(parse(r#"[{"op": "rename", "project": "web", "title": "setup"}]"#).is_err());
+        assert!(parse(r#"[{"op": "delete", "project": "web"}]"#).is_err());
+    }
+    
+    #[test]
+    fn test_stop_or_continue_on_error() {
+        let (_dir, mut workspace) = seeded();
+        let results = apply(&mut workspace, &parse(OPS).unwrap(), BatchOptions::default()).unwrap();
+        assert_eq!(outcomes(&results), [Outcome::Ok, Outcome::Ok, Outcome::Error, Outcome::Skipped]);
+        assert!(results[2].error.as_deref().unwrap().contains("missing"));
+        assert!(workspace.get_action("api", "auth").unwrap().is_priority());
+        assert_eq!(workspace.get_action("web", "setup").unwrap().notes().unwrap(), "Changed");
+        
+        let (dir, mut workspace) = seeded();
+        let options = BatchOptions { continue_on_error: true, ..Default::default() };
+        let results = apply(&mut workspace, &parse(OPS).unwrap(), options).unwrap();
+        assert_eq!(outcomes(&results), [Outcome::Ok, Outcome::Ok, Outcome::Error, Outcome::Ok]);
+        assert!(!dir.path().join("web/setup.md").exists());
+    }
+    
+    #[test]
+    fn test_atomic_batch_rolls_back() {
+        let (dir, mut workspace) = seeded();
+        let before = fs::read(dir.path().join("web/setup.md")).unwrap();
+        
+        let options = BatchOptions { atomic: true, ..Default::default() };
+        let results = apply(&mut workspace, &parse(OPS).unwrap(), options).unwrap();
+        assert_eq!(outcomes(&results), [Outcome::RolledBack, Outcome::RolledBack, Outcome::Error, Outcome::Skipped]);
+        
+        assert_eq!(fs::read(dir.path().join("web/setup.md")).unwrap(), before);
+        assert!(!dir.path().join("api").exists(), "directories created by the batch are removed");
+        assert!(workspace.get_action("api", "auth").is_err());
+        assert!(workspace.get_action("web", "setup").is_ok());
+    }
+    
+    #[test]
+    fn test_snapshot_covers_only_project_files() {
+        let (dir, workspace) = seeded();
+        workspace.create_meta_graph("web", "setup").unwrap();
+        fs::write(dir.path().join("web/setup/sub.md"), "# Sub\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "mine").unwrap();
+        fs::create_dir_all(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::create_dir_all(dir.path().join("web/.git")).unwrap();
+        fs::write(dir.path().join("web/.git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        
+        let mut files = workspace_files(dir.path());
+        files.sort();
+        assert_eq!(files, [dir.path().join("web/setup/sub.md"), dir.path().join("web/setup.md")]);
+    }
+    
+    #[test]
+    fn test_batch_needs_the_lock() {
+        let (_dir, mut workspace) = seeded();
+        let _held = workspace.lock().unwrap();
+        let error = apply(&mut workspace, &[], BatchOptions::default()).unwrap_err();
+        assert!(error.to_string().contains("locked"), "{}", error);
+    }
+}
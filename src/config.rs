@@ -0,0 +1,459 @@
+use crate::hooks::Hooks;
+use crate::template::ActionType;
+use crate::workflow::{Workflow, WorkflowStatus};
+use crate::utils;
+use crate::Status;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Name of the workspace marker, which doubles as the settings directory
+pub const STATE_DIR: &str = ".action-lite";
+const CONFIG_FILE: &str = "config.toml";
+const GITIGNORE_FILE: &str = ".gitignore";
+/// Held while a process needs the workspace to itself
+pub const LOCK_FILE: &str = "lock";
+/// Transient files in the state directory that shouldn't be committed.
+/// `config.toml` and `metrics.jsonl` are left tracked.
+pub const IGNORED_STATE: &[&str] = &["lock", "index.json", "journal.jsonl", "*.tmp", "history.log"];
+/// Workspace files that shouldn't be committed: editor leftovers and the
+/// temporary files of interrupted saves
+pub const IGNORED_FILES: &[&str] = &["*.swp", "*~", ".*.tmp"];
+/// strftime pattern used for timestamps in human-readable output
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M %Z";
+/// Settings `action config` can read and write. Tables like `types` and
+/// `hooks` are edited in `config.toml` directly.
+pub const SETTING_KEYS: &[&str] = &[
+    "date_format",
+    "default_editor",
+    "default_status",
+    "auto_commit",
+    "follow_symlinks",
+    "gitignore",
+    "id_scheme",
+    "meta_graph_layout",
+];
+
+/// Workspace settings stored in `.action-lite/config.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Where meta-graph directories live relative to their action file
+    pub meta_graph_layout: MetaGraphLayout,
+    /// Whether symlinked project directories and action files are loaded
+    pub follow_symlinks: bool,
+    /// Custom action types, overriding built-ins of the same name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub types: BTreeMap<String, ActionType>,
+    /// strftime pattern for timestamps shown to people; files always store RFC 3339
+    pub date_format: String,
+    /// Extra entry gates: sections that must be filled in, keyed by the status they guard
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub entry_requirements: BTreeMap<String, Vec<String>>,
+    /// Whether `init` and `migrate` keep `.action-lite/.gitignore` up to date
+    pub gitignore: bool,
+    /// How ids are generated for new actions
+    pub id_scheme: IdScheme,
+    /// Editor for `action edit`, used ahead of `$EDITOR`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_editor: Option<String>,
+    /// Status new actions start at instead of the workflow's first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_status: Option<String>,
+    /// Commit each created action and status or priority change to git
+    pub auto_commit: bool,
+    /// Starting content for sections of new actions, overriding the type's
+    /// placeholder; see `template::expand_placeholders`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub default_sections: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Hooks::is_empty")]
+    pub hooks: Hooks,
+    /// Custom workflow replacing the built-in statuses, in order; see `Config::workflow`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<WorkflowStatus>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            meta_graph_layout: MetaGraphLayout::default(),
+            follow_symlinks: true,
+            types: BTreeMap::new(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            entry_requirements: BTreeMap::new(),
+            gitignore: true,
+            id_scheme: IdScheme::default(),
+            default_editor: None,
+            default_status: None,
+            auto_commit: false,
+            default_sections: BTreeMap::new(),
+            hooks: Hooks::default(),
+            statuses: Vec::new(),
+        }
+    }
+}
+
+/// Placement of an action's meta-graph directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetaGraphLayout {
+    /// `project/foo/` next to `project/foo.md`
+    #[default]
+    Sibling,
+    /// `project/.meta/foo/`, keeping the project directory free of sub-directories
+    Hidden,
+}
+
+/// How new action ids are generated. Existing ids are read as written
+/// whatever the scheme, so it can be changed at any time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdScheme {
+    /// Random UUIDs
+    #[default]
+    Uuid,
+    /// 8 random base32 characters
+    Short,
+    /// `<project>-<n>`, counting up within each project
+    Seq,
+}
+
+#[derive(Error, Debug)]
+#[error("Invalid id scheme: {0}. Valid schemes are: uuid, short, seq")]
+pub struct IdSchemeError(String);
+
+impl FromStr for IdScheme {
+    type Err = IdSchemeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "uuid" => Ok(IdScheme::Uuid),
+            "short" => Ok(IdScheme::Short),
+            "seq" => Ok(IdScheme::Seq),
+            _ => Err(IdSchemeError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MetaGraphLayoutError {
+    #[error("Invalid meta-graph layout: {0}. Valid layouts are: sibling, hidden")]
+    InvalidLayout(String),
+}
+
+impl FromStr for MetaGraphLayout {
+    type Err = MetaGraphLayoutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sibling" => Ok(MetaGraphLayout::Sibling),
+            "hidden" => Ok(MetaGraphLayout::Hidden),
+            _ => Err(MetaGraphLayoutError::InvalidLayout(s.to_string())),
+        }
+    }
+}
+
+impl MetaGraphLayout {
+    /// The meta-graph directory for the action stored at `action_file`
+    pub fn path_for(&self, action_file: &Path) -> PathBuf {
+        match self {
+            MetaGraphLayout::Sibling => action_file.with_extension(""),
+            MetaGraphLayout::Hidden => {
+                let parent = action_file.parent().unwrap_or(Path::new(""));
+                let stem = action_file.file_stem().unwrap_or_default();
+                parent.join(".meta").join(stem)
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Resolve an action type by name, preferring the config over built-ins
+    pub fn action_type(&self, name: &str) -> Result<ActionType> {
+        if let Some(action_type) = self.types.get(name) {
+            return Ok(action_type.clone());
+        }
+        
+        ActionType::builtin(name).with_context(|| {
+            let mut names: Vec<&str> = ActionType::builtin_names().to_vec();
+            names.extend(self.types.keys().map(|s| s.as_str()));
+            names.sort();
+            names.dedup();
+            format!("Unknown action type: {}. Valid types are: {}", name, names.join(", "))
+        })
+    }
+    
+    /// The configured workflow, or the built-in one when no `[[statuses]]` are set.
+    /// Fails if a status name is repeated.
+    pub fn workflow(&self) -> Result<Workflow> {
+        Workflow::from_config(&self.statuses)
+            .with_context(|| "Invalid [[statuses]] in config")
+    }
+    
+    /// Sections that must be filled in to enter `status`: the built-in
+    /// `Status::required_inputs` plus any configured for it
+    pub fn required_inputs(&self, status: &Status) -> Vec<String> {
+        let mut required: Vec<String> = status.required_inputs().iter().map(|s| s.to_string()).collect();
+        let configured = self.entry_requirements
+            .iter()
+            .filter(|(name, _)| &Status::named(name) == status)
+            .flat_map(|(_, sections)| sections.iter().cloned());
+        for section in configured {
+            if !required.contains(&section) {
+                required.push(section);
+            }
+        }
+        required
+    }
+    
+    /// A setting from `SETTING_KEYS` as it's written in the config file,
+    /// or `None` when it's unset
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        check_setting_key(key)?;
+        let table = toml::Table::try_from(self).context("Failed to serialize config")?;
+        Ok(table.get(key).map(|value| match value {
+            toml::Value::String(text) => text.clone(),
+            other => other.to_string(),
+        }))
+    }
+    
+    /// Change a setting, reading `value` as the type the setting holds.
+    /// The config is left as it was if the value doesn't fit.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        check_setting_key(key)?;
+        let mut table = toml::Table::try_from(&*self).context("Failed to serialize config")?;
+        let value = match table.get(key) {
+            Some(toml::Value::Boolean(_)) => toml::Value::Boolean(value.parse().with_context(|| {
+                format!("Invalid value for {}: {}. Expected true or false", key, value)
+            })?),
+            _ => toml::Value::String(value.to_string()),
+        };
+        table.insert(key.to_string(), value);
+        let config: Config = table.try_into().with_context(|| format!("Invalid value for {}", key))?;
+        if !utils::is_valid_date_format(&config.date_format) {
+            anyhow::bail!("Invalid date_format: {}", config.date_format);
+        }
+        if let Some(status) = &config.default_status {
+            config.workflow()?.parse(status)?;
+        }
+        *self = config;
+        Ok(())
+    }
+    
+    /// Put a setting back to its default
+    pub fn unset(&mut self, key: &str) -> Result<()> {
+        check_setting_key(key)?;
+        let mut table = toml::Table::try_from(&*self).context("Failed to serialize config")?;
+        table.remove(key);
+        *self = table.try_into().context("Failed to reset config")?;
+        Ok(())
+    }
+    
+    /// Where the config for the workspace at `root` is stored
+    pub fn path<P: AsRef<Path>>(root: P) -> PathBuf {
+        root.as_ref().join(STATE_DIR).join(CONFIG_FILE)
+    }
+    
+    /// Load the workspace config, falling back to defaults when none is present
+    pub fn load<P: AsRef<Path>>(root: P) -> Result<Self> {
+        let path = Self::path(root);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    }
+    
+    pub fn save<P: AsRef<Path>>(&self, root: P) -> Result<()> {
+        let path = ensure_state_dir(root)?.join(CONFIG_FILE);
+        let content = toml::to_string_pretty(self)
+            .with_context(|| "Failed to serialize config")?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write config file: {}", path.display()))
+    }
+}
+
+fn check_setting_key(key: &str) -> Result<()> {
+    if !SETTING_KEYS.contains(&key) {
+        anyhow::bail!("Unknown setting: {}. Valid settings are: {}", key, SETTING_KEYS.join(", "));
+    }
+    Ok(())
+}
+
+/// Make sure `.action-lite` is a directory, upgrading a legacy marker file
+pub fn ensure_state_dir<P: AsRef<Path>>(root: P) -> Result<PathBuf> {
+    let dir = root.as_ref().join(STATE_DIR);
+    
+    if dir.is_file() {
+        fs::remove_file(&dir)
+            .with_context(|| format!("Failed to replace legacy marker file: {}", dir.display()))?;
+    }
+    
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create workspace directory: {}", dir.display()))?;
+    }
+    
+    Ok(dir)
+}
+
+/// Make sure `.action-lite/.gitignore` lists every `IGNORED_STATE` entry.
+/// Missing entries are appended, leaving anything the user wrote in place.
+/// Returns whether the file was written.
+pub fn ensure_gitignore<P: AsRef<Path>>(root: P) -> Result<bool> {
+    let path = ensure_state_dir(root)?.join(GITIGNORE_FILE);
+    ensure_ignored(&path, "# Transient action-lite state\n", IGNORED_STATE)
+}
+
+/// Add whichever `IGNORED_FILES` are missing to the workspace's top-level
+/// `.gitignore`. Returns whether the file changed.
+pub fn ensure_root_gitignore<P: AsRef<Path>>(root: P) -> Result<bool> {
+    let path = root.as_ref().join(GITIGNORE_FILE);
+    ensure_ignored(&path, "# Editor and interrupted-save leftovers\n", IGNORED_FILES)
+}
+
+/// Append the `entries` missing from the ignore file at `path`, starting a
+/// new file with `header`
+fn ensure_ignored(path: &Path, header: &str, entries: &[&str]) -> Result<bool> {
+    let mut content = if path.is_file() {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+    } else {
+        header.to_string()
+    };
+    
+    let missing: Vec<&str> = entries
+        .iter()
+        .copied()
+        .filter(|entry| !content.lines().any(|line| line.trim() == *entry))
+        .collect();
+    if missing.is_empty() {
+        return Ok(false);
+    }
+    
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for entry in missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
+    fs::write(path, content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_meta_graph_layout_paths() {
+        let file = Path::new("ws/web/setup.md");
+        assert_eq!(MetaGraphLayout::Sibling.path_for(file), PathBuf::from("ws/web/setup"));
+        assert_eq!(MetaGraphLayout::Hidden.path_for(file), PathBuf::from("ws/web/.meta/setup"));
+    }
+    
+    #[test]
+    fn test_config_round_trip_and_legacy_marker() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(STATE_DIR), "# Action Lite Workspace\n").unwrap();
+        assert_eq!(Config::load(dir.path()).unwrap(), Config::default());
+        
+        let config = Config { meta_graph_layout: MetaGraphLayout::Hidden, ..Default::default() };
+        config.save(dir.path()).unwrap();
+        assert!(dir.path().join(STATE_DIR).is_dir());
+        assert_eq!(Config::load(dir.path()).unwrap(), config);
+    }
+    
+    #[test]
+    fn test_get_and_set_settings() {
+        let mut config = Config::default();
+        assert_eq!(config.get("date_format").unwrap().as_deref(), Some(DEFAULT_DATE_FORMAT));
+        assert_eq!(config.get("follow_symlinks").unwrap().as_deref(), Some("true"));
+        assert_eq!(config.get("default_editor").unwrap(), None);
+        assert!(config.get("types").is_err());
+        
+        config.set("default_editor", "vim").unwrap();
+        config.set("follow_symlinks", "false").unwrap();
+        config.set("id_scheme", "seq").unwrap();
+        assert_eq!(config.default_editor.as_deref(), Some("vim"));
+        assert!(!config.follow_symlinks);
+        assert_eq!(config.id_scheme, IdScheme::Seq);
+        
+        let before = config.clone();
+        assert!(config.set("follow_symlinks", "yes").is_err());
+        assert!(config.set("id_scheme", "random").is_err());
+        assert!(config.set("date_format", "%Q").is_err());
+        assert!(config.set("default_status", "shipping").is_err());
+        assert_eq!(config, before, "a rejected value changes nothing");
+        
+        config.set("default_status", "design").unwrap();
+        config.unset("default_editor").unwrap();
+        config.unset("follow_symlinks").unwrap();
+        assert_eq!((config.default_editor.as_deref(), config.follow_symlinks), (None, true));
+        assert_eq!(config.default_status.as_deref(), Some("design"));
+    }
+    
+    #[test]
+    fn test_custom_action_types() {
+        let config: Config = toml::from_str(r#"
+            [types.spike]
+            sections = ["Notes"]
+            required = ["Notes"]
+            
+            [types.bug]
+            sections = ["Notes", "Statement of Action"]
+            placeholders = { "Statement of Action" = "Steps to reproduce" }
+        "#).unwrap();
+        
+        let spike = config.action_type("spike").unwrap();
+        assert_eq!(spike.sections, ["Notes"]);
+        assert_eq!(spike.placeholder("notes"), "General notes on the task");
+        
+        let bug = config.action_type("bug").unwrap();
+        assert!(bug.required.is_empty());
+        assert_eq!(bug.placeholder("Statement of Action"), "Steps to reproduce");
+        
+        assert!(config.action_type("feature").is_ok());
+        assert!(config.required_inputs(&Status::Implement).is_empty());
+        let err = config.action_type("epic").unwrap_err().to_string();
+        assert!(err.contains("action, bug, feature, research, spike"), "{}", err);
+    }
+    
+    #[test]
+    fn test_entry_requirements() {
+        let config: Config = toml::from_str(r#"
+            [entry_requirements]
+            Implement = ["Statement of Design"]
+            publish = ["Analysis of Impact", "Notes"]
+        "#).unwrap();
+        
+        assert_eq!(config.required_inputs(&Status::Implement), ["Statement of Design"]);
+        assert_eq!(config.required_inputs(&Status::Publish), ["Analysis of Impact", "Notes"]);
+        assert!(config.required_inputs(&Status::Test).is_empty());
+    }
+    
+    #[test]
+    fn test_ensure_gitignore_only_adds_missing_entries() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(STATE_DIR).join(GITIGNORE_FILE);
+        
+        assert!(ensure_gitignore(dir.path()).unwrap());
+        let created = fs::read_to_string(&path).unwrap();
+        assert!(IGNORED_STATE.iter().all(|entry| created.lines().any(|line| line == *entry)));
+        assert!(!ensure_gitignore(dir.path()).unwrap(), "nothing left to add");
+        
+        fs::write(&path, "scratch/\n  lock\nindex.json").unwrap();
+        assert!(ensure_gitignore(dir.path()).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "scratch/\n  lock\nindex.json\njournal.jsonl\n*.tmp\nhistory.log\n");
+    }
+}
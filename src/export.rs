@@ -0,0 +1,425 @@
+use crate::{Action, Status, Workflow, Workspace};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use tera::Tera;
+use thiserror::Error;
+
+/// JSON shape of an action for scripts: the stored fields plus values
+/// derived from them, so consumers don't have to re-implement the tool's logic.
+/// Serialize `Action` directly for the exact stored shape.
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionView<'a> {
+    #[serde(flatten)]
+    pub action: &'a Action,
+    /// Action file relative to the workspace root
+    pub file_path: String,
+    pub is_overdue: bool,
+    /// Whole days since the action was created
+    pub age_days: i64,
+    pub has_meta_graph: bool,
+    /// User tags (see `Action::user_tags`)
+    pub tags: Vec<String>,
+    pub next_status: Option<Status>,
+    pub content_hash: String,
+}
+
+impl<'a> ActionView<'a> {
+    pub fn new(action: &'a Action, root: &Path, workflow: &Workflow, now: DateTime<Utc>) -> Self {
+        Self {
+            action,
+            file_path: crate::utils::format_relative_path(&action.file_path, root),
+            is_overdue: action.is_overdue(now),
+            age_days: (now - action.created_at).num_days(),
+            has_meta_graph: action.has_meta_graph(),
+            tags: action.user_tags(),
+            next_status: workflow.next(action.status()),
+            content_hash: action.content_hash(),
+        }
+    }
+}
+
+/// An action as seen by export templates: its stored fields plus derived values
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionContext {
+    pub id: String,
+    pub title: String,
+    pub project: String,
+    pub status: String,
+    /// Whether any priority level is set, so `{% if a.priority %}` keeps working
+    pub priority: bool,
+    /// `none`, `low`, `medium` or `high`
+    pub priority_level: String,
+    #[serde(rename = "type")]
+    pub action_type: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Whole days since the action was created
+    pub age_days: i64,
+    /// File path relative to the workspace root
+    pub path: String,
+    /// Section bodies keyed by section name, present sections only
+    pub sections: BTreeMap<String, String>,
+    pub tags: Vec<String>,
+    pub links: Vec<LinkContext>,
+}
+
+/// A link from an action, with the action it resolves to if any
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkContext {
+    pub text: String,
+    pub target: String,
+    pub project: Option<String>,
+    pub title: Option<String>,
+}
+
+impl ActionContext {
+    pub fn new(workspace: &Workspace, action: &Action, now: DateTime<Utc>) -> Self {
+        let sections = crate::action::SECTION_NAMES
+            .iter()
+            .filter_map(|name| action.section(name).map(|body| (name.to_string(), body.clone())))
+            .collect();
+        
+        let links = workspace
+            .resolved_links(action)
+            .into_iter()
+            .map(|link| LinkContext {
+                text: link.text,
+                target: link.target,
+                project: link.resolved.as_ref().map(|t| t.project.clone()),
+                title: link.resolved.map(|t| t.title),
+            })
+            .collect();
+        
+        Self {
+            id: action.id.to_string(),
+            title: action.title().to_string(),
+            project: action.project().to_string(),
+            status: action.status().to_string(),
+            priority: action.is_priority(),
+            priority_level: action.priority().to_string(),
+            action_type: action.action_type().to_string(),
+            created_at: action.created_at,
+            updated_at: action.updated_at,
+            age_days: (now - action.created_at).num_days(),
+            path: crate::utils::format_relative_path(&action.file_path, workspace.root()),
+            sections,
+            tags: action.tags(),
+            links,
+        }
+    }
+}
+
+/// Render actions through a Tera template file.
+///
+/// The template sees `actions` (every action) and `generated_at`. With
+/// `each`, it is instead rendered once per action with that action bound
+/// to `action`, and the results are concatenated.
+pub fn render_template(
+    workspace: &Workspace,
+    actions: &[&Action],
+    template: &Path,
+    each: bool,
+) -> Result<String> {
+    let source = fs::read_to_string(template)
+        .with_context(|| format!("Failed to read template: {}", template.display()))?;
+    let name = template.display().to_string();
+    
+    let mut tera = Tera::default();
+    tera.add_raw_template(&name, &source)
+        .map_err(flatten_tera_error)
+        .with_context(|| format!("Invalid template: {}", name))?;
+    
+    let now = Utc::now();
+    let contexts: Vec<ActionContext> = actions
+        .iter()
+        .map(|action| ActionContext::new(workspace, action, now))
+        .collect();
+    
+    let mut context = tera::Context::new();
+    context.insert("generated_at", &now);
+    
+    let render = |context: &tera::Context| {
+        tera.render(&name, context)
+            .map_err(flatten_tera_error)
+            .with_context(|| format!("Failed to render template: {}", name))
+    };
+    
+    if !each {
+        context.insert("actions", &contexts);
+        return render(&context);
+    }
+    
+    let mut output = String::new();
+    for action in &contexts {
+        context.insert("action", action);
+        output.push_str(&render(&context)?);
+    }
+    Ok(output)
+}
+
+/// Whole-workspace dump format for `export --format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One document: `{"projects": {"<name>": {"description", "actions"}}}`
+    Json,
+    /// One action per line, for streaming
+    Ndjson,
+}
+
+#[derive(Error, Debug)]
+pub enum ExportFormatError {
+    #[error("Invalid export format: {0}. Valid formats are: json, ndjson")]
+    InvalidFormat(String),
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportFormatError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "ndjson" | "jsonl" => Ok(ExportFormat::Ndjson),
+            _ => Err(ExportFormatError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+/// The workspace as written by `export --format json`, keyed by project name
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WorkspaceExport<'a> {
+    pub projects: BTreeMap<String, ProjectExport<'a>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectExport<'a> {
+    /// First paragraph of the project README, if any
+    pub description: Option<String>,
+    pub actions: Vec<ExportedAction<'a>>,
+}
+
+/// An action's stored fields plus its tag-line tags, which `Action` leaves
+/// out of its serialized form but an import needs to restore the file
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedAction<'a> {
+    #[serde(flatten)]
+    pub action: &'a Action,
+    pub tags: &'a [String],
+}
+
+impl<'a> From<&'a Action> for ExportedAction<'a> {
+    fn from(action: &'a Action) -> Self {
+        Self { action, tags: &action.tag_line }
+    }
+}
+
+/// Projects and actions read back from `export --format` output
+#[derive(Debug, Clone, Default)]
+pub struct ImportData {
+    /// Every project mentioned, with its description if the export had one
+    pub projects: BTreeMap<String, Option<String>>,
+    pub actions: Vec<Action>,
+}
+
+#[derive(Deserialize)]
+struct ImportedWorkspace {
+    projects: BTreeMap<String, ImportedProject>,
+}
+
+#[derive(Deserialize)]
+struct ImportedProject {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    actions: Vec<ImportedAction>,
+}
+
+#[derive(Deserialize)]
+struct ImportedAction {
+    #[serde(flatten)]
+    action: Action,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl From<ImportedAction> for Action {
+    fn from(imported: ImportedAction) -> Self {
+        let mut action = imported.action;
+        action.tag_line = imported.tags;
+        action
+    }
+}
+
+/// Parse the output of `export --format json` or `--format ndjson`. A JSON
+/// document with a `projects` key is a full export; anything else is read
+/// as one action per line. Statuses are not checked here.
+pub fn parse_import(content: &str) -> Result<ImportData> {
+    let mut data = ImportData::default();
+    
+    if let Ok(serde_json::Value::Object(document)) = serde_json::from_str(content) {
+        if document.contains_key("projects") {
+            let export: ImportedWorkspace = serde_json::from_value(serde_json::Value::Object(document))
+                .context("Invalid export: expected the output of `export --format json`")?;
+            for (name, project) in export.projects {
+                for imported in project.actions {
+                    let mut action = Action::from(imported);
+                    action.project = name.clone();
+                    data.actions.push(action);
+                }
+                data.projects.insert(name, project.description);
+            }
+            return Ok(data);
+        }
+    }
+    
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let imported: ImportedAction = serde_json::from_str(line)
+            .with_context(|| format!("Invalid action on line {}", index + 1))?;
+        let action = Action::from(imported);
+        data.projects.entry(action.project.clone()).or_default();
+        data.actions.push(action);
+    }
+    Ok(data)
+}
+
+/// Tera keeps the useful part of its errors (including line and column for
+/// syntax errors) in the source chain; fold it into a single message.
+fn flatten_tera_error(error: tera::Error) -> anyhow::Error {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(&error);
+    while let Some(cause) = source {
+        message.push_str(&format!("\n{}", cause));
+        source = cause.source();
+    }
+    anyhow::anyhow!(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActionFilter, Priority};
+    use tempfile::TempDir;
+    
+    fn setup() -> (TempDir, Workspace) {
+        let dir = TempDir::new().unwrap();
+        let mut workspace = Workspace::init(dir.path()).unwrap();
+        workspace.create_action("web", "setup", Priority::High).unwrap();
+        workspace.create_action("web", "deploy", Priority::None).unwrap();
+        (dir, workspace)
+    }
+    
+    #[test]
+    fn test_render_list_and_each() {
+        let (dir, workspace) = setup();
+        let actions = workspace.list_actions(None, None, false).unwrap();
+        
+        let list = dir.path().join("list.tera");
+        fs::write(&list, "{% for a in actions %}{{ a.project }}/{{ a.title }}:{{ a.status }}{% if a.priority %}!{% endif %} {% endfor %}").unwrap();
+        assert_eq!(
+            render_template(&workspace, &actions, &list, false).unwrap(),
+            "web/setup:discovery! web/deploy:discovery "
+        );
+        
+        let each = dir.path().join("each.tera");
+        fs::write(&each, "{{ action.path }} {{ action.age_days }} {{ action.tags | join(sep=\",\") }}\n").unwrap();
+        assert_eq!(
+            render_template(&workspace, &actions, &each, true).unwrap(),
+            "web/setup.md 0 project,web,discovery,priority\nweb/deploy.md 0 project,web,discovery\n"
+        );
+    }
+    
+    #[test]
+    fn test_action_view_adds_computed_fields() {
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        action.file_path = "/ws/web/setup.md".into();
+        action.created_at = Utc::now() - chrono::Duration::days(3);
+        action.due = Some(Utc::now() - chrono::Duration::days(1));
+        action.update_section("notes", Some("#ui".to_string()));
+        
+        action.add_tag("q3");
+        
+        let rendered = serde_json::to_string(&ActionView::new(&action, Path::new("/ws"), &Workflow::builtin(), Utc::now())).unwrap();
+        assert_eq!(rendered.matches("\"tags\"").count(), 1, "{}", rendered);
+        let json: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(json["title"], "setup");
+        assert_eq!(json["file_path"], "web/setup.md");
+        assert_eq!(json["status"], "discovery");
+        assert_eq!(json["is_overdue"], true);
+        assert_eq!(json["age_days"], 3);
+        assert_eq!(json["has_meta_graph"], false);
+        assert_eq!(json["tags"], serde_json::json!(["q3", "ui"]));
+        assert_eq!(json["next_status"], "design");
+        assert_eq!(json["content_hash"], action.content_hash());
+    }
+    
+    #[test]
+    fn test_template_syntax_error_reports_position() {
+        let (dir, workspace) = setup();
+        let template = dir.path().join("bad.tera");
+        fs::write(&template, "ok\n{% for a in actions %}\n{{ a.title }\n").unwrap();
+        
+        let error = render_template(&workspace, &[], &template, false).unwrap_err();
+        let message = format!("{:#}", error);
+        assert!(message.contains("bad.tera"), "{}", message);
+        assert!(message.contains("3:"), "{}", message);
+    }
+    
+    #[test]
+    fn test_export_json_and_ndjson() {
+        let (_dir, mut workspace) = setup();
+        workspace.create_action("api", "auth", Priority::None).unwrap();
+        
+        let json = workspace.export(ExportFormat::Json, &ActionFilter::default()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let titles: Vec<&str> = value["projects"]["web"]["actions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|action| action["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles, ["setup", "deploy"]);
+        assert_eq!(value["projects"]["api"]["actions"][0]["status"], "discovery");
+        
+        let filter = ActionFilter { project: Some("web".to_string()), ..Default::default() };
+        let ndjson = workspace.export(ExportFormat::Ndjson, &filter).unwrap();
+        let lines: Vec<serde_json::Value> = ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["project"], "web");
+        
+        assert_eq!("NDJSON".parse::<ExportFormat>().unwrap(), ExportFormat::Ndjson);
+        assert!("csv".parse::<ExportFormat>().is_err());
+    }
+    
+    #[test]
+    fn test_import_round_trips_an_export() {
+        let (_dir, mut workspace) = setup();
+        workspace.add_action_tag("web", "setup", "frontend").unwrap();
+        workspace.update_action_status("web", "deploy", Status::Design).unwrap();
+        let original = workspace.get_action("web", "setup").unwrap().to_markdown();
+        let json = workspace.export(ExportFormat::Json, &ActionFilter::default()).unwrap();
+        let ndjson = workspace.export(ExportFormat::Ndjson, &ActionFilter::default()).unwrap();
+        
+        let restored_dir = TempDir::new().unwrap();
+        let mut restored = Workspace::init(restored_dir.path()).unwrap();
+        let summary = restored.import(parse_import(&json).unwrap(), false).unwrap();
+        assert_eq!((summary.projects_created, summary.actions_created), (1, 2));
+        assert_eq!(fs::read_to_string(restored_dir.path().join("web/setup.md")).unwrap(), original);
+        assert_eq!(restored.get_action("web", "deploy").unwrap().status(), &Status::Design);
+        
+        let error = restored.import(parse_import(&ndjson).unwrap(), false).unwrap_err();
+        assert!(error.to_string().contains("already exists"), "{}", error);
+        let summary = restored.import(parse_import(&ndjson).unwrap(), true).unwrap();
+        assert_eq!((summary.projects_created, summary.actions_overwritten), (0, 2));
+        
+        let bad = ndjson.replace("\"design\"", "\"shipping\"");
+        let error = restored.import(parse_import(&bad).unwrap(), true).unwrap_err();
+        assert!(format!("{:#}", error).contains("Invalid status: shipping"), "{:#}", error);
+    }
+}
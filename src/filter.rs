@@ -0,0 +1,426 @@
+//! A small boolean query language for selecting actions, e.g.
+//! `status:design and priority` or `project:web and not (status:published or tag:wontfix)`.
+//!
+//! Terms are `priority` or `field:value` with fields `status`, `project`,
+//! `priority`, `tag` and `assignee`. Values containing spaces can be quoted.
+//! `priority` takes a level (`priority:high`) and also compares with `>`,
+//! `>=`, `<` and `<=` (`priority>=medium`); bare `priority`, `priority:true`
+//! and `priority:false` ask whether any level is set.
+//! Terms combine with `and`, `or`, `not` and parentheses; `and` binds
+//! tighter than `or`. Status names are checked against a `Workflow`;
+//! `from_str` uses the built-in one.
+
+use crate::{Action, Priority, Status, Workflow};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A parsed filter expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterExpr {
+    Status(Status),
+    Project(String),
+    Priority(Comparison, Priority),
+    Tag(String),
+    Assignee(String),
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+/// How a `priority` term compares an action's level with the term's
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `:`
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Equal => ordering.is_eq(),
+            Comparison::Greater => ordering.is_gt(),
+            Comparison::GreaterOrEqual => ordering.is_ge(),
+            Comparison::Less => ordering.is_lt(),
+            Comparison::LessOrEqual => ordering.is_le(),
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Comparison::Equal => ":",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+        };
+        f.write_str(symbol)
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("Unexpected '{token}' at position {position}")]
+    UnexpectedToken { token: String, position: usize },
+    #[error("Unexpected end of filter, expected {expected}")]
+    UnexpectedEnd { expected: &'static str },
+    #[error("Unknown field '{field}' at position {position}. Valid fields are: status, project, priority, tag, assignee")]
+    UnknownField { field: String, position: usize },
+    #[error("Invalid value '{value}' for {field} at position {position}")]
+    InvalidValue { field: String, value: String, position: usize },
+    #[error("Unterminated quote at position {position}")]
+    UnterminatedQuote { position: usize },
+    #[error("Field '{field}' at position {position} can't be compared with '{comparison}'; use ':'")]
+    UnsupportedComparison { field: String, comparison: Comparison, position: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    /// A bare word or `field:value` pair; `field>=value` and friends carry
+    /// their comparison
+    Term(String, Option<(Comparison, String)>),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    /// 1-based character position of the token in the expression
+    position: usize,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FilterError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    
+    while i < chars.len() {
+        let c = chars[i];
+        let position = i + 1;
+        
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        
+        if c == '(' || c == ')' {
+            let kind = if c == '(' { TokenKind::LParen } else { TokenKind::RParen };
+            tokens.push(Token { kind, text: c.to_string(), position });
+            i += 1;
+            continue;
+        }
+        
+        let start = i;
+        let mut name = String::new();
+        let mut value: Option<(Comparison, String)> = None;
+        
+        while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '(' && chars[i] != ')' {
+            let c = chars[i];
+            match &mut value {
+                None if c == ':' => value = Some((Comparison::Equal, String::new())),
+                None if c == '>' || c == '<' => {
+                    let or_equal = chars.get(i + 1) == Some(&'=');
+                    let comparison = match (c, or_equal) {
+                        ('>', false) => Comparison::Greater,
+                        ('>', true) => Comparison::GreaterOrEqual,
+                        ('<', false) => Comparison::Less,
+                        _ => Comparison::LessOrEqual,
+                    };
+                    if or_equal {
+                        i += 1;
+                    }
+                    value = Some((comparison, String::new()));
+                }
+                None => name.push(c),
+                Some((_, value)) if c == '"' => {
+                    let quote = i + 1;
+                    i += 1;
+                    while i < chars.len() && chars[i] != '"' {
+                        value.push(chars[i]);
+                        i += 1;
+                    }
+                    if i == chars.len() {
+                        return Err(FilterError::UnterminatedQuote { position: quote });
+                    }
+                }
+                Some((_, value)) => value.push(c),
+            }
+            i += 1;
+        }
+        
+        let text: String = chars[start..i].iter().collect();
+        let kind = match (name.to_lowercase().as_str(), &value) {
+            ("and", None) => TokenKind::And,
+            ("or", None) => TokenKind::Or,
+            ("not", None) => TokenKind::Not,
+            _ => TokenKind::Term(name, value),
+        };
+        tokens.push(Token { kind, text, position });
+    }
+    
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    workflow: &'a Workflow,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+    
+    fn next(&mut self, expected: &'static str) -> Result<Token, FilterError> {
+        let token = self.tokens.get(self.pos).cloned()
+            .ok_or(FilterError::UnexpectedEnd { expected })?;
+        self.pos += 1;
+        Ok(token)
+    }
+    
+    fn parse_or(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut expr = self.parse_and()?;
+        while matches!(self.peek(), Some(Token { kind: TokenKind::Or, .. })) {
+            self.pos += 1;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+    
+    fn parse_and(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut expr = self.parse_unary()?;
+        while matches!(self.peek(), Some(Token { kind: TokenKind::And, .. })) {
+            self.pos += 1;
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+    
+    fn parse_unary(&mut self) -> Result<FilterExpr, FilterError> {
+        let token = self.next("a term")?;
+        match token.kind {
+            TokenKind::Not => Ok(FilterExpr::Not(Box::new(self.parse_unary()?))),
+            TokenKind::LParen => {
+                let expr = self.parse_or()?;
+                let close = self.next("')'")?;
+                if close.kind != TokenKind::RParen {
+                    return Err(unexpected(close));
+                }
+                Ok(expr)
+            }
+            TokenKind::Term(name, value) => parse_term(&name, value, token.position, self.workflow),
+            _ => Err(unexpected(token)),
+        }
+    }
+}
+
+fn unexpected(token: Token) -> FilterError {
+    FilterError::UnexpectedToken { token: token.text, position: token.position }
+}
+
+fn parse_term(name: &str, value: Option<(Comparison, String)>, position: usize, workflow: &Workflow) -> Result<FilterExpr, FilterError> {
+    let field = name.to_lowercase();
+    let invalid = |value: &str| FilterError::InvalidValue {
+        field: field.clone(),
+        value: value.to_string(),
+        position,
+    };
+    
+    let Some((comparison, value)) = value else {
+        return match field.as_str() {
+            "priority" => Ok(FilterExpr::Priority(Comparison::Greater, Priority::None)),
+            "status" | "project" | "tag" | "assignee" => Err(invalid("")),
+            _ => Err(FilterError::UnknownField { field, position }),
+        };
+    };
+    
+    if value.is_empty() {
+        return Err(invalid(""));
+    }
+    
+    if field == "priority" {
+        return match (comparison, value.to_lowercase().as_str()) {
+            (Comparison::Equal, "true" | "yes") => Ok(FilterExpr::Priority(Comparison::Greater, Priority::None)),
+            (Comparison::Equal, "false" | "no") => Ok(FilterExpr::Priority(Comparison::Equal, Priority::None)),
+            (_, "true" | "yes" | "false" | "no") => Err(invalid(&value)),
+            _ => value.parse()
+                .map(|level| FilterExpr::Priority(comparison, level))
+                .map_err(|_| invalid(&value)),
+        };
+    }
+    
+    if comparison != Comparison::Equal && matches!(field.as_str(), "status" | "project" | "tag" | "assignee") {
+        return Err(FilterError::UnsupportedComparison { field, comparison, position });
+    }
+    
+    match field.as_str() {
+        "status" => workflow.parse(&value).map(FilterExpr::Status).map_err(|_| invalid(&value)),
+        "project" => Ok(FilterExpr::Project(value)),
+        "tag" => Ok(FilterExpr::Tag(value.trim_start_matches('#').to_string())),
+        "assignee" => Ok(FilterExpr::Assignee(value)),
+        _ => Err(FilterError::UnknownField { field, position }),
+    }
+}
+
+impl FromStr for FilterExpr {
+    type Err = FilterError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FilterExpr::parse_with(s, &Workflow::builtin())
+    }
+}
+
+impl FilterExpr {
+    /// Parse a filter whose `status:` terms name statuses of `workflow`
+    pub fn parse_with(s: &str, workflow: &Workflow) -> Result<Self, FilterError> {
+        let mut parser = Parser { tokens: tokenize(s)?, pos: 0, workflow };
+        let expr = parser.parse_or()?;
+        match parser.tokens.get(parser.pos).cloned() {
+            Some(token) => Err(unexpected(token)),
+            None => Ok(expr),
+        }
+    }
+    
+    pub fn matches(&self, action: &Action) -> bool {
+        match self {
+            FilterExpr::Status(status) => action.status() == status,
+            FilterExpr::Project(project) => action.project() == project,
+            FilterExpr::Priority(comparison, level) => comparison.holds(action.priority().cmp(level)),
+            FilterExpr::Tag(tag) => action.tags().iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            FilterExpr::Assignee(assignee) => action.is_assigned_to(assignee),
+            FilterExpr::Not(expr) => !expr.matches(action),
+            FilterExpr::And(left, right) => left.matches(action) && right.matches(action),
+            FilterExpr::Or(left, right) => left.matches(action) || right.matches(action),
+        }
+    }
+}
+
+impl fmt::Display for FilterExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FilterExpr::Status(status) => write!(f, "status:{}", status),
+            FilterExpr::Project(project) => write!(f, "project:{:?}", project),
+            FilterExpr::Priority(comparison, level) => write!(f, "priority{}{}", comparison, level),
+            FilterExpr::Tag(tag) => write!(f, "tag:{:?}", tag),
+            FilterExpr::Assignee(assignee) => write!(f, "assignee:{:?}", assignee),
+            FilterExpr::Not(expr) => write!(f, "not {}", expr),
+            FilterExpr::And(left, right) => write!(f, "({} and {})", left, right),
+            FilterExpr::Or(left, right) => write!(f, "({} or {})", left, right),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn action(project: &str, status: Status, priority: bool) -> Action {
+        leveled(project, status, Priority::from(priority))
+    }
+    
+    fn leveled(project: &str, status: Status, priority: Priority) -> Action {
+        let mut action = Action::new(project.to_string(), "Task".to_string(), priority);
+        action.set_status(status);
+        action
+    }
+    
+    #[test]
+    fn test_precedence_and_grouping() {
+        let expr = FilterExpr::from_str("status:design or project:web and priority").unwrap();
+        assert_eq!(expr.to_string(), "(status:design or (project:\"web\" and priority>none))");
+        
+        let expr = FilterExpr::from_str("(status:design or project:web) and not priority").unwrap();
+        assert_eq!(expr.to_string(), "((status:design or project:\"web\") and not priority>none)");
+        
+        let expr = FilterExpr::from_str("priority>=medium and not priority:HIGH").unwrap();
+        assert_eq!(expr.to_string(), "(priority>=medium and not priority:high)");
+    }
+    
+    #[test]
+    fn test_priority_levels_and_comparisons() {
+        let matching = |filter: &str| -> Vec<Priority> {
+            let expr = FilterExpr::from_str(filter).unwrap();
+            Priority::all().into_iter()
+                .filter(|&level| expr.matches(&leveled("api", Status::Design, level)))
+                .collect()
+        };
+        assert_eq!(matching("priority:high"), [Priority::High]);
+        assert_eq!(matching("priority:none"), [Priority::None]);
+        assert_eq!(matching("priority>=medium"), [Priority::Medium, Priority::High]);
+        assert_eq!(matching("priority>low"), [Priority::Medium, Priority::High]);
+        assert_eq!(matching("priority<medium"), [Priority::None, Priority::Low]);
+        assert_eq!(matching("priority<=low"), [Priority::None, Priority::Low]);
+        assert_eq!(matching("priority"), [Priority::Low, Priority::Medium, Priority::High]);
+        assert_eq!(matching("priority:true"), matching("priority"));
+        assert_eq!(matching("priority:no"), [Priority::None]);
+        
+        assert_eq!(
+            FilterExpr::from_str("priority:urgent"),
+            Err(FilterError::InvalidValue { field: "priority".to_string(), value: "urgent".to_string(), position: 1 })
+        );
+        assert!(matches!(FilterExpr::from_str("priority>=true"), Err(FilterError::InvalidValue { .. })));
+        assert_eq!(
+            FilterExpr::from_str("status>design"),
+            Err(FilterError::UnsupportedComparison { field: "status".to_string(), comparison: Comparison::Greater, position: 1 })
+        );
+    }
+    
+    #[test]
+    fn test_matches() {
+        let expr = FilterExpr::from_str("project:\"web app\" and not status:published").unwrap();
+        assert!(expr.matches(&action("web app", Status::Design, false)));
+        assert!(!expr.matches(&action("web app", Status::Published, false)));
+        assert!(!expr.matches(&action("api", Status::Design, false)));
+        
+        let expr = FilterExpr::from_str("tag:priority or status:test").unwrap();
+        assert!(expr.matches(&action("api", Status::Design, true)));
+        assert!(expr.matches(&action("api", Status::Test, false)));
+        assert!(!expr.matches(&action("api", Status::Design, false)));
+        
+        let mut assigned = action("api", Status::Design, false);
+        assigned.assignee = Some("Ana".to_string());
+        let expr = FilterExpr::from_str("assignee:ana").unwrap();
+        assert!(expr.matches(&assigned));
+        assert!(!expr.matches(&action("api", Status::Design, false)));
+        let expr = FilterExpr::from_str("assignee:Unassigned").unwrap();
+        assert!(!expr.matches(&assigned));
+        assert!(expr.matches(&action("api", Status::Design, false)));
+    }
+    
+    #[test]
+    fn test_errors_report_position() {
+        assert_eq!(
+            FilterExpr::from_str("status:design and owner:me"),
+            Err(FilterError::UnknownField { field: "owner".to_string(), position: 19 })
+        );
+        assert_eq!(
+            FilterExpr::from_str("status:design priority"),
+            Err(FilterError::UnexpectedToken { token: "priority".to_string(), position: 15 })
+        );
+        assert_eq!(
+            FilterExpr::from_str("status:dsign"),
+            Err(FilterError::InvalidValue { field: "status".to_string(), value: "dsign".to_string(), position: 1 })
+        );
+        assert_eq!(
+            FilterExpr::from_str("(priority"),
+            Err(FilterError::UnexpectedEnd { expected: "')'" })
+        );
+        assert_eq!(
+            FilterExpr::from_str("project:\"web"),
+            Err(FilterError::UnterminatedQuote { position: 9 })
+        );
+    }
+}
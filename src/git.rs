@@ -0,0 +1,44 @@
+use anyhow::{Context, Result};
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::Command;
+
+/// Run `git` with `args` in `root`, failing with git's own message if it
+/// exits unsuccessfully
+fn run<I, S>(root: &Path, args: I) -> Result<()>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() { stdout } else { stderr };
+        anyhow::bail!("git failed: {}", message.trim());
+    }
+    Ok(())
+}
+
+/// Make `root` a git repository; harmless if it already is one
+pub fn init(root: &Path) -> Result<()> {
+    run(root, ["init", "--quiet"])
+}
+
+/// Stage `paths` and commit just those, whatever else is staged
+pub fn commit<P: AsRef<Path>>(root: &Path, paths: &[P], message: &str) -> Result<()> {
+    let paths: Vec<&OsStr> = paths.iter().map(|path| path.as_ref().as_os_str()).collect();
+    run(root, [OsStr::new("add"), OsStr::new("--")].into_iter().chain(paths.iter().copied()))?;
+    run(
+        root,
+        [OsStr::new("commit"), OsStr::new("--quiet"), OsStr::new("-m"), OsStr::new(message), OsStr::new("--")]
+            .into_iter()
+            .chain(paths.iter().copied()),
+    )
+}
@@ -0,0 +1,81 @@
+use crate::{Action, Workflow};
+
+/// Which way to follow links when walking the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Follow an action's own input links
+    Forward,
+    /// Follow backlinks from the actions that reference it
+    Reverse,
+}
+
+/// Node fill colors by workflow position; the final status is always the last
+const STATUS_COLORS: &[&str] = &["lightgrey", "lightyellow", "khaki", "lightsalmon", "lightblue", "plum", "palegreen"];
+
+/// A set of actions and the links between them.
+///
+/// Edges are index pairs into `nodes`, pointing from the linking action to
+/// the linked one.
+#[derive(Debug, Default)]
+pub struct ActionGraph<'a> {
+    pub nodes: Vec<&'a Action>,
+    pub edges: Vec<(usize, usize)>,
+    /// Links from a node to `.md` targets that don't exist, as `(node, target)`
+    pub dangling: Vec<(usize, String)>,
+}
+
+impl<'a> ActionGraph<'a> {
+    /// Render the graph as a Graphviz DOT digraph. Nodes are filled by their
+    /// status's place in `workflow`; self-links and dangling links are dotted.
+    pub fn to_dot(&self, workflow: &Workflow) -> String {
+        let mut dot = String::from("digraph actions {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, style=filled];\n");
+        
+        for action in &self.nodes {
+            dot.push_str(&format!(
+                "    {} [fillcolor={}];\n",
+                node_id(action),
+                status_color(action, workflow)
+            ));
+        }
+        
+        for (from, to) in &self.edges {
+            let style = if from == to { " [style=dotted]" } else { "" };
+            dot.push_str(&format!(
+                "    {} -> {}{};\n",
+                node_id(self.nodes[*from]),
+                node_id(self.nodes[*to]),
+                style
+            ));
+        }
+        
+        for (from, target) in &self.dangling {
+            let target = quote(target);
+            dot.push_str(&format!("    {} [shape=plaintext, style=\"\", fontcolor=grey];\n", target));
+            dot.push_str(&format!("    {} -> {} [style=dotted, color=grey];\n", node_id(self.nodes[*from]), target));
+        }
+        
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn status_color(action: &Action, workflow: &Workflow) -> &'static str {
+    let Some(position) = workflow.position(action.status()) else {
+        return "white";
+    };
+    if action.status() == workflow.last() {
+        STATUS_COLORS[STATUS_COLORS.len() - 1]
+    } else {
+        STATUS_COLORS[position % (STATUS_COLORS.len() - 1)]
+    }
+}
+
+fn node_id(action: &Action) -> String {
+    quote(&format!("{}/{}", action.project(), action.title()))
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
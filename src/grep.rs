@@ -0,0 +1,136 @@
+use crate::action::SECTION_NAMES;
+use crate::Action;
+use regex::Regex;
+
+/// A run of lines from one section, holding at least one match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepHunk {
+    pub project: String,
+    pub title: String,
+    pub section: &'static str,
+    /// `(line number within the section, 1-based; text; whether it matched)`
+    pub lines: Vec<(usize, String, bool)>,
+}
+
+/// Search the sections of `actions` line by line.
+///
+/// Each match brings up to `context` lines before and after it. Context stops
+/// at the edges of the section, and hunks whose context overlaps are merged.
+pub fn grep<'a>(actions: impl IntoIterator<Item = &'a Action>, pattern: &Regex, context: usize) -> Vec<GrepHunk> {
+    let mut hunks = Vec::new();
+    
+    for action in actions {
+        for &section in SECTION_NAMES {
+            let Some(body) = action.section(section) else {
+                continue;
+            };
+            let lines: Vec<&str> = body.lines().collect();
+            let matches: Vec<usize> = (0..lines.len()).filter(|&i| pattern.is_match(lines[i])).collect();
+            
+            // Group matches whose context windows touch or overlap
+            let mut windows: Vec<(usize, usize)> = Vec::new();
+            for &i in &matches {
+                let start = i.saturating_sub(context);
+                let end = (i + context).min(lines.len() - 1);
+                match windows.last_mut() {
+                    Some(last) if start <= last.1 + 1 => last.1 = end,
+                    _ => windows.push((start, end)),
+                }
+            }
+            
+            for (start, end) in windows {
+                hunks.push(GrepHunk {
+                    project: action.project().to_string(),
+                    title: action.title().to_string(),
+                    section,
+                    lines: (start..=end)
+                        .map(|i| (i + 1, lines[i].to_string(), matches.contains(&i)))
+                        .collect(),
+                });
+            }
+        }
+    }
+    
+    hunks
+}
+
+/// An action found by `search`, with where the first match is
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub action: &'a Action,
+    /// `"Title"` or the name of the matching section
+    pub section: &'static str,
+    /// The first matching line
+    pub line: String,
+}
+
+/// Find the actions whose title or sections match `pattern`, one hit per
+/// matching title or section, in the order the actions are given
+pub fn search<'a>(actions: impl IntoIterator<Item = &'a Action>, pattern: &Regex) -> Vec<SearchHit<'a>> {
+    let mut hits = Vec::new();
+    
+    for action in actions {
+        if pattern.is_match(action.title()) {
+            hits.push(SearchHit { action, section: "Title", line: action.title().to_string() });
+        }
+        for &section in SECTION_NAMES {
+            let line = action.section(section).and_then(|body| body.lines().find(|line| pattern.is_match(line)));
+            if let Some(line) = line {
+                hits.push(SearchHit { action, section, line: line.to_string() });
+            }
+        }
+    }
+    
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+    
+    fn action() -> Action {
+        let mut action = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        action.update_section("notes", Some("one\ntwo\nTODO three\nfour\nfive\nsix\ntodo seven".to_string()));
+        action.update_section("statement of action", Some("TODO first line\nsecond".to_string()));
+        action
+    }
+    
+    fn numbers(hunk: &GrepHunk) -> Vec<usize> {
+        hunk.lines.iter().map(|(number, _, _)| *number).collect()
+    }
+    
+    #[test]
+    fn test_context_is_merged_and_stays_in_its_section() {
+        let action = action();
+        let pattern = Regex::new("(?i)todo").unwrap();
+        
+        let hunks = grep([&action], &pattern, 2);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].section, "Notes");
+        assert_eq!(numbers(&hunks[0]), [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(hunks[1].section, "Statement of Action");
+        assert_eq!(numbers(&hunks[1]), [1, 2], "context doesn't reach into the Notes section");
+        
+        let hunks = grep([&action], &pattern, 1);
+        assert_eq!(hunks.iter().map(numbers).collect::<Vec<_>>(), [vec![2, 3, 4], vec![6, 7], vec![1, 2]]);
+        assert_eq!(hunks[0].lines[1], (3, "TODO three".to_string(), true));
+        assert!(!hunks[0].lines[0].2);
+    }
+    
+    #[test]
+    fn test_search_reports_each_matching_section_once() {
+        let action = action();
+        let hits = search([&action], &Regex::new("(?i)todo|setup").unwrap());
+        let found: Vec<(&str, &str)> = hits.iter().map(|hit| (hit.section, hit.line.as_str())).collect();
+        assert_eq!(found, [("Title", "setup"), ("Notes", "TODO three"), ("Statement of Action", "TODO first line")]);
+        assert!(search([&action], &Regex::new("missing").unwrap()).is_empty());
+    }
+    
+    #[test]
+    fn test_case_sensitive_without_context() {
+        let action = action();
+        let hunks = grep([&action], &Regex::new("TODO").unwrap(), 0);
+        assert_eq!(hunks.iter().map(numbers).collect::<Vec<_>>(), [vec![3], vec![1]]);
+    }
+}
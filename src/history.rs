@@ -0,0 +1,80 @@
+use crate::config::{self, STATE_DIR};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Append-only log of status and priority changes, one JSON object per line
+pub const HISTORY_FILE: &str = "history.log";
+
+/// Which field of an action a change touched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Status,
+    Priority,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Field::Status => write!(f, "status"),
+            Field::Priority => write!(f, "priority"),
+        }
+    }
+}
+
+/// One recorded change to an action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub project: String,
+    pub title: String,
+    pub field: Field,
+    pub old: String,
+    pub new: String,
+    pub timestamp: DateTime<Utc>,
+    /// Set on the change that `action undo` made, so it isn't undone in turn
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub undo: bool,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} {}: {} -> {}", self.project, self.title, self.field, self.old, self.new)
+    }
+}
+
+/// Append `change` to the workspace's history log
+pub fn record<P: AsRef<Path>>(root: P, change: &Change) -> Result<()> {
+    let path = config::ensure_state_dir(&root)?.join(HISTORY_FILE);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open history log: {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(change)?)
+        .with_context(|| format!("Failed to write history log: {}", path.display()))
+}
+
+/// Every recorded change, oldest first
+pub fn load<P: AsRef<Path>>(root: P) -> Result<Vec<Change>> {
+    let path = root.as_ref().join(STATE_DIR).join(HISTORY_FILE);
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read history log: {}", path.display()))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid history entry at {}:{}", path.display(), index + 1))
+        })
+        .collect()
+}
@@ -0,0 +1,77 @@
+use crate::Action;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+/// Shell commands run after workspace events, configured under `[hooks]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    /// Run after an action is created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_create: Option<String>,
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.on_create.is_none()
+    }
+}
+
+/// Run a hook `command` through the shell from `dir`.
+///
+/// The action is described to the command by environment variables:
+/// `ACTION_PROJECT`, `ACTION_TITLE`, `ACTION_ID`, `ACTION_STATUS` and
+/// `ACTION_PATH` (absolute). Its output goes straight to the terminal.
+/// Fails if the command can't be started or exits unsuccessfully.
+pub fn run(command: &str, action: &Action, dir: &Path) -> Result<()> {
+    let mut shell = if cfg!(target_os = "windows") {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    
+    let path = dir.join(&action.file_path);
+    let status = shell
+        .arg(command)
+        .current_dir(dir)
+        .env("ACTION_PROJECT", action.project())
+        .env("ACTION_TITLE", action.title())
+        .env("ACTION_ID", action.id.as_str())
+        .env("ACTION_STATUS", action.status().to_string())
+        .env("ACTION_PATH", &path)
+        .status()
+        .with_context(|| format!("Failed to run hook: {}", command))?;
+    
+    if !status.success() {
+        anyhow::bail!("Hook `{}` exited with {}", command, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+    use tempfile::TempDir;
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_run_passes_action_in_env() {
+        let dir = TempDir::new().unwrap();
+        let mut action = Action::new("web app".to_string(), "setup".to_string(), Priority::None);
+        action.file_path = "web app/setup.md".into();
+        
+        run("printf '%s|%s|%s' \"$ACTION_PROJECT\" \"$ACTION_TITLE\" \"$ACTION_PATH\" > out", &action, dir.path()).unwrap();
+        let out = std::fs::read_to_string(dir.path().join("out")).unwrap();
+        assert_eq!(out, format!("web app|setup|{}", dir.path().join("web app/setup.md").display()));
+        
+        let error = run("exit 3", &action, dir.path()).unwrap_err();
+        assert!(error.to_string().contains("exit 3"), "{}", error);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::config::IdScheme;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// Alphabet for short ids: RFC 4648 base32, lowercased
+const BASE32: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+/// Characters in a short id; 8 base32 characters carry 40 random bits.
+///
+/// By the birthday bound, `n` short ids generated independently collide with
+/// probability about `n² / 2⁴¹`: roughly 1 in 2 million at 1,000 ids, 1 in
+/// 22,000 at 10,000, and 1% at about 150,000. `generate` regenerates an id
+/// that clashes with one already in its project, so the bound only applies to
+/// ids that never saw each other: across projects, or created in separate
+/// clones and merged. Such a clash is reported by `action validate` as
+/// `duplicate-id` and repaired by `--fix-ids`, which gives the newer action a
+/// fresh id.
+const SHORT_LEN: usize = 8;
+
+/// An action's identifier, kept exactly as written in the `id:` frontmatter
+/// key so files created under any `IdScheme` load the same way
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ActionId(String);
+
+impl ActionId {
+    /// Accept any single token as an id; `None` for empty or multi-word values
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() || value.contains(char::is_whitespace) {
+            return None;
+        }
+        Some(Self(value.to_string()))
+    }
+    
+    /// A random UUID (v4), the default scheme
+    pub fn new_uuid() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+    
+    /// 8 random base32 characters, e.g. `k3vq7m2a`
+    pub fn new_short() -> Self {
+        let bytes = Uuid::new_v4().into_bytes();
+        let bits = bytes[..5].iter().fold(0u64, |bits, &byte| (bits << 8) | byte as u64);
+        let id = (0..SHORT_LEN)
+            .rev()
+            .map(|i| BASE32[((bits >> (i * 5)) & 31) as usize] as char)
+            .collect();
+        Self(id)
+    }
+    
+    /// Generate an id for a new action in `project` under `scheme`.
+    ///
+    /// Sequential ids are `<project-slug>-<n>`, one past the highest number
+    /// among `existing`; random ids are regenerated if they clash with one.
+    pub fn generate<'a>(scheme: IdScheme, project: &str, existing: impl IntoIterator<Item = &'a ActionId>) -> Self {
+        let existing: Vec<&ActionId> = existing.into_iter().collect();
+        
+        match scheme {
+            IdScheme::Seq => {
+                let prefix = format!("{}-", project.replace(' ', "-").to_lowercase());
+                let next = existing
+                    .iter()
+                    .filter_map(|id| id.0.strip_prefix(&prefix)?.parse::<u64>().ok())
+                    .max()
+                    .unwrap_or(0) + 1;
+                Self(format!("{}{}", prefix, next))
+            }
+            IdScheme::Uuid | IdScheme::Short => loop {
+                let id = if scheme == IdScheme::Short { Self::new_short() } else { Self::new_uuid() };
+                if !existing.contains(&&id) {
+                    return id;
+                }
+            },
+        }
+    }
+    
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ActionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_accepts_any_scheme() {
+        let uuid = "550e8400-e29b-41d4-a716-446655440000";
+        assert_eq!(ActionId::parse(uuid).unwrap().as_str(), uuid);
+        assert_eq!(ActionId::parse(" k3vq7m2a ").unwrap().as_str(), "k3vq7m2a");
+        assert_eq!(ActionId::parse("web-12").unwrap().to_string(), "web-12");
+        assert!(ActionId::parse("").is_none());
+        assert!(ActionId::parse("two words").is_none());
+    }
+    
+    #[test]
+    fn test_generate_per_scheme() {
+        let short = ActionId::generate(IdScheme::Short, "web", []);
+        assert_eq!(short.as_str().len(), SHORT_LEN);
+        assert!(short.as_str().bytes().all(|b| BASE32.contains(&b)));
+        
+        assert!(Uuid::parse_str(ActionId::generate(IdScheme::Uuid, "web", []).as_str()).is_ok());
+        
+        let existing = ["web-app-2", "web-app-10", "api-40", "k3vq7m2a"].map(|id| ActionId::parse(id).unwrap());
+        assert_eq!(ActionId::generate(IdScheme::Seq, "Web App", &existing).as_str(), "web-app-11");
+        assert_eq!(ActionId::generate(IdScheme::Seq, "docs", &existing).as_str(), "docs-1");
+    }
+}
@@ -1,12 +1,38 @@
 pub mod action;
+pub mod batch;
+pub mod config;
 pub mod project;
 pub mod status;
 pub mod workspace;
 pub mod utils;
 pub mod template;
 pub mod parser;
+pub mod graph;
+pub mod validation;
+pub mod stats;
+pub mod filter;
+pub mod export;
+pub mod merge;
+pub mod worklog;
+pub mod grep;
+pub mod hooks;
+pub mod id;
+pub mod workflow;
+pub mod watch;
+pub mod report;
+pub mod history;
+pub mod git;
 
-pub use action::Action;
+pub use action::{Action, ActionLink, LinkTarget};
+pub use config::{Config, IdScheme, MetaGraphLayout};
+pub use graph::{ActionGraph, Direction};
+pub use id::ActionId;
+pub use merge::{MergeError, MergeStrategy};
 pub use project::Project;
-pub use status::Status;
-pub use workspace::Workspace;
\ No newline at end of file
+pub use filter::{Comparison, FilterExpr};
+pub use stats::WorkspaceStats;
+pub use status::{Lifecycle, Priority, Status};
+pub use template::ActionType;
+pub use workspace::{ActionFilter, AdvanceOutcome, CreateOptions, GroupKey, ImportSummary, InitOptions, Rollup, SortKey, TagIndex, Workspace, WorkspaceError, WorkspaceInfo, WorkspaceLock};
+pub use validation::{Severity, ValidationIssue};
+pub use workflow::{Workflow, WorkflowStatus};
@@ -1,9 +1,78 @@
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use anyhow::{Context, Result};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use colored::*;
-use std::path::PathBuf;
+use std::io::{Read, Write};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use action_lite::action::{parse_due, SECTION_NAMES};
+use action_lite::config;
+use action_lite::export::{self, ActionView, ExportFormat};
+use action_lite::stats::{self, Snapshot};
+use action_lite::worklog::{self, WorklogOptions};
+use action_lite::batch::{self, BatchOptions};
+use action_lite::grep;
+use action_lite::report;
+use action_lite::watch::{self, WorkspaceWatcher};
+use action_lite::{utils, Action, ActionFilter, AdvanceOutcome, CreateOptions, Direction, FilterExpr, GroupKey, InitOptions, Lifecycle, WorkspaceError, MetaGraphLayout, Priority, SortKey, ValidationIssue, Workspace, Status};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// How much besides a command's results gets printed, from `--quiet`/`--verbose`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Print an informational `✓`/`ℹ` line, unless `--quiet`
+macro_rules! notice {
+    ($($arg:tt)*) => {
+        if verbosity() >= Verbosity::Normal {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Print extra detail, only with `--verbose`
+macro_rules! detail {
+    ($($arg:tt)*) => {
+        if verbosity() >= Verbosity::Verbose {
+            println!($($arg)*);
+        }
+    };
+}
 
-use action_lite::{Workspace, Status};
 
 #[derive(Parser)]
 #[command(name = "action")]
@@ -14,6 +83,26 @@ struct Cli {
     #[arg(short, long, value_name = "DIR")]
     workspace: Option<PathBuf>,
     
+    /// Show timestamps in the local time zone instead of UTC
+    #[arg(long, global = true)]
+    local: bool,
+    
+    /// Refuse to modify anything in the workspace
+    #[arg(long, global = true)]
+    read_only: bool,
+    
+    /// Only print command results and errors, not `✓`/`ℹ` messages
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    
+    /// Print extra detail, such as the file an action was written to
+    #[arg(short, long, global = true)]
+    verbose: bool,
+    
+    /// When to color output; `auto` colors only a terminal and honors NO_COLOR
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+    
     #[command(subcommand)]
     command: Commands,
 }
@@ -24,6 +113,21 @@ enum Commands {
     Init {
         /// Directory to initialize (defaults to current directory)
         path: Option<PathBuf>,
+        /// Only create the workspace marker, without a README
+        #[arg(long, conflicts_with = "template")]
+        bare: bool,
+        /// Seed the workspace README from this file
+        #[arg(long, value_name = "FILE")]
+        template: Option<PathBuf>,
+        /// Re-initialize an existing workspace; with --template, also replace an existing README
+        #[arg(short, long)]
+        force: bool,
+        /// Don't write .action-lite/.gitignore for the tool's transient files
+        #[arg(long)]
+        no_gitignore: bool,
+        /// Also run git init and write a top-level .gitignore
+        #[arg(long)]
+        git: bool,
     },
     /// Create a new action
     New {
@@ -31,54 +135,278 @@ enum Commands {
         project: String,
         /// Action title
         title: String,
-        /// Set priority flag
+        /// Priority level (none, low, medium, high); high if no level is given
+        #[arg(short, long, value_name = "LEVEL", num_args = 0..=1, default_missing_value = "high")]
+        priority: Option<Priority>,
+        /// Action type (action, bug, feature, research or one defined in the config)
+        #[arg(short = 't', long = "type", value_name = "TYPE")]
+        action_type: Option<String>,
+        /// Create the action without placeholder sections
+        #[arg(long, visible_alias = "blank")]
+        no_template: bool,
+        /// Remove the action again if the on_create hook fails
+        #[arg(long)]
+        rollback_on_hook_failure: bool,
+        /// Prompt for the Statement of Action, Statement of Inputs and priority
         #[arg(short, long)]
-        priority: bool,
+        interactive: bool,
     },
     /// List actions
+    #[command(visible_alias = "ls")]
     List {
-        /// Filter by project
-        #[arg(short, long)]
-        project: Option<String>,
-        /// Filter by status
+        #[command(flatten)]
+        filters: Box<ListFilters>,
+        /// Group actions by status, project, priority or assignee
+        #[arg(short, long, value_name = "KEY")]
+        group_by: Option<String>,
+        /// Only print the size of each group
+        #[arg(long, requires = "group_by")]
+        count_only: bool,
+        /// Also show when each action was last updated
         #[arg(short, long)]
-        status: Option<String>,
-        /// Show only priority actions
+        long: bool,
+        /// Sort by created, updated, status, priority or title instead of project and title
+        #[arg(long, value_name = "FIELD")]
+        sort: Option<String>,
+        /// Reverse the sort order
         #[arg(long)]
-        priority: bool,
+        reverse: bool,
+        /// Emit the matching actions as a JSON array, with computed fields
+        #[arg(long, conflicts_with = "group_by")]
+        json: bool,
     },
+    /// List projects with their action counts and README descriptions
+    Projects,
     /// Show action details
+    #[command(visible_alias = "cat")]
     Show {
         /// Project name
         project: String,
         /// Action title
         title: String,
+        /// Print the action file exactly as stored on disk
+        #[arg(long, conflicts_with = "outline")]
+        raw: bool,
+        /// Print an outline of each section's sub-headings instead of the full text
+        #[arg(long)]
+        outline: bool,
+        /// Also list the actions linked from the Statement of Inputs, with their statuses
+        #[arg(long, conflicts_with_all = ["raw", "outline"])]
+        with_deps: bool,
+        /// Also list the actions that link to this one
+        #[arg(long, conflicts_with_all = ["raw", "outline"])]
+        with_backlinks: bool,
+        /// Print the action as JSON, in the same shape as list --json
+        #[arg(long, conflicts_with_all = ["raw", "outline", "with_deps", "with_backlinks"])]
+        json: bool,
+    },
+    /// Delete an action along with its meta-graph
+    #[command(visible_alias = "rm")]
+    Delete {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Change an action's title, renaming its file and meta-graph
+    Rename {
+        /// Project name
+        project: String,
+        /// Current action title
+        title: String,
+        /// New action title
+        new_title: String,
+    },
+    /// Move an action, with its meta-graph, to another project
+    Move {
+        /// Project the action is in
+        project: String,
+        /// Action title
+        title: String,
+        /// Project to move it to (created if needed)
+        dest_project: String,
+    },
+    /// Shell-style rename or move: a new title renames, --to moves to another project
+    Mv {
+        /// Project the action is in
+        project: String,
+        /// Action title
+        title: String,
+        /// New action title
+        #[arg(required_unless_present = "to")]
+        new_title: Option<String>,
+        /// Project to move it to (created if needed); renames in place if it's the same project
+        #[arg(long, value_name = "PROJECT")]
+        to: Option<String>,
     },
     /// Update action status
     Status {
         /// Project name
         project: String,
         /// Action title
-        title: String,
+        #[arg(required_unless_present = "all")]
+        title: Option<String>,
         /// New status
-        status: String,
+        #[arg(required_unless_present = "all")]
+        status: Option<String>,
+        /// Advance every action in the project to its next status
+        #[arg(long, conflicts_with_all = ["title", "status"])]
+        all: bool,
+        /// With --all, only report what would change
+        #[arg(long, requires = "all")]
+        dry_run: bool,
+        /// Don't add placeholder sections for the new status's required outputs
+        #[arg(long)]
+        no_scaffold: bool,
+        /// Allow skipping statuses, leaving a status without its required outputs and ignoring entry requirements
+        #[arg(long, conflicts_with = "all")]
+        force: bool,
+    },
+    /// Move an action to the next status in the workflow
+    Next {
+        /// Project name
+        #[arg(required_unless_present = "all")]
+        project: Option<String>,
+        /// Action title
+        #[arg(required_unless_present = "all")]
+        title: Option<String>,
+        /// Advance every action matching --project and --status instead
+        #[arg(long, conflicts_with_all = ["project", "title"])]
+        all: bool,
+        /// With --all, only actions in this project
+        #[arg(short = 'p', long = "project", value_name = "PROJECT", requires = "all")]
+        only_project: Option<String>,
+        /// With --all, only actions at this status
+        #[arg(short, long, requires = "all")]
+        status: Option<String>,
+    },
+    /// Move an action back to the previous status in the workflow
+    Prev {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
     },
-    /// Set or unset priority flag
+    /// Mark an action as blocked; it keeps its status but won't advance
+    Block {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+    },
+    /// Mark an action as cancelled and hide it from listings
+    Cancel {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+    },
+    /// Return a blocked or cancelled action to the pipeline
+    Reactivate {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+    },
+    /// Set an action's priority level
     Priority {
         /// Project name
         project: String,
         /// Action title
         title: String,
-        /// Set priority (true) or remove priority (false)
+        /// none, low, medium or high
+        #[arg(conflicts_with = "set")]
+        level: Option<Priority>,
+        /// Set high priority; without a level or --set, priority is removed
         #[arg(short, long)]
         set: bool,
     },
+    /// Set or clear an action's due date
+    Due {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Due date as YYYY-MM-DD (end of that day, UTC) or RFC 3339
+        #[arg(required_unless_present = "clear", conflicts_with = "clear")]
+        date: Option<String>,
+        /// Remove the due date
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Set how long an action is expected to take
+    Estimate {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Hours and minutes, like 2h30m, 1h or 45m
+        #[arg(required_unless_present = "clear", conflicts_with = "clear")]
+        duration: Option<String>,
+        /// Remove the estimate
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Add time spent on an action
+    LogTime {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Hours and minutes, like 2h30m, 1h or 45m
+        duration: String,
+    },
+    /// Assign an action to someone
+    Assign {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Who to assign it to
+        who: String,
+    },
+    /// Remove an action's assignee
+    Unassign {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+    },
+    /// Manage dependencies recorded in an action's depends_on frontmatter
+    Dep {
+        #[command(subcommand)]
+        command: DepCommand,
+    },
+    /// Append a timestamped entry to an action's log
+    Comment {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Comment text
+        text: String,
+        /// Author to attribute the entry to (defaults to git user.name or $USER)
+        #[arg(short, long)]
+        author: Option<String>,
+    },
     /// Edit an action in your default editor
     Edit {
         /// Project name
         project: String,
         /// Action title
         title: String,
+        /// Create the action first if it doesn't exist
+        #[arg(long)]
+        create: bool,
+        /// With --create, give a newly created action this priority (high if no level is given)
+        #[arg(long, requires = "create", value_name = "LEVEL", num_args = 0..=1, default_missing_value = "high")]
+        priority: Option<Priority>,
+        /// Only report what would happen
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Create a meta-graph for an action
     MetaGraph {
@@ -87,82 +415,828 @@ enum Commands {
         /// Action title
         title: String,
     },
+    /// Copy a file into an action's meta-graph as an attachment
+    Attach {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// File to attach
+        file: PathBuf,
+    },
+    /// List an action's attachments with their sizes
+    Attachments {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+    },
+    /// Remove an attachment from an action
+    Detach {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Attachment file name
+        name: String,
+    },
+    /// Migrate workspace layout settings
+    Migrate {
+        /// Move meta-graphs to this layout (sibling or hidden)
+        #[arg(long, value_name = "LAYOUT")]
+        meta_graph_layout: String,
+        /// Only report what would move
+        #[arg(long)]
+        dry_run: bool,
+        /// Print a diff of the link fixes in moved files (implies --dry-run)
+        #[arg(long)]
+        diff: bool,
+    },
+    /// Read and change settings in .action-lite/config.toml
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Apply a JSON array of operations read from stdin, printing a JSON result for each
+    Batch {
+        /// Keep applying operations after one fails
+        #[arg(long, conflicts_with = "atomic")]
+        continue_on_error: bool,
+        /// Undo every operation if any of them fails
+        #[arg(long)]
+        atomic: bool,
+    },
+    /// Find actions whose title or content mention a phrase (case-insensitive)
+    Search {
+        /// Text to look for (a regular expression with --regex)
+        query: String,
+        /// Treat the query as a regular expression
+        #[arg(short = 'E', long)]
+        regex: bool,
+    },
+    /// Search action sections line by line, like git grep
+    Grep {
+        /// Text to search for (a regular expression with --regex)
+        pattern: String,
+        /// Lines of context to show around each match
+        #[arg(short = 'C', long, default_value_t = 0, value_name = "N")]
+        context: usize,
+        /// Treat the pattern as a regular expression
+        #[arg(short = 'E', long)]
+        regex: bool,
+        /// Ignore case
+        #[arg(short, long)]
+        ignore_case: bool,
+        /// Only search this project
+        #[arg(short, long)]
+        project: Option<String>,
+    },
+    /// Find pairs of actions with similar titles and statements
+    Duplicates {
+        /// Minimum similarity to report, from 0 to 1
+        #[arg(long, default_value_t = 0.8)]
+        threshold: f64,
+        /// Also compare actions in different projects
+        #[arg(long)]
+        cross_project: bool,
+    },
+    /// Rewrite action files in their canonical layout
+    Fmt {
+        /// Only format this project
+        #[arg(short, long)]
+        project: Option<String>,
+        /// Only list the files that would change
+        #[arg(long)]
+        dry_run: bool,
+        /// Print a diff of each change (implies --dry-run)
+        #[arg(long)]
+        diff: bool,
+    },
+    /// Print the action link graph in Graphviz DOT format
+    Graph {
+        /// Only include actions reachable from this action (project/title)
+        #[arg(long, value_name = "PROJECT/TITLE")]
+        from: Option<String>,
+        /// Only include this project's actions
+        #[arg(short, long, conflicts_with = "from")]
+        project: Option<String>,
+        /// Output format
+        #[arg(long, default_value = "dot", value_parser = ["dot"])]
+        format: String,
+        /// Maximum number of hops to follow from the starting action
+        #[arg(long, requires = "from")]
+        depth: Option<usize>,
+        /// Follow backlinks instead of the action's own links
+        #[arg(long, requires = "from")]
+        reverse: bool,
+    },
+    /// Compute a parent action's progress from the actions in its Children section
+    Rollup {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Set the parent's status to the rolled-up status
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Render actions through a custom template, or dump them as JSON or NDJSON
+    Export {
+        /// Tera template file
+        #[arg(long, value_name = "FILE", required_unless_present = "format", conflicts_with = "format")]
+        template: Option<PathBuf>,
+        /// Dump the workspace as json (keyed by project) or ndjson (one action per line)
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+        /// Render the template once per action (bound to `action`) instead of once for all
+        #[arg(long, requires = "template")]
+        each: bool,
+        /// Only export actions from this project
+        #[arg(short, long)]
+        project: Option<String>,
+        /// Filter expression, as for list --filter
+        #[arg(short, long, value_name = "EXPR")]
+        filter: Option<String>,
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Revert the last status or priority change
+    Undo,
+    /// Write a markdown summary of the workspace for sharing
+    Report {
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Recreate projects and actions from `export --format` output
+    Import {
+        /// JSON or NDJSON file written by export
+        file: PathBuf,
+        /// Replace actions that already exist instead of refusing
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// Show where the workspace is and how it's configured
+    Info,
+    /// List tags used across actions with how many actions carry each
+    Tags {
+        /// Show the tags that most often appear together with this one
+        #[arg(long, value_name = "TAG")]
+        related: Option<String>,
+        /// Sort by count (default) or name
+        #[arg(long, default_value = "count", value_parser = ["count", "name"])]
+        sort: String,
+    },
+    /// Add or remove a tag on a single action
+    Tag {
+        #[command(subcommand)]
+        command: TagCommand,
+    },
+    /// Add, remove or rename a tag on every matching action
+    #[command(group(clap::ArgGroup::new("operation").required(true).args(["add", "remove", "rename"])))]
+    TagAll {
+        /// Tag to add
+        #[arg(long, value_name = "TAG")]
+        add: Option<String>,
+        /// Tag to remove
+        #[arg(long, value_name = "TAG")]
+        remove: Option<String>,
+        /// Replace one tag with another
+        #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+        rename: Option<Vec<String>>,
+        /// Only actions in this project
+        #[arg(short, long)]
+        project: Option<String>,
+        /// Only actions with this status
+        #[arg(short, long)]
+        status: Option<String>,
+        /// Show which actions would change without saving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show workspace statistics
+    Stats {
+        /// Emit statistics as JSON
+        #[arg(long)]
+        json: bool,
+        /// Record today's statistics in the metrics history
+        #[arg(long)]
+        snapshot: bool,
+        /// Count actions not updated for more than this many days as stale
+        #[arg(long, default_value_t = stats::DEFAULT_STALE_DAYS, value_name = "DAYS")]
+        stale_days: i64,
+    },
+    /// Show how status counts changed across recorded snapshots
+    Trend,
+    /// Report what was touched each day: creations, comments and updates
+    Worklog {
+        /// First day to include (YYYY-MM-DD); defaults to a week ago
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+        /// Only comments by this author
+        #[arg(long)]
+        author: Option<String>,
+        /// Time zone for day boundaries: UTC or an offset like +02:00
+        #[arg(long, default_value = "UTC")]
+        tz: String,
+    },
     /// Validate workspace structure and files
-    Validate,
+    Validate {
+        /// Emit issues as a JSON array
+        #[arg(long, alias = "porcelain")]
+        json: bool,
+        /// Give fresh ids to copies of actions that share an id (the oldest keeps it)
+        #[arg(long)]
+        fix_ids: bool,
+        /// Fail on broken links instead of only warning about them
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Re-validate the workspace whenever an action file changes, until Ctrl-C
+    Watch {
+        /// Fail on broken links instead of only warning about them
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+/// Which actions `action list` shows
+#[derive(Args)]
+struct ListFilters {
+    /// Filter by project
+    #[arg(short, long)]
+    project: Option<String>,
+    /// Filter by status
+    #[arg(short, long)]
+    status: Option<String>,
+    /// Hide actions with this status (repeatable)
+    #[arg(long, value_name = "STATUS")]
+    not_status: Vec<String>,
+    /// Show actions at this stage or later
+    #[arg(long, value_name = "STATUS", visible_alias = "since-status")]
+    min_status: Option<String>,
+    /// Show actions at this stage or earlier
+    #[arg(long, value_name = "STATUS")]
+    max_status: Option<String>,
+    /// Show only priority actions
+    #[arg(long)]
+    priority: bool,
+    /// Show only blocked actions
+    #[arg(long, conflicts_with = "cancelled")]
+    blocked: bool,
+    /// Show only cancelled actions (hidden otherwise)
+    #[arg(long)]
+    cancelled: bool,
+    /// Filter by action type
+    #[arg(short = 't', long = "type", value_name = "TYPE")]
+    action_type: Option<String>,
+    /// Show only actions with a meta-graph
+    #[arg(long)]
+    has_meta: bool,
+    /// Only show actions that have this section, custom ones included (repeatable)
+    #[arg(long, value_name = "SECTION")]
+    has_section: Vec<String>,
+    /// Only show actions missing this section (repeatable)
+    #[arg(long, value_name = "SECTION")]
+    missing_section: Vec<String>,
+    /// Only show actions carrying this tag
+    #[arg(long, value_name = "TAG")]
+    tag: Option<String>,
+    /// Only show actions past their due date
+    #[arg(long)]
+    overdue: bool,
+    /// Only show actions due before this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long, value_name = "DATE")]
+    due_before: Option<String>,
+    /// Only show actions assigned to this person, or "unassigned"
+    #[arg(long, value_name = "WHO")]
+    assignee: Option<String>,
+    /// Filter expression, e.g. "status:design and priority" or "project:web and not status:published"
+    #[arg(short, long, value_name = "EXPR")]
+    filter: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum TagCommand {
+    /// Add a tag to an action's tag line
+    Add {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Tag, with or without the leading #
+        tag: String,
+    },
+    /// Remove a tag from an action's tag line
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// Tag, with or without the leading #
+        tag: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum DepCommand {
+    /// Record that an action depends on another
+    Add {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// The action depended on, as project/title or a title in the same project
+        target: String,
+    },
+    /// Remove a recorded dependency
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Project name
+        project: String,
+        /// Action title
+        title: String,
+        /// The dependency to drop
+        target: String,
+    },
+    /// List actions waiting on dependencies that aren't finished
+    Blocked,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print a setting's value
+    Get {
+        /// Setting name, e.g. date_format
+        key: String,
+    },
+    /// Change a setting
+    Set {
+        /// Setting name, e.g. default_editor
+        key: String,
+        /// New value
+        value: String,
+    },
+    /// Put a setting back to its default
+    Unset {
+        /// Setting name
+        key: String,
+    },
+    /// Print every setting
+    List,
+}
+
+/// One line of `list` output; `long` appends how long ago the action was updated
+fn print_action_row(action: &Action, long: bool) {
+    let priority_marker = match action.priority() {
+        Priority::High => "!".red(),
+        Priority::Medium => "+".yellow(),
+        Priority::Low => "-".dimmed(),
+        Priority::None => " ".normal(),
+    };
+    let meta_marker = if action.has_meta_graph() { "⊕" } else { " " };
+    let status_color = match action.status() {
+        Status::Discovery => "yellow",
+        Status::Design => "blue", 
+        Status::Implement => "magenta",
+        Status::Test => "cyan",
+        Status::Document => "green",
+        Status::Publish => "bright_green",
+        Status::Published => "bright_blue",
+        Status::Custom(_) => "white",
+    };
     
-    let workspace_path = cli.workspace.unwrap_or_else(|| {
-        std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-    });
+    let status = match action.lifecycle() {
+        Lifecycle::Active => action.status().to_string().color(status_color),
+        Lifecycle::Blocked => format!("{}, blocked", action.status()).red(),
+        Lifecycle::Cancelled => format!("{}, cancelled", action.status()).dimmed().strikethrough(),
+    };
     
-    match cli.command {
-        Commands::Init { path } => {
-            let init_path = path.unwrap_or(workspace_path);
-            let _workspace = Workspace::init(&init_path)?;
-            println!("{} Action Lite workspace initialized at: {}", 
-                "✓".green(), 
-                init_path.display().to_string().cyan()
-            );
-            Ok(())
-        }
-        
-        Commands::New { project, title, priority } => {
-            let mut workspace = Workspace::load(&workspace_path)?;
-            let _action = workspace.create_action(&project, &title, priority)?;
-            println!("{} Created action: {} in project {}", 
-                "✓".green(), 
-                title.cyan(), 
-                project.yellow()
-            );
-            if priority {
-                println!("  {} Priority action", "!".red());
-            }
-            Ok(())
-        }
-        
-        Commands::List { project, status, priority } => {
-            let workspace = Workspace::load(&workspace_path)?;
-            let actions = workspace.list_actions(project.as_deref(), status.as_deref(), priority)?;
-            
-            if actions.is_empty() {
-                println!("{} No actions found matching criteria", "ℹ".blue());
-                return Ok(());
-            }
-            
-            for action in actions {
-                let priority_marker = if action.is_priority() { "!" } else { " " };
-                let status_color = match action.status() {
-                    Status::Discovery => "yellow",
-                    Status::Design => "blue", 
-                    Status::Implement => "magenta",
-                    Status::Test => "cyan",
-                    Status::Document => "green",
-                    Status::Publish => "bright_green",
-                    Status::Published => "bright_blue",
-                };
-                
-                println!("{} [{}] {}/{} - {}", 
-                    priority_marker.red(),
-                    action.status().to_string().color(status_color),
-                    action.project().yellow(),
-                    action.title().cyan(),
-                    action.statement_of_action().map_or("No description", |s| s.as_str()).dimmed()
-                );
-            }
-            Ok(())
+    let now = chrono::Utc::now();
+    let overdue = if action.is_overdue(now) { " (overdue)" } else { "" };
+    let updated = if long { format!(" (updated {})", utils::time_ago(&action.updated_at, now)) } else { String::new() };
+    
+    println!("{}{} [{}] {}/{}{} - {}{}", 
+        priority_marker,
+        meta_marker.blue(),
+        status,
+        action.project().yellow(),
+        action.title().cyan(),
+        overdue.red(),
+        action.statement_of_action().map_or("No description", |s| s.as_str()).dimmed(),
+        updated.dimmed()
+    );
+}
+
+/// Whether validation fails; with `strict`, broken links count as errors
+fn fails_validation(issues: &[ValidationIssue], strict: bool) -> bool {
+    issues.iter().any(|issue| issue.is_error() || (strict && issue.code == "broken-link"))
+}
+
+/// Print each issue and an overall pass/fail line; returns whether it failed
+fn print_validation(issues: &[ValidationIssue], strict: bool) -> bool {
+    for issue in issues {
+        if issue.is_error() {
+            println!("{} {}", "✗".red(), issue);
+        } else {
+            println!("{} {}", "⚠".yellow(), issue);
         }
-        
-        Commands::Show { project, title } => {
-            let workspace = Workspace::load(&workspace_path)?;
-            let action = workspace.get_action(&project, &title)?;
+    }
+    
+    let failed = fails_validation(issues, strict);
+    if failed {
+        println!("{} Workspace validation failed", "✗".red());
+    } else {
+        notice!("{} Workspace validation passed", "✓".green());
+    }
+    failed
+}
+
+/// Apply `list --sort`/`--reverse`; `--reverse` alone flips the default order
+fn order_actions(workspace: &Workspace, actions: &mut [&Action], sort: Option<SortKey>, reverse: bool) {
+    match sort {
+        Some(key) => workspace.sort_actions(actions, key, reverse),
+        None if reverse => actions.reverse(),
+        None => {}
+    }
+}
+
+/// Up to 80 characters of `line` around the first match of `pattern`
+fn snippet(line: &str, pattern: &regex::Regex) -> String {
+    const WIDTH: usize = 80;
+    let line = line.trim();
+    if line.chars().count() <= WIDTH {
+        return line.to_string();
+    }
+    
+    let start = pattern.find(line).map_or(0, |found| line[..found.start()].chars().count().saturating_sub(WIDTH / 4));
+    let text: String = line.chars().skip(start).take(WIDTH).collect();
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if start + WIDTH < line.chars().count() { "…" } else { "" };
+    format!("{}{}{}", prefix, text, suffix)
+}
+
+/// `line` with every match of `pattern` highlighted
+fn highlight_matches(line: &str, pattern: &regex::Regex) -> String {
+    let mut highlighted = String::new();
+    let mut last = 0;
+    for found in pattern.find_iter(line) {
+        highlighted.push_str(&line[last..found.start()]);
+        highlighted.push_str(&found.as_str().red().bold().to_string());
+        last = found.end();
+    }
+    highlighted.push_str(&line[last..]);
+    highlighted
+}
+
+/// Human-readable file size, e.g. `12.3 KB`
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Load the workspace, offering to initialize one when there is none and
+/// we're talking to a terminal. Scripts keep getting the plain error.
+fn load_workspace(path: &Path, read_only: bool) -> Result<Workspace> {
+    Ok(Workspace::load(path)?.with_read_only(read_only))
+}
+
+/// For commands that touch a single action: actions are read on demand
+/// through `Workspace::load_action`
+fn load_workspace_lazy(path: &Path, read_only: bool) -> Result<Workspace> {
+    Ok(Workspace::load_lazy(path)?.with_read_only(read_only))
+}
+
+fn load_or_offer_init(path: &Path, read_only: bool) -> Result<Workspace> {
+    let error = match load_workspace(path, read_only) {
+        Ok(workspace) => return Ok(workspace),
+        Err(error) => error,
+    };
+    
+    let interactive = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+    if read_only || !interactive || !matches!(error.downcast_ref::<WorkspaceError>(), Some(WorkspaceError::NotAWorkspace(_))) {
+        return Err(error);
+    }
+    
+    let confirmed = dialoguer::Confirm::new()
+        .with_prompt(format!("No Action Lite workspace in {} — initialize one?", path.display()))
+        .default(false)
+        .interact()?;
+    if !confirmed {
+        return Err(error);
+    }
+    
+    validate_workspace_dir(path)?;
+    let workspace = Workspace::init(path)?;
+    notice!("{} Action Lite workspace initialized at: {}", "✓".green(), path.display().to_string().cyan());
+    Ok(workspace)
+}
+
+/// The workspace directory's own name must pass the same checks as a project name
+fn validate_workspace_dir(path: &Path) -> Result<()> {
+    let absolute = std::path::absolute(path)
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    match absolute.file_name().and_then(|name| name.to_str()) {
+        Some(name) => utils::validate_project_name(name)
+            .with_context(|| format!("Invalid workspace directory name '{}'", name)),
+        None => Ok(()),
+    }
+}
+
+/// Ask for the sections `new --interactive` fills in. Blank answers keep the
+/// placeholder text. Returns the priority and the answered sections.
+fn prompt_new_action(priority: Priority) -> Result<(Priority, Vec<(String, String)>)> {
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        anyhow::bail!("--interactive needs a terminal; fill the sections in with `action edit` instead");
+    }
+    
+    let mut sections = Vec::new();
+    for (section, hint) in [
+        ("Statement of Action", "the task to be performed and why"),
+        ("Statement of Inputs", "links to the files it builds on"),
+    ] {
+        let answer: String = dialoguer::Input::new()
+            .with_prompt(format!("{} ({}, blank to skip)", section, hint))
+            .allow_empty(true)
+            .interact_text()?;
+        if !answer.trim().is_empty() {
+            sections.push((section.to_string(), answer.trim().to_string()));
+        }
+    }
+    
+    let levels = Priority::all();
+    let chosen = dialoguer::Select::new()
+        .with_prompt("Priority")
+        .items(&levels)
+        .default(levels.iter().position(|level| *level == priority).unwrap_or_default())
+        .interact()?;
+    Ok((levels[chosen], sections))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let level = if cli.quiet {
+        Verbosity::Quiet
+    } else if cli.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    };
+    VERBOSITY.store(level as u8, Ordering::Relaxed);
+    colored::control::set_override(cli.color.enabled());
+    
+    let read_only = cli.read_only;
+    let discovered_by = if cli.workspace.is_some() { "--workspace flag" } else { "current directory" };
+    let workspace_path = cli.workspace.unwrap_or_else(|| {
+        std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+    });
+    
+    match cli.command {
+        Commands::Init { path, bare, template, force, no_gitignore, git } => {
+            if read_only {
+                return Err(WorkspaceError::ReadOnly.into());
+            }
+            let init_path = path.unwrap_or(workspace_path);
+            validate_workspace_dir(&init_path)?;
+            let kept_readme = template.is_some() && !force && init_path.join("README.md").exists();
+            let options = InitOptions { bare, template, force, no_gitignore, git };
+            let _workspace = Workspace::init_with(&init_path, &options)?;
+            notice!("{} Action Lite workspace initialized at: {}", 
+                "✓".green(), 
+                init_path.display().to_string().cyan()
+            );
+            if kept_readme {
+                notice!("{} Kept the existing README.md; pass --force to replace it with the template", "ℹ".blue());
+            }
+            Ok(())
+        }
+        
+        Commands::New { project, title, priority, action_type, no_template, rollback_on_hook_failure, interactive } => {
+            let mut workspace = load_or_offer_init(&workspace_path, read_only)?;
+            let priority = priority.unwrap_or_default();
+            let (priority, sections) = if interactive {
+                prompt_new_action(priority)?
+            } else {
+                (priority, Vec::new())
+            };
+            let options = CreateOptions { priority, action_type, blank: no_template, rollback_on_hook_failure, sections };
+            let action = workspace.create_action_with(&project, &title, &options)?;
+            notice!("{} Created action: {} in project {}", 
+                "✓".green(), 
+                title.cyan(), 
+                project.yellow()
+            );
+            if priority.is_set() {
+                notice!("  {} {} priority", "!".red(), priority);
+            }
+            detail!("  {} {}", "→".dimmed(), action.file_path.display());
+            Ok(())
+        }
+        
+        Commands::List { filters, group_by, count_only, long, sort, reverse, json } => {
+            let ListFilters { project, status, not_status, min_status, max_status, priority, blocked, cancelled, action_type, has_meta, has_section, missing_section, tag, overdue, due_before, assignee, filter } = *filters;
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let filter = ActionFilter {
+                project,
+                status: status.as_deref().map(|status| workspace.parse_status(status)).transpose()?,
+                excluded_statuses: not_status.iter()
+                    .map(|status| workspace.parse_status(status))
+                    .collect::<Result<_>>()?,
+                min_status: min_status.as_deref().map(|status| workspace.parse_status(status)).transpose()?,
+                max_status: max_status.as_deref().map(|status| workspace.parse_status(status)).transpose()?,
+                priority_only: priority,
+                lifecycle: if blocked {
+                    Some(Lifecycle::Blocked)
+                } else if cancelled {
+                    Some(Lifecycle::Cancelled)
+                } else {
+                    None
+                },
+                action_type,
+                has_meta_graph: has_meta,
+                has_sections: has_section,
+                missing_sections: missing_section,
+                tag,
+                overdue,
+                due_before: due_before.as_deref().map(parse_due).transpose()?,
+                assignee,
+                expr: filter.as_deref()
+                    .map(|filter| FilterExpr::parse_with(filter, workspace.workflow()))
+                    .transpose()
+                    .context("Invalid --filter expression")?,
+            };
+            let sort = sort.as_deref().map(SortKey::from_str).transpose()?;
+            
+            if let Some(key) = group_by {
+                let key = GroupKey::from_str(&key)?;
+                let mut groups = workspace.group_actions_by(key, &filter)?;
+                for (_, actions) in &mut groups {
+                    order_actions(&workspace, actions, sort, reverse);
+                }
+                
+                if groups.is_empty() {
+                    notice!("{} No actions found matching criteria", "ℹ".blue());
+                    return Ok(());
+                }
+                
+                for (name, actions) in groups {
+                    if count_only {
+                        println!("{} {}", name.bold(), format!("({})", actions.len()).dimmed());
+                        continue;
+                    }
+                    
+                    println!("\n{} {}", name.bold(), format!("({})", actions.len()).dimmed());
+                    for action in actions {
+                        print_action_row(action, long);
+                    }
+                }
+                return Ok(());
+            }
+            
+            let mut actions = workspace.filter_actions(&filter)?;
+            order_actions(&workspace, &mut actions, sort, reverse);
+            
+            if json {
+                let now = chrono::Utc::now();
+                let views: Vec<ActionView> = actions.iter()
+                    .map(|action| ActionView::new(action, workspace.root(), workspace.workflow(), now))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&views)?);
+                return Ok(());
+            }
+            
+            if actions.is_empty() {
+                notice!("{} No actions found matching criteria", "ℹ".blue());
+                return Ok(());
+            }
+            
+            for action in actions {
+                print_action_row(action, long);
+            }
+            Ok(())
+        }
+        
+        Commands::Projects => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            
+            if workspace.projects().is_empty() {
+                notice!("{} No projects yet", "ℹ".blue());
+                return Ok(());
+            }
+            
+            let width = workspace.projects().keys().map(|name| name.chars().count()).max().unwrap_or(0);
+            for (name, project) in workspace.projects() {
+                let actions = project.list_actions();
+                let priority = actions.iter().filter(|action| action.is_priority()).count();
+                let counts = format!("{} actions, {} priority", actions.len(), priority);
+                let description = match project.description() {
+                    Some(description) => utils::truncate(&description, 60).normal(),
+                    None => "(no description)".dimmed(),
+                };
+                println!("{:<width$}  {:<24} {}", name.yellow(), counts.dimmed(), description, width = width);
+            }
+            Ok(())
+        }
+        
+        Commands::Show { project, title, raw, outline, with_deps, with_backlinks, json } => {
+            // Dependencies and backlinks need every action loaded
+            let mut workspace = if with_deps || with_backlinks {
+                load_workspace(&workspace_path, read_only)?
+            } else {
+                load_workspace_lazy(&workspace_path, read_only)?
+            };
+            workspace.load_action(&project, &title)?;
+            let action = workspace.get_action(&project, &title)?;
+            
+            if raw {
+                let bytes = std::fs::read(&action.file_path)
+                    .with_context(|| format!("Failed to read action file: {}", action.file_path.display()))?;
+                std::io::stdout().write_all(&bytes)?;
+                return Ok(());
+            }
+            
+            if json {
+                let view = ActionView::new(action, workspace.root(), workspace.workflow(), chrono::Utc::now());
+                println!("{}", serde_json::to_string_pretty(&view)?);
+                return Ok(());
+            }
+            
+            if outline {
+                println!("{} {}/{}", "Action:".bold(), project.yellow(), title.cyan());
+                for name in SECTION_NAMES {
+                    if action.section(name).is_none() {
+                        continue;
+                    }
+                    println!("{}", name.bold());
+                    for (level, heading) in action.section_outline(name) {
+                        println!("{}{}", "  ".repeat(level - 2), heading);
+                    }
+                }
+                return Ok(());
+            }
             
             println!("\n{} {}/{}", "Action:".bold(), project.yellow(), title.cyan());
             println!("{} {}", "Status:".bold(), action.status().to_string().green());
+            if !action.is_active() {
+                println!("{} {}", "Lifecycle:".bold(), action.lifecycle().to_string().red());
+            }
+            println!("{} {}", "Type:".bold(), action.action_type());
+            let config = workspace.config();
+            let now = chrono::Utc::now();
+            for (label, timestamp) in [("Created:", &action.created_at), ("Updated:", &action.updated_at)] {
+                println!("{} {} {}",
+                    label.bold(),
+                    utils::format_timestamp(timestamp, config, cli.local),
+                    format!("({})", utils::time_ago(timestamp, now)).dimmed()
+                );
+            }
+            if action.has_meta_graph() {
+                println!("{} {}", "Meta-graph:".bold(), 
+                    utils::format_relative_path(action.meta_graph_path(), workspace.root()).blue()
+                );
+            }
+            let attachments = action.attachments().len();
+            if attachments > 0 {
+                println!("{} {}", "Attachments:".bold(), attachments);
+            }
             if action.is_priority() {
-                println!("{} {}", "Priority:".bold(), "HIGH".red());
+                println!("{} {}", "Priority:".bold(), action.priority().to_string().to_uppercase().red());
+            }
+            if let Some(assignee) = &action.assignee {
+                println!("{} {}", "Assignee:".bold(), assignee);
+            }
+            if let Some(estimate) = action.estimate {
+                println!("{} {}", "Estimate:".bold(), utils::format_duration(estimate));
+            }
+            if let Some(spent) = action.time_spent {
+                println!("{} {}", "Time spent:".bold(), utils::format_duration(spent));
+            }
+            if let Some(due) = action.due {
+                let now = chrono::Utc::now();
+                let days = action.days_until_due(now).unwrap_or_default();
+                let when = if action.is_overdue(now) {
+                    format!("overdue by {} days", -days).red()
+                } else {
+                    format!("in {} days", days).normal()
+                };
+                println!("{} {} ({})", "Due:".bold(), utils::format_timestamp(&due, config, cli.local), when);
             }
             
             if let Some(notes) = action.notes() {
@@ -185,44 +1259,370 @@ fn main() -> Result<()> {
                 println!("\n{}:\n{}", "Analysis of Impact".bold(), impact);
             }
             
+            if let Some(children) = action.children() {
+                println!("\n{}:\n{}", "Children".bold(), children);
+            }
+            
+            if let Some(log) = action.log() {
+                println!("\n{}:\n{}", "Log".bold(), log);
+            }
+            
+            let print_related = |heading: &str, related: Vec<&Action>| {
+                println!("\n{}:", heading.bold());
+                if related.is_empty() {
+                    println!("  (none)");
+                }
+                for other in related {
+                    let status = other.status().to_string();
+                    let status = if *other.status() == Status::Published { status.green() } else { status.yellow() };
+                    println!("  [{}] {}/{}", status, other.project(), other.title());
+                }
+            };
+            if with_deps {
+                print_related("Dependencies", workspace.dependencies(action));
+            }
+            if with_backlinks {
+                print_related("Backlinks", workspace.backlinks(action));
+            }
+            
             Ok(())
         }
         
-        Commands::Status { project, title, status } => {
-            let mut workspace = Workspace::load(&workspace_path)?;
-            let new_status = Status::from_str(&status)?;
-            workspace.update_action_status(&project, &title, new_status)?;
-            println!("{} Updated status of {}/{} to {}", 
+        Commands::Delete { project, title, force } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let action = workspace.get_action(&project, &title)?;
+            
+            if !force {
+                if !std::io::stdin().is_terminal() {
+                    anyhow::bail!("Refusing to delete {}/{} without confirmation; pass --force", project, title);
+                }
+                let also = if action.has_meta_graph() { " and its meta-graph" } else { "" };
+                let confirmed = dialoguer::Confirm::new()
+                    .with_prompt(format!("Delete {}/{}{}?", project, action.title(), also))
+                    .default(false)
+                    .interact()?;
+                if !confirmed {
+                    notice!("{} Nothing deleted", "ℹ".blue());
+                    return Ok(());
+                }
+            }
+            
+            let deleted = workspace.delete_action(&project, &title)?;
+            notice!("{} Deleted action: {} from project {}", "✓".green(), deleted.title().cyan(), project.yellow());
+            Ok(())
+        }
+        
+        Commands::Rename { project, title, new_title } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let title = workspace.get_action(&project, &title)?.title().to_string();
+            workspace.rename_action(&project, &title, &new_title)?;
+            notice!("{} Renamed {}/{} to {}", "✓".green(), project.yellow(), title, new_title.cyan());
+            Ok(())
+        }
+        
+        Commands::Move { project, title, dest_project } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            workspace.move_action(&project, &title, &dest_project)?;
+            notice!("{} Moved {} from {} to {}", "✓".green(), title.cyan(), project.yellow(), dest_project.yellow());
+            Ok(())
+        }
+        
+        Commands::Mv { project, title, new_title, to } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let dest_project = to.unwrap_or_else(|| project.clone());
+            let mut title = workspace.get_action(&project, &title)?.title().to_string();
+            if dest_project != project {
+                workspace.move_action(&project, &title, &dest_project)?;
+                notice!("{} Moved {} from {} to {}", "✓".green(), title.cyan(), project.yellow(), dest_project.yellow());
+            }
+            if let Some(new_title) = new_title.filter(|new_title| *new_title != title) {
+                workspace.rename_action(&dest_project, &title, &new_title)?;
+                notice!("{} Renamed {}/{} to {}", "✓".green(), dest_project.yellow(), title, new_title.cyan());
+                title = new_title;
+            }
+            detail!("  {} {}", "→".dimmed(), workspace.get_action(&dest_project, &title)?.file_path.display());
+            Ok(())
+        }
+        
+        Commands::Status { project, title, status, all, dry_run, no_scaffold, force } => {
+            let mut workspace = if all {
+                load_workspace(&workspace_path, read_only)?
+            } else {
+                load_workspace_lazy(&workspace_path, read_only)?
+            };
+            
+            if all {
+                let outcomes = workspace.advance_project(&project, dry_run, !no_scaffold)?;
+                let (mut advanced, mut blocked, mut finished, mut inactive) = (0, 0, 0, 0);
+                let verb = if dry_run { "Would advance" } else { "Advanced" };
+                
+                for outcome in &outcomes {
+                    match outcome {
+                        AdvanceOutcome::Advanced { title, from, to } => {
+                            advanced += 1;
+                            notice!("{} {} {}/{}: {} → {}", 
+                                "✓".green(), 
+                                verb,
+                                project.yellow(), 
+                                title.cyan(), 
+                                from, 
+                                to.to_string().green()
+                            );
+                        }
+                        AdvanceOutcome::Blocked { title, status, missing } => {
+                            blocked += 1;
+                            println!("{} Blocked {}/{} at {}: missing {}", 
+                                "✗".red(), 
+                                project.yellow(), 
+                                title.cyan(), 
+                                status, 
+                                missing.join(", ")
+                            );
+                        }
+                        AdvanceOutcome::Finished { .. } => finished += 1,
+                        AdvanceOutcome::Inactive { .. } => inactive += 1,
+                    }
+                }
+                
+                notice!("{} {} {}, {} blocked, {} already published, {} inactive", 
+                    "ℹ".blue(), 
+                    advanced, 
+                    if dry_run { "would advance" } else { "advanced" },
+                    blocked, 
+                    finished,
+                    inactive
+                );
+                return Ok(());
+            }
+            
+            let (title, status) = title.zip(status).expect("clap requires title and status without --all");
+            let new_status = workspace.parse_status(&status)?;
+            workspace.load_action(&project, &title)?;
+            let added = workspace.update_action_status_checked(&project, &title, new_status, !no_scaffold, force)?;
+            notice!("{} Updated status of {}/{} to {}", 
                 "✓".green(), 
                 project.yellow(), 
                 title.cyan(), 
                 status.green()
             );
+            detail!("  {} {}", "→".dimmed(), workspace.get_action(&project, &title)?.file_path.display());
+            for section in added {
+                notice!("  {} Added a {} section to fill in", "+".green(), section);
+            }
+            Ok(())
+        }
+        
+        Commands::Next { project, title, all, only_project, status } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            
+            if !all {
+                let (project, title) = project.zip(title).expect("clap requires project and title without --all");
+                let action_desc = format!("{}/{}", project.yellow(), title.cyan());
+                match workspace.next_action_status(&project, &title)? {
+                    Some(status) => notice!("{} Moved {} to {}", "✓".green(), action_desc, status.to_string().green()),
+                    None => notice!("{} {} is already {}; nothing comes after it", "ℹ".blue(), action_desc, workspace.workflow().last()),
+                }
+                return Ok(());
+            }
+            
+            let filter = ActionFilter {
+                project: only_project,
+                status: status.as_deref().map(|status| workspace.parse_status(status)).transpose()?,
+                ..Default::default()
+            };
+            let targets: Vec<(String, String)> = workspace.filter_actions(&filter)?
+                .iter()
+                .map(|action| (action.project().to_string(), action.title().to_string()))
+                .collect();
+            
+            let (mut changed, mut unchanged) = (0, 0);
+            for (project, title) in &targets {
+                match workspace.next_action_status(project, title) {
+                    Ok(Some(status)) => {
+                        changed += 1;
+                        notice!("{} {}/{} → {}", "✓".green(), project.yellow(), title.cyan(), status.to_string().green());
+                    }
+                    Ok(None) => unchanged += 1,
+                    Err(e) => {
+                        unchanged += 1;
+                        println!("{} {}/{}: {:#}", "✗".red(), project.yellow(), title.cyan(), e);
+                    }
+                }
+            }
+            notice!("{} Advanced {} of {} action(s)", "ℹ".blue(), changed, changed + unchanged);
+            Ok(())
+        }
+        
+        Commands::Prev { project, title } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let action_desc = format!("{}/{}", project.yellow(), title.cyan());
+            match workspace.previous_action_status(&project, &title)? {
+                Some(status) => notice!("{} Moved {} back to {}", "✓".green(), action_desc, status.to_string().green()),
+                None => notice!("{} {} is already {}; nothing comes before it", "ℹ".blue(), action_desc, workspace.workflow().first()),
+            }
+            Ok(())
+        }
+        
+        Commands::Block { project, title } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            workspace.set_action_lifecycle(&project, &title, Lifecycle::Blocked)?;
+            notice!("{} Blocked {}/{}", "✓".green(), project.yellow(), title.cyan());
+            Ok(())
+        }
+        
+        Commands::Cancel { project, title } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            workspace.set_action_lifecycle(&project, &title, Lifecycle::Cancelled)?;
+            notice!("{} Cancelled {}/{}", "✓".green(), project.yellow(), title.cyan());
+            Ok(())
+        }
+        
+        Commands::Reactivate { project, title } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            workspace.set_action_lifecycle(&project, &title, Lifecycle::Active)?;
+            notice!("{} Reactivated {}/{}", "✓".green(), project.yellow(), title.cyan());
             Ok(())
         }
         
-        Commands::Priority { project, title, set } => {
-            let mut workspace = Workspace::load(&workspace_path)?;
-            workspace.set_action_priority(&project, &title, set)?;
+        Commands::Priority { project, title, level, set } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let level = level.unwrap_or(Priority::from(set));
+            workspace.set_action_priority(&project, &title, level)?;
             let action_desc = format!("{}/{}", project.yellow(), title.cyan());
-            if set {
-                println!("{} Set priority for {}", "✓".green(), action_desc);
+            if level.is_set() {
+                notice!("{} Set {} priority for {}", "✓".green(), level, action_desc);
             } else {
-                println!("{} Removed priority from {}", "✓".green(), action_desc);
+                notice!("{} Removed priority from {}", "✓".green(), action_desc);
+            }
+            Ok(())
+        }
+        
+        Commands::Due { project, title, date, clear: _ } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let due = date.as_deref().map(parse_due).transpose()?;
+            let warning = workspace.set_action_due(&project, &title, due)?;
+            let action_desc = format!("{}/{}", project.yellow(), title.cyan());
+            match due {
+                Some(due) => notice!("{} {} is due {}", "✓".green(), action_desc, due.format("%Y-%m-%d %H:%M UTC")),
+                None => notice!("{} Cleared the due date of {}", "✓".green(), action_desc),
+            }
+            if let Some(warning) = warning {
+                println!("{} {}", "⚠".yellow(), warning);
             }
             Ok(())
         }
         
-        Commands::Edit { project, title } => {
-            let workspace = Workspace::load(&workspace_path)?;
+        Commands::Estimate { project, title, duration, clear: _ } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let estimate = duration.as_deref().map(utils::parse_duration).transpose()?;
+            workspace.set_action_estimate(&project, &title, estimate)?;
+            let action_desc = format!("{}/{}", project.yellow(), title.cyan());
+            match estimate {
+                Some(estimate) => notice!("{} Estimated {} at {}", "✓".green(), action_desc, utils::format_duration(estimate)),
+                None => notice!("{} Cleared the estimate of {}", "✓".green(), action_desc),
+            }
+            Ok(())
+        }
+        
+        Commands::LogTime { project, title, duration } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let duration = utils::parse_duration(&duration)?;
+            let total = workspace.log_action_time(&project, &title, duration)?;
+            notice!("{} Logged {} on {}/{} ({} in total)",
+                "✓".green(),
+                utils::format_duration(duration),
+                project.yellow(),
+                title.cyan(),
+                utils::format_duration(total)
+            );
+            Ok(())
+        }
+        
+        Commands::Assign { project, title, who } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            workspace.set_action_assignee(&project, &title, Some(&who))?;
+            notice!("{} Assigned {}/{} to {}", "✓".green(), project.yellow(), title.cyan(), who.trim().bold());
+            Ok(())
+        }
+        
+        Commands::Unassign { project, title } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            workspace.set_action_assignee(&project, &title, None)?;
+            notice!("{} Unassigned {}/{}", "✓".green(), project.yellow(), title.cyan());
+            Ok(())
+        }
+        
+        Commands::Dep { command } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            match command {
+                DepCommand::Add { project, title, target } => {
+                    let action_desc = format!("{}/{}", project.yellow(), title.cyan());
+                    if workspace.add_dependency(&project, &title, &target)? {
+                        notice!("{} {} now depends on {}", "✓".green(), action_desc, target.cyan());
+                    } else {
+                        notice!("{} {} already depends on {}", "ℹ".blue(), action_desc, target.cyan());
+                    }
+                }
+                DepCommand::Remove { project, title, target } => {
+                    let action_desc = format!("{}/{}", project.yellow(), title.cyan());
+                    if !workspace.remove_dependency(&project, &title, &target)? {
+                        anyhow::bail!("{}/{} doesn't depend on {}", project, title, target);
+                    }
+                    notice!("{} {} no longer depends on {}", "✓".green(), action_desc, target.cyan());
+                }
+                DepCommand::Blocked => {
+                    let blocked = workspace.blocked_actions();
+                    if blocked.is_empty() {
+                        notice!("{} No actions are waiting on dependencies", "ℹ".blue());
+                    }
+                    for (action, pending) in blocked {
+                        println!("{}/{}", action.project().yellow(), action.title().cyan());
+                        for dependency in pending {
+                            println!("  waits on [{}] {}/{}", dependency.status(), dependency.project(), dependency.title());
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+        
+        Commands::Comment { project, title, text, author } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let author = author.unwrap_or_else(|| utils::detect_author(workspace.root()));
+            workspace.comment_on_action(&project, &title, &author, &text)?;
+            notice!("{} Added comment to {}/{}", 
+                "✓".green(), 
+                project.yellow(), 
+                title.cyan()
+            );
+            Ok(())
+        }
+        
+        Commands::Edit { project, title, create, priority, dry_run } => {
+            let mut workspace = load_workspace_lazy(&workspace_path, read_only)?;
+            let missing = create && workspace.load_action(&project, &title).is_err();
+            
+            if dry_run {
+                let verb = if missing { "Would create and open" } else { "Would open" };
+                println!("{} {}/{} in {}", verb, project.yellow(), title.cyan(), workspace.editor());
+                return Ok(());
+            }
+            
+            if missing {
+                // A new action's id depends on every other action in the project
+                workspace = load_workspace(&workspace_path, read_only)?;
+                let options = CreateOptions { priority: priority.unwrap_or_default(), ..Default::default() };
+                workspace.create_action_with(&project, &title, &options)?;
+                notice!("{} Created action: {} in project {}", "✓".green(), title.cyan(), project.yellow());
+            }
             workspace.edit_action(&project, &title)?;
             Ok(())
         }
         
         Commands::MetaGraph { project, title } => {
-            let workspace = Workspace::load(&workspace_path)?;
+            let workspace = load_workspace(&workspace_path, read_only)?;
             let _meta_graph_path = workspace.create_meta_graph(&project, &title)?;
-            println!("{} Created meta-graph directory for {}/{}", 
+            notice!("{} Created meta-graph directory for {}/{}", 
                 "✓".green(), 
                 project.yellow(), 
                 title.cyan()
@@ -230,18 +1630,608 @@ fn main() -> Result<()> {
             Ok(())
         }
         
-        Commands::Validate => {
-            let workspace = Workspace::load(&workspace_path)?;
-            match workspace.validate() {
-                Ok(()) => {
-                    println!("{} Workspace validation passed", "✓".green());
+        Commands::Attach { project, title, file } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let path = workspace.attach(&project, &title, &file)?;
+            notice!("{} Attached {} to {}/{}",
+                "✓".green(),
+                utils::format_relative_path(&path, workspace.root()).blue(),
+                project.yellow(),
+                title.cyan()
+            );
+            Ok(())
+        }
+        
+        Commands::Attachments { project, title } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let attachments = workspace.get_action(&project, &title)?.attachments();
+            
+            if attachments.is_empty() {
+                notice!("{} No attachments for {}/{}", "ℹ".blue(), project, title);
+                return Ok(());
+            }
+            
+            for path in attachments {
+                let size = std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or_default();
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                println!("  {:>10}  {}", format_size(size).dimmed(), name);
+            }
+            Ok(())
+        }
+        
+        Commands::Detach { project, title, name } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            workspace.detach(&project, &title, &name)?;
+            notice!("{} Removed {} from {}/{}", "✓".green(), name, project.yellow(), title.cyan());
+            Ok(())
+        }
+        
+        Commands::Migrate { meta_graph_layout, dry_run, diff } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let layout = MetaGraphLayout::from_str(&meta_graph_layout)?;
+            
+            if dry_run || diff {
+                for planned in workspace.meta_graph_moves(layout)? {
+                    println!("Would move {} -> {}",
+                        utils::format_relative_path(&planned.from, workspace.root()).blue(),
+                        utils::format_relative_path(&planned.to, workspace.root()).blue()
+                    );
+                    if diff {
+                        for change in &planned.changes {
+                            print!("{}", change.diff(workspace.root()));
+                        }
+                    }
+                }
+                return Ok(());
+            }
+            
+            let moved = workspace.migrate_meta_graphs(layout)?;
+            notice!("{} Switched meta-graph layout to {} ({} moved)", 
+                "✓".green(), 
+                meta_graph_layout.to_lowercase().cyan(),
+                moved
+            );
+            Ok(())
+        }
+        
+        Commands::Config { command } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            match command {
+                ConfigCommand::Get { key } => match workspace.config().get(&key)? {
+                    Some(value) => println!("{}", value),
+                    None => notice!("{} {} is not set", "ℹ".blue(), key),
+                },
+                ConfigCommand::Set { key, value } => {
+                    workspace.set_config(&key, &value)?;
+                    notice!("{} Set {} to {}", "✓".green(), key.bold(), value.cyan());
+                }
+                ConfigCommand::Unset { key } => {
+                    workspace.unset_config(&key)?;
+                    notice!("{} Reset {} to its default", "✓".green(), key.bold());
                 }
-                Err(e) => {
-                    println!("{} Workspace validation failed: {}", "✗".red(), e);
-                    std::process::exit(1);
+                ConfigCommand::List => {
+                    for key in config::SETTING_KEYS {
+                        match workspace.config().get(key)? {
+                            Some(value) => println!("{} = {}", key, value),
+                            None => println!("{} = {}", key, "(unset)".dimmed()),
+                        }
+                    }
                 }
             }
             Ok(())
         }
+        
+        Commands::Search { query, regex } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let hits = workspace.search(&query, regex)?;
+            if hits.is_empty() {
+                notice!("{} No actions match '{}'", "ℹ".blue(), query);
+                return Ok(());
+            }
+            
+            let source = if regex { query.clone() } else { regex::escape(&query) };
+            let matcher = regex::RegexBuilder::new(&source).case_insensitive(true).build()?;
+            let mut last: Option<&Path> = None;
+            for hit in &hits {
+                if last != Some(hit.action.file_path.as_path()) {
+                    println!("{}/{}", hit.action.project().yellow(), hit.action.title().cyan());
+                    last = Some(hit.action.file_path.as_path());
+                }
+                println!("  {}: {}", hit.section.dimmed(), highlight_matches(&snippet(&hit.line, &matcher), &matcher));
+            }
+            Ok(())
+        }
+        
+        Commands::Grep { pattern, context, regex, ignore_case, project } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let source = if regex { pattern.clone() } else { regex::escape(&pattern) };
+            let matcher = regex::RegexBuilder::new(&source)
+                .case_insensitive(ignore_case)
+                .build()
+                .with_context(|| format!("Invalid pattern: {}", pattern))?;
+            
+            let actions = workspace.projects()
+                .values()
+                .filter(|p| project.as_ref().is_none_or(|name| &p.name == name))
+                .flat_map(|p| p.list_actions());
+            let hunks = grep::grep(actions, &matcher, context);
+            
+            for (i, hunk) in hunks.iter().enumerate() {
+                if context > 0 && i > 0 {
+                    println!("{}", "--".dimmed());
+                }
+                for (number, line, matched) in &hunk.lines {
+                    let location = format!("{}/{}:{}:{}", hunk.project, hunk.title, hunk.section, number);
+                    if *matched {
+                        println!("{}: {}", location.purple(), highlight_matches(line, &matcher));
+                    } else {
+                        println!("{}- {}", location.dimmed(), line);
+                    }
+                }
+            }
+            
+            if hunks.is_empty() {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        
+        Commands::Batch { continue_on_error, atomic } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)
+                .with_context(|| "Failed to read operations from stdin")?;
+            let ops = batch::parse(&input)?;
+            
+            let options = BatchOptions { continue_on_error, atomic };
+            let results = batch::apply(&mut workspace, &ops, options)?;
+            println!("{}", serde_json::to_string_pretty(&results)?);
+            
+            if results.iter().any(|result| result.outcome != batch::Outcome::Ok) {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        
+        Commands::Duplicates { threshold, cross_project } => {
+            if !(0.0..=1.0).contains(&threshold) {
+                anyhow::bail!("Invalid threshold: {}. Expected a value between 0 and 1", threshold);
+            }
+            
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let pairs = workspace.near_duplicates(threshold, cross_project);
+            if pairs.is_empty() {
+                notice!("{} No similar actions found", "✓".green());
+                return Ok(());
+            }
+            
+            for (score, action, other) in pairs {
+                println!("{:>4.0}%  {}/{}  {}/{}",
+                    score * 100.0,
+                    action.project().yellow(),
+                    action.title().cyan(),
+                    other.project().yellow(),
+                    other.title().cyan()
+                );
+            }
+            Ok(())
+        }
+        
+        Commands::Fmt { project, dry_run, diff } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let changes = workspace.format_actions(project.as_deref(), dry_run || diff)?;
+            
+            for change in &changes {
+                if diff {
+                    print!("{}", change.diff(workspace.root()));
+                } else {
+                    let verb = if dry_run { "Would format" } else { "Formatted" };
+                    println!("{} {}", verb, utils::format_relative_path(&change.path, workspace.root()));
+                }
+            }
+            if changes.is_empty() {
+                notice!("{} All action files are already formatted", "✓".green());
+            }
+            Ok(())
+        }
+        
+        Commands::Graph { from, project, format: _, depth, reverse } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let graph = match (from, project) {
+                (Some(reference), _) => {
+                    let (project, title) = reference.split_once('/')
+                        .with_context(|| format!("Expected PROJECT/TITLE, got '{}'", reference))?;
+                    let start = workspace.get_action(project, title)?;
+                    let direction = if reverse { Direction::Reverse } else { Direction::Forward };
+                    workspace.subgraph(start, depth.unwrap_or(usize::MAX), direction)
+                }
+                (None, Some(project)) => workspace.project_graph(&project)?,
+                (None, None) => workspace.graph(),
+            };
+            print!("{}", graph.to_dot(workspace.workflow()));
+            Ok(())
+        }
+        
+        Commands::Rollup { project, title, apply } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let rollup = workspace.rollup(&project, &title)?;
+            
+            for (child_project, child_title, status) in &rollup.children {
+                println!("  [{}] {}/{}", status.to_string().cyan(), child_project, child_title);
+            }
+            for target in &rollup.unresolved {
+                println!("  {} {} does not point at an action", "⚠".yellow(), target);
+            }
+            
+            let Some(status) = rollup.status.clone() else {
+                notice!("{} {}/{} has no children", "ℹ".blue(), project, title);
+                return Ok(());
+            };
+            
+            let total = rollup.children.len();
+            println!("\n{}/{}: {} of {} children published ({:.0}%), rolled-up status {}", 
+                project, 
+                title, 
+                rollup.published(), 
+                total, 
+                rollup.published() as f64 * 100.0 / total as f64,
+                status.to_string().bold()
+            );
+            
+            if apply {
+                workspace.update_action_status(&project, &title, status.clone())?;
+                notice!("{} Set {}/{} to {}", "✓".green(), project, title, status);
+            }
+            Ok(())
+        }
+        
+        Commands::Export { template, format, each, project, filter, output } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let filter = ActionFilter {
+                project,
+                expr: filter.as_deref()
+                    .map(|filter| FilterExpr::parse_with(filter, workspace.workflow()))
+                    .transpose()
+                    .context("Invalid --filter expression")?,
+                ..Default::default()
+            };
+            let actions = workspace.filter_actions(&filter)?;
+            let rendered = match (template, format) {
+                (Some(template), _) => export::render_template(&workspace, &actions, &template, each)?,
+                (None, Some(format)) => workspace.export(ExportFormat::from_str(&format)?, &filter)?,
+                (None, None) => unreachable!("clap requires --template or --format"),
+            };
+            
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    notice!("{} Exported {} actions to {}", "✓".green(), actions.len(), path.display());
+                }
+                None => print!("{}", rendered),
+            }
+            Ok(())
+        }
+        
+        Commands::Undo => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            match workspace.undo_last_change()? {
+                Some(change) => notice!("{} Undid {}", "✓".green(), change),
+                None => notice!("{} Nothing to undo", "ℹ".blue()),
+            }
+            Ok(())
+        }
+        
+        Commands::Report { output } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let report = report::render(&workspace, chrono::Utc::now())?;
+            
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, report)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    notice!("{} Wrote report to {}", "✓".green(), path.display());
+                }
+                None => print!("{}", report),
+            }
+            Ok(())
+        }
+        
+        Commands::Import { file, overwrite } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let data = export::parse_import(&content)
+                .with_context(|| format!("Failed to import {}", file.display()))?;
+            let summary = workspace.import(data, overwrite)?;
+            
+            notice!(
+                "{} Imported {} actions ({} new, {} overwritten); created {} projects",
+                "✓".green(),
+                summary.actions_created + summary.actions_overwritten,
+                summary.actions_created,
+                summary.actions_overwritten,
+                summary.projects_created
+            );
+            Ok(())
+        }
+        
+        Commands::Tags { related, sort } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let index = workspace.tag_index();
+            
+            let mut rows = match &related {
+                Some(tag) => {
+                    let tag = tag.trim_start_matches('#');
+                    if !index.actions.contains_key(tag) {
+                        anyhow::bail!("No action is tagged #{}", tag);
+                    }
+                    index.related(tag)
+                }
+                None => index.counts(),
+            };
+            if sort == "name" {
+                rows.sort_by(|a, b| a.0.cmp(b.0));
+            }
+            
+            if rows.is_empty() {
+                notice!("{} No tags found", "ℹ".blue());
+                return Ok(());
+            }
+            
+            for (tag, count) in rows {
+                println!("{:>5}  #{}", count, tag.cyan());
+            }
+            Ok(())
+        }
+        
+        Commands::Tag { command } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            match command {
+                TagCommand::Add { project, title, tag } => {
+                    let action_desc = format!("{}/{}", project.yellow(), title.cyan());
+                    let tag = format!("#{}", tag.trim_start_matches('#'));
+                    if workspace.add_action_tag(&project, &title, &tag)? {
+                        notice!("{} Tagged {} with {}", "✓".green(), action_desc, tag.green());
+                    } else {
+                        notice!("{} {} is already tagged {}", "ℹ".blue(), action_desc, tag);
+                    }
+                }
+                TagCommand::Remove { project, title, tag } => {
+                    let tag = format!("#{}", tag.trim_start_matches('#'));
+                    if !workspace.remove_action_tag(&project, &title, &tag)? {
+                        anyhow::bail!("{}/{} isn't tagged {} on its tag line", project, title, tag);
+                    }
+                    notice!("{} Removed {} from {}/{}", "✓".green(), tag, project.yellow(), title.cyan());
+                }
+            }
+            Ok(())
+        }
+        
+        Commands::TagAll { add, remove, rename, project, status, dry_run } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let filter = ActionFilter {
+                project,
+                status: status.as_deref().map(|status| workspace.parse_status(status)).transpose()?,
+                ..Default::default()
+            };
+            let (remove, add) = match &rename {
+                Some(names) => (Some(names[0].as_str()), Some(names[1].as_str())),
+                None => (remove.as_deref(), add.as_deref()),
+            };
+            
+            let changed = workspace.retag(&filter, remove, add, dry_run)?;
+            for (project, title) in &changed {
+                println!("  {}/{}", project.cyan(), title);
+            }
+            
+            let verb = if dry_run { "Would update" } else { "Updated" };
+            notice!("{} {} {} action(s)", "✓".green(), verb, changed.len());
+            Ok(())
+        }
+        
+        Commands::Info => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let info = workspace.info();
+            let row = |label: &str, value: String| println!("{:<16} {}", format!("{}:", label).bold(), value);
+            
+            row("Workspace", info.root.display().to_string().cyan().to_string());
+            row("Found via", discovered_by.to_string());
+            row("Marker", if info.legacy_marker { 
+                "legacy file (upgraded on next config write)".yellow().to_string() 
+            } else { 
+                config::STATE_DIR.to_string() 
+            });
+            row("Config", info.config_file.map_or("none (defaults)".dimmed().to_string(), |path| path.display().to_string()));
+            row("Projects", info.projects.to_string());
+            row("Actions", info.actions.to_string());
+            let workflow = workspace.workflow();
+            let statuses = workflow.statuses().iter().map(|s| s.to_string()).collect::<Vec<_>>().join(" → ");
+            row("Workflow", format!("{} ({})", if workflow.is_builtin() { "built-in" } else { "custom" }, statuses));
+            row("Action types", if info.custom_types.is_empty() { 
+                "built-in".to_string() 
+            } else { 
+                format!("built-in + {}", info.custom_types.join(", ")) 
+            });
+            row("Meta-graphs", format!("{:?}", info.meta_graph_layout).to_lowercase());
+            row("Symlinks", if info.follow_symlinks { "followed" } else { "ignored" }.to_string());
+            row("Editor", info.editor);
+            Ok(())
+        }
+        
+        Commands::Stats { json, snapshot, stale_days } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let stats = workspace.stats(stale_days);
+            
+            if snapshot {
+                workspace.ensure_writable()?;
+                stats::record_snapshot(workspace.root(), Snapshot {
+                    timestamp: chrono::Utc::now(),
+                    stats: stats.clone(),
+                })?;
+            }
+            
+            if json {
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+                return Ok(());
+            }
+            
+            let statuses = workspace.workflow().statuses();
+            let width = statuses.iter()
+                .map(|status| status.to_string().len())
+                .chain(stats.by_project.keys().map(|name| name.chars().count()))
+                .chain(["with meta-graph".len()])
+                .max()
+                .unwrap_or_default();
+            let row = |label: &str, value: usize| println!("{:<width$} {:>5}", label, value, width = width);
+            
+            for status in &statuses {
+                row(&status.to_string(), stats.count(status));
+            }
+            println!("{:<width$} {:>5}", "total".bold(), stats.total, width = width);
+            println!("{:<width$} {:>4.0}%", "complete".bold(), stats.completion_ratio * 100.0, width = width);
+            
+            if !stats.by_project.is_empty() {
+                println!();
+                for (project, count) in &stats.by_project {
+                    row(project, *count);
+                }
+            }
+            
+            println!();
+            row("priority", stats.priority);
+            row("with meta-graph", stats.with_meta_graph);
+            row(&format!("stale (>{}d)", stats.stale_days), stats.stale);
+            
+            if !stats.time_by_project.is_empty() {
+                println!();
+                println!("{:<width$} {:>8} {:>8}", "time".bold(), "spent", "estimate", width = width);
+                for (project, totals) in &stats.time_by_project {
+                    println!("{:<width$} {:>8} {:>8}",
+                        project,
+                        utils::format_duration(chrono::Duration::minutes(totals.spent_minutes)),
+                        utils::format_duration(chrono::Duration::minutes(totals.estimate_minutes)),
+                        width = width
+                    );
+                }
+            }
+            if snapshot {
+                notice!("{} Recorded snapshot", "✓".green());
+            }
+            Ok(())
+        }
+        
+        Commands::Worklog { since, author, tz } => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let offset = worklog::parse_offset(&tz)?;
+            let since = match since {
+                Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                    .with_context(|| format!("Invalid date: {}. Expected YYYY-MM-DD", date))?,
+                None => chrono::Utc::now().with_timezone(&offset).date_naive() - chrono::Duration::days(7),
+            };
+            
+            let options = WorklogOptions { since: Some(since), author, offset };
+            let actions = workspace.projects().values().flat_map(|project| project.list_actions());
+            let log = worklog::collect(actions, &options);
+            
+            if log.is_empty() {
+                notice!("{} No activity since {}", "ℹ".blue(), since);
+                return Ok(());
+            }
+            
+            for (day, entries) in log {
+                println!("{}", day.format("%Y-%m-%d %a").to_string().bold());
+                for entry in entries {
+                    println!("  {}/{} {}", entry.project.yellow(), entry.title.cyan(), entry.activity.to_string().dimmed());
+                }
+            }
+            Ok(())
+        }
+        
+        Commands::Trend => {
+            let workspace = load_workspace(&workspace_path, read_only)?;
+            let history = stats::load_history(workspace.root())?;
+            
+            let (Some(first), Some(last)) = (history.first(), history.last()) else {
+                notice!("{} No snapshots recorded yet. Run 'action stats --snapshot' to start", "ℹ".blue());
+                return Ok(());
+            };
+            
+            println!("{} snapshots from {} to {}\n", 
+                history.len(), 
+                first.timestamp.format("%Y-%m-%d"), 
+                last.timestamp.format("%Y-%m-%d")
+            );
+            
+            let mut rows: Vec<(String, Vec<usize>)> = workspace.workflow().statuses()
+                .iter()
+                .map(|status| (status.to_string(), history.iter().map(|s| s.stats.count(status)).collect()))
+                .collect();
+            rows.push(("total".to_string(), history.iter().map(|s| s.stats.total).collect()));
+            
+            for (name, values) in rows {
+                let start = values[0];
+                let end = values[values.len() - 1];
+                let change = end as i64 - start as i64;
+                let change = match change {
+                    0 => "±0".dimmed(),
+                    c if c > 0 => format!("+{}", c).yellow(),
+                    c => c.to_string().green(),
+                };
+                println!("{:<10} {} {:>4} → {:<4} {}", name, stats::sparkline(&values).cyan(), start, end, change);
+            }
+            Ok(())
+        }
+        
+        Commands::Validate { json, fix_ids, strict } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            if fix_ids {
+                for (project, title) in workspace.fix_duplicate_ids()? {
+                    eprintln!("{} Assigned a new id to {}/{}", "✓".green(), project.yellow(), title.cyan());
+                }
+            }
+            
+            let issues = workspace.check();
+            let has_errors = if json {
+                println!("{}", serde_json::to_string_pretty(&issues)?);
+                fails_validation(&issues, strict)
+            } else {
+                print_validation(&issues, strict)
+            };
+            
+            if has_errors {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        
+        Commands::Watch { strict } => {
+            let mut workspace = load_workspace(&workspace_path, read_only)?;
+            let watcher = WorkspaceWatcher::new(workspace.root())?;
+            notice!("{} Watching {} for changes (Ctrl-C to stop)", "ℹ".blue(), workspace.root().display());
+            print_validation(&workspace.check(), strict);
+            
+            // Nothing is written while watching, so Ctrl-C's default exit is clean
+            while let Some(changed) = watcher.next_batch(watch::DEFAULT_DEBOUNCE)? {
+                println!();
+                for path in &changed {
+                    println!("{} {}", "~".blue(), utils::format_relative_path(path, watcher.root()));
+                }
+                for project in watch::affected_projects(watcher.root(), &changed) {
+                    if let Err(e) = workspace.reload_project(&project) {
+                        println!("{} Failed to reload {}: {:#}", "✗".red(), project, e);
+                    }
+                }
+                print_validation(&workspace.check(), strict);
+            }
+            Ok(())
+        }
+        
+        Commands::Completions { shell } => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+            Ok(())
+        }
     }
-}
\ No newline at end of file
+}
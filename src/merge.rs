@@ -0,0 +1,175 @@
+//! Reconciling two copies of the same action edited in different places.
+//!
+//! Both copies must share an `id`; merging actions with different ids is an
+//! error, since they are different actions that happen to look alike.
+
+use crate::action::SECTION_NAMES;
+use crate::Action;
+use thiserror::Error;
+
+/// How `Action::merge` picks between differing values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Take every differing field from the copy with the later `updated_at`
+    PreferNewer,
+    PreferSelf,
+    PreferOther,
+    /// Like `PreferNewer` for fields, but keep the text of both copies for
+    /// sections that differ
+    SectionUnion,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MergeError {
+    #[error("Cannot merge different actions: {ours} and {theirs}")]
+    IdMismatch { ours: String, theirs: String },
+}
+
+impl Action {
+    /// Names of the fields and sections whose values differ from `other`.
+    /// Timestamps and file locations are bookkeeping and never conflict.
+    pub fn conflicts_with(&self, other: &Action) -> Vec<&'static str> {
+        let mut conflicts = Vec::new();
+        
+        let fields = [
+            ("title", self.title != other.title),
+            ("project", self.project != other.project),
+            ("status", self.status != other.status),
+            ("lifecycle", self.lifecycle != other.lifecycle),
+            ("priority", self.priority != other.priority),
+            ("type", self.action_type != other.action_type),
+            ("due", self.due != other.due),
+            ("tags", self.tag_line != other.tag_line),
+            ("depends_on", self.depends_on != other.depends_on),
+        ];
+        conflicts.extend(fields.iter().filter(|(_, differs)| *differs).map(|(name, _)| *name));
+        
+        conflicts.extend(SECTION_NAMES.iter().filter(|name| self.section(name) != other.section(name)));
+        conflicts
+    }
+    
+    /// Merge `other` into a copy of `self`, field by field and section by section.
+    ///
+    /// The result keeps `self`'s file path, the earlier `created_at` and the
+    /// later `updated_at`.
+    pub fn merge(&self, other: &Action, strategy: MergeStrategy) -> Result<Action, MergeError> {
+        if self.id != other.id {
+            return Err(MergeError::IdMismatch { ours: self.id.to_string(), theirs: other.id.to_string() });
+        }
+        
+        let take_other = match strategy {
+            MergeStrategy::PreferSelf => false,
+            MergeStrategy::PreferOther => true,
+            MergeStrategy::PreferNewer | MergeStrategy::SectionUnion => other.updated_at > self.updated_at,
+        };
+        let preferred = if take_other { other } else { self };
+        
+        let mut merged = self.clone();
+        merged.title = preferred.title.clone();
+        merged.project = preferred.project.clone();
+        merged.status = preferred.status.clone();
+        merged.lifecycle = preferred.lifecycle;
+        merged.priority = preferred.priority;
+        merged.action_type = preferred.action_type.clone();
+        merged.due = preferred.due;
+        merged.tag_line = preferred.tag_line.clone();
+        merged.depends_on = preferred.depends_on.clone();
+        if strategy == MergeStrategy::SectionUnion {
+            for tag in &other.tag_line {
+                if !merged.tag_line.contains(tag) {
+                    merged.tag_line.push(tag.clone());
+                }
+            }
+            for dependency in &other.depends_on {
+                if !merged.depends_on.contains(dependency) {
+                    merged.depends_on.push(dependency.clone());
+                }
+            }
+        }
+        
+        for name in SECTION_NAMES {
+            let content = match (self.section(name), other.section(name)) {
+                (Some(ours), Some(theirs)) if strategy == MergeStrategy::SectionUnion => {
+                    Some(union(ours, theirs))
+                }
+                (ours, None) if strategy == MergeStrategy::SectionUnion => ours.cloned(),
+                (None, theirs) if strategy == MergeStrategy::SectionUnion => theirs.cloned(),
+                _ => preferred.section(name).cloned(),
+            };
+            merged.update_section(name, content);
+        }
+        
+        merged.created_at = self.created_at.min(other.created_at);
+        merged.updated_at = self.updated_at.max(other.updated_at);
+        merged.parse_warnings.clear();
+        Ok(merged)
+    }
+}
+
+/// Both texts, without repeating one that already contains the other
+fn union(ours: &str, theirs: &str) -> String {
+    if ours.contains(theirs) {
+        ours.to_string()
+    } else if theirs.contains(ours) {
+        theirs.to_string()
+    } else {
+        format!("{}\n\n{}", ours.trim_end(), theirs.trim_start())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Priority, Status};
+    use chrono::Duration;
+    
+    fn copies() -> (Action, Action) {
+        let mut ours = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        ours.update_section("notes", Some("Shared notes".to_string()));
+        let mut theirs = ours.clone();
+        
+        ours.update_section("statement of action", Some("Ours".to_string()));
+        theirs.update_section("statement of action", Some("Theirs".to_string()));
+        theirs.update_section("analysis of impact", Some("Only theirs".to_string()));
+        theirs.set_status(Status::Design);
+        theirs.updated_at = ours.updated_at + Duration::hours(1);
+        (ours, theirs)
+    }
+    
+    #[test]
+    fn test_conflicts_with_lists_differing_fields_and_sections() {
+        let (ours, theirs) = copies();
+        assert_eq!(ours.conflicts_with(&theirs), ["status", "Statement of Action", "Analysis of Impact"]);
+        assert!(ours.conflicts_with(&ours.clone()).is_empty());
+    }
+    
+    #[test]
+    fn test_merge_strategies() {
+        let (ours, theirs) = copies();
+        
+        let newer = ours.merge(&theirs, MergeStrategy::PreferNewer).unwrap();
+        assert_eq!(newer.status, Status::Design);
+        assert_eq!(newer.statement_of_action.as_deref(), Some("Theirs"));
+        assert_eq!(newer.updated_at, theirs.updated_at);
+        
+        let mine = ours.merge(&theirs, MergeStrategy::PreferSelf).unwrap();
+        assert_eq!(mine.status, Status::Discovery);
+        assert_eq!(mine.analysis_of_impact, None);
+        
+        let other = ours.merge(&theirs, MergeStrategy::PreferOther).unwrap();
+        assert!(other.conflicts_with(&theirs).is_empty());
+        
+        let union = ours.merge(&theirs, MergeStrategy::SectionUnion).unwrap();
+        assert_eq!(union.status, Status::Design);
+        assert_eq!(union.notes.as_deref(), Some("Shared notes"));
+        assert_eq!(union.statement_of_action.as_deref(), Some("Ours\n\nTheirs"));
+        assert_eq!(union.analysis_of_impact.as_deref(), Some("Only theirs"));
+    }
+    
+    #[test]
+    fn test_merge_rejects_different_ids() {
+        let ours = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        let theirs = Action::new("web".to_string(), "setup".to_string(), Priority::None);
+        assert!(matches!(ours.merge(&theirs, MergeStrategy::PreferSelf), Err(MergeError::IdMismatch { .. })));
+    }
+}
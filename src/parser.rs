@@ -1,39 +1,235 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use regex::Regex;
 use std::collections::HashMap;
+use thiserror::Error;
+
+/// Malformed markdown detected while parsing, with 1-based line numbers
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("unterminated frontmatter starting at line {line}")]
+    UnterminatedFrontmatter { line: usize },
+    #[error("invalid frontmatter at line {line}: expected 'key: value', found '{content}'")]
+    InvalidFrontmatterLine { line: usize, content: String },
+}
+
+/// Frontmatter key/value pairs
+pub type Metadata = HashMap<String, String>;
+/// Section bodies keyed by their `## ` heading
+pub type Sections = HashMap<String, String>;
+/// Sections keyed by heading, each as the text before its first `### `
+/// sub-heading and its sub-section bodies keyed by sub-heading
+pub type StructuredSections = HashMap<String, (String, HashMap<String, String>)>;
+
+/// Recoverable problems noticed while parsing
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    #[error("unterminated frontmatter starting at line {line}; treating the file as having no frontmatter")]
+    UnterminatedFrontmatter { line: usize },
+    #[error("duplicate frontmatter key '{key}' at line {line} (first set at line {first}); the last value wins")]
+    DuplicateKey { key: String, line: usize, first: usize },
+    #[error("unrecognized frontmatter key '{key}' at line {line}")]
+    UnknownKey { key: String, line: usize },
+}
+
+impl ParseWarning {
+    /// Short machine-readable identifier, used as the validation issue code
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseWarning::UnterminatedFrontmatter { .. } => "parse-warning",
+            ParseWarning::DuplicateKey { .. } => "duplicate-key",
+            ParseWarning::UnknownKey { .. } => "unknown-key",
+        }
+    }
+    
+    /// Worth reporting from `validate` but not on every load: unknown keys
+    /// are kept as custom fields, so nothing is lost
+    pub fn is_informational(&self) -> bool {
+        matches!(self, ParseWarning::UnknownKey { .. })
+    }
+}
+
+/// A section body split on its `### ` sub-headings (or another level, with
+/// `parse_level`). Deeper headings stay part of their sub-section's content.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Subsections {
+    /// Text before the first sub-heading
+    pub intro: String,
+    /// `(heading, content)` pairs in document order
+    pub entries: Vec<(String, String)>,
+}
+
+impl Subsections {
+    /// Split a section body, ignoring headings inside fenced code blocks
+    pub fn parse(body: &str) -> Self {
+        Self::parse_level(body, 3)
+    }
+    
+    /// Like `parse`, splitting on headings with `level` `#`s
+    pub fn parse_level(body: &str, level: usize) -> Self {
+        let marker = format!("{} ", "#".repeat(level));
+        let mut subsections = Self::default();
+        let mut current: Option<String> = None;
+        let mut content = String::new();
+        let mut in_fence = false;
+        
+        for line in body.lines() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            
+            match line.strip_prefix(&marker) {
+                Some(heading) if !in_fence => {
+                    subsections.push(current.take(), &content);
+                    content.clear();
+                    current = Some(heading.trim().to_string());
+                }
+                _ => {
+                    content.push_str(line);
+                    content.push('\n');
+                }
+            }
+        }
+        subsections.push(current, &content);
+        
+        subsections
+    }
+    
+    fn push(&mut self, heading: Option<String>, content: &str) {
+        let content = content.trim().to_string();
+        match heading {
+            Some(heading) => self.entries.push((heading, content)),
+            None => self.intro = content,
+        }
+    }
+    
+    /// Content of the sub-section with this heading, case-insensitively
+    pub fn get(&self, heading: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(heading))
+            .map(|(_, content)| content.as_str())
+    }
+    
+    pub fn headings(&self) -> Vec<&str> {
+        self.entries.iter().map(|(heading, _)| heading.as_str()).collect()
+    }
+    
+    /// Render back to a section body
+    pub fn to_markdown(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if !self.intro.is_empty() {
+            parts.push(self.intro.clone());
+        }
+        for (heading, content) in &self.entries {
+            if content.is_empty() {
+                parts.push(format!("### {}", heading));
+            } else {
+                parts.push(format!("### {}\n\n{}", heading, content));
+            }
+        }
+        parts.join("\n\n")
+    }
+}
 
 pub struct MarkdownParser<'a> {
     content: &'a str,
+    strict: bool,
+    known_keys: Option<&'a [&'a str]>,
 }
 
 impl<'a> MarkdownParser<'a> {
     pub fn new(content: &'a str) -> Self {
-        Self { content }
+        Self { content, strict: true, known_keys: None }
     }
     
-    pub fn parse(&self) -> Result<(HashMap<String, String>, HashMap<String, String>)> {
+    /// In lenient mode recoverable problems become warnings instead of errors
+    pub fn lenient(mut self) -> Self {
+        self.strict = false;
+        self
+    }
+    
+    /// Warn about frontmatter keys not in `keys`
+    pub fn known_keys(mut self, keys: &'a [&'a str]) -> Self {
+        self.known_keys = Some(keys);
+        self
+    }
+    
+    pub fn parse(&self) -> Result<(Metadata, Sections)> {
+        let (metadata, sections, _) = self.parse_with_warnings()?;
+        Ok((metadata, sections))
+    }
+    
+    /// Like `parse`, with each section split on its `### ` sub-headings (see
+    /// `Subsections`). A repeated sub-heading keeps its last body.
+    pub fn parse_structured(&self) -> Result<(Metadata, StructuredSections)> {
+        let (metadata, sections) = self.parse()?;
+        let structured = sections
+            .into_iter()
+            .map(|(heading, body)| {
+                let subsections = Subsections::parse(&body);
+                (heading, (subsections.intro, subsections.entries.into_iter().collect()))
+            })
+            .collect();
+        Ok((metadata, structured))
+    }
+    
+    pub fn parse_with_warnings(&self) -> Result<(Metadata, Sections, Vec<ParseWarning>)> {
+        let mut warnings = Vec::new();
         let mut metadata = HashMap::new();
         let mut sections = HashMap::new();
         
-        let lines: Vec<&str> = self.content.lines().collect();
+        // `lines` drops the `\r` of a CRLF ending; also drop carriage returns
+        // left over from files whose line endings were converted twice
+        let lines: Vec<&str> = self.content.lines().map(|line| line.trim_end_matches('\r')).collect();
         let mut i = 0;
         
         // Parse frontmatter if present
-        if i < lines.len() && lines[i].trim() == "---" {
-            i += 1;
-            while i < lines.len() && lines[i].trim() != "---" {
-                let line = lines[i].trim();
-                if let Some((key, value)) = line.split_once(':') {
-                    metadata.insert(
-                        key.trim().to_string(), 
-                        value.trim().to_string()
-                    );
+        let frontmatter_end = if i < lines.len() && lines[i].trim() == "---" {
+            let end = lines[i + 1..]
+                .iter()
+                .position(|line| line.trim() == "---")
+                .map(|offset| i + 1 + offset);
+            
+            match end {
+                Some(end) => Some(end),
+                None if self.strict => {
+                    return Err(ParseError::UnterminatedFrontmatter { line: i + 1 }.into());
+                }
+                None => {
+                    warnings.push(ParseWarning::UnterminatedFrontmatter { line: i + 1 });
+                    None
                 }
-                i += 1;
             }
-            if i < lines.len() {
-                i += 1; // Skip closing ---
+        } else {
+            None
+        };
+        
+        if let Some(end) = frontmatter_end {
+            let mut first_seen: HashMap<String, usize> = HashMap::new();
+            for (index, line) in lines.iter().enumerate().take(end).skip(i + 1) {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let (key, value) = line.split_once(':').ok_or_else(|| {
+                    ParseError::InvalidFrontmatterLine { line: index + 1, content: line.to_string() }
+                })?;
+                let key = key.trim().to_string();
+                let line = index + 1;
+                
+                if let Some(&first) = first_seen.get(&key) {
+                    warnings.push(ParseWarning::DuplicateKey { key: key.clone(), line, first });
+                } else {
+                    first_seen.insert(key.clone(), line);
+                    if self.known_keys.is_some_and(|known| !known.contains(&key.as_str())) {
+                        warnings.push(ParseWarning::UnknownKey { key: key.clone(), line });
+                    }
+                }
+                
+                metadata.insert(key, value.trim().to_string());
             }
+            i = end + 1; // Skip closing ---
         }
         
         // Parse sections
@@ -44,7 +240,7 @@ impl<'a> MarkdownParser<'a> {
             let line = lines[i];
             
             // Check for section headers (## Section Name)
-            if line.starts_with("## ") {
+            if let Some(heading) = line.strip_prefix("## ") {
                 // Save previous section if exists
                 if let Some(section_name) = current_section.take() {
                     sections.insert(section_name, section_content.trim().to_string());
@@ -52,7 +248,7 @@ impl<'a> MarkdownParser<'a> {
                 }
                 
                 // Start new section
-                current_section = Some(line[3..].trim().to_string());
+                current_section = Some(heading.trim().to_string());
             } else if current_section.is_some() {
                 // Add line to current section
                 section_content.push_str(line);
@@ -67,7 +263,7 @@ impl<'a> MarkdownParser<'a> {
             sections.insert(section_name, section_content.trim().to_string());
         }
         
-        Ok((metadata, sections))
+        Ok((metadata, sections, warnings))
     }
     
     pub fn extract_tags(&self) -> Vec<String> {
@@ -79,10 +275,136 @@ impl<'a> MarkdownParser<'a> {
     }
     
     pub fn extract_links(&self) -> Vec<String> {
+        self.extract_labeled_links()
+            .into_iter()
+            .map(|(_, target)| target)
+            .collect()
+    }
+    
+    /// Markdown links to `.md` files as `(text, target)` pairs
+    pub fn extract_labeled_links(&self) -> Vec<(String, String)> {
         let link_regex = Regex::new(r"\[([^\]]+)\]\(([^)]+\.md)\)").unwrap();
         link_regex
             .captures_iter(self.content)
-            .map(|cap| cap[2].to_string())
+            .map(|cap| (cap[1].to_string(), cap[2].to_string()))
             .collect()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn parse_error(content: &str) -> ParseError {
+        MarkdownParser::new(content)
+            .parse()
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap()
+    }
+    
+    #[test]
+    fn test_parse_frontmatter_and_sections() {
+        let content = "---\nid: 123\n\nstatus: design\n---\n\n# Title\n\n## Notes\n\nSome notes\n";
+        let (metadata, sections) = MarkdownParser::new(content).parse().unwrap();
+        assert_eq!(metadata.get("id").unwrap(), "123");
+        assert_eq!(metadata.get("status").unwrap(), "design");
+        assert_eq!(sections.get("Notes").unwrap(), "Some notes");
+    }
+    
+    #[test]
+    fn test_section_headings() {
+        let content = "# Title\n\n## Notes   \n\nFirst\n##Not a heading\n### Nor this\n\n##  Log\n\n- entry\n";
+        let (_, sections) = MarkdownParser::new(content).parse().unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections["Notes"], "First\n##Not a heading\n### Nor this");
+        assert_eq!(sections["Log"], "- entry");
+    }
+    
+    #[test]
+    fn test_parse_structured_splits_subsections() {
+        let content = "# Title\n\n## Statement of Design\n\nOverview\n\n### Output\n\nA report\n\n### Design\n\n```\n### not a heading\n```\n\n## Notes\n\nPlain\n";
+        let (_, sections) = MarkdownParser::new(content).parse_structured().unwrap();
+        let (intro, subsections) = &sections["Statement of Design"];
+        assert_eq!(intro, "Overview");
+        assert_eq!(subsections["Output"], "A report");
+        assert_eq!(subsections["Design"], "```\n### not a heading\n```");
+        assert_eq!(sections["Notes"], ("Plain".to_string(), HashMap::new()));
+    }
+    
+    #[test]
+    fn test_crlf_line_endings() {
+        let content = "---\r\nid: 1\r\nstatus: design\r\n---\r\n# Title\r\n\r\n## Notes\r\nfirst\r\nsecond\r\r\n\r\n## Log\r\n- entry\r\n";
+        let (metadata, sections) = MarkdownParser::new(content).parse().unwrap();
+        assert_eq!(metadata["status"], "design");
+        assert_eq!(sections["Notes"], "first\nsecond");
+        assert_eq!(sections["Log"], "- entry");
+        assert!(!sections.values().any(|body| body.contains('\r')));
+    }
+    
+    #[test]
+    fn test_unterminated_frontmatter_reports_start_line() {
+        let content = "---\nid: 123\n\n# Title\n\n## Notes\n\nnotes\n";
+        assert_eq!(parse_error(content), ParseError::UnterminatedFrontmatter { line: 1 });
+        assert_eq!(
+            parse_error(content).to_string(),
+            "unterminated frontmatter starting at line 1"
+        );
+    }
+    
+    #[test]
+    fn test_unterminated_frontmatter_is_a_warning_when_lenient() {
+        let content = "---\nid: 123\n\n# Title\n\n## Notes\n\nDeadline: friday\nOwner: bob\n";
+        let (metadata, sections, warnings) = MarkdownParser::new(content)
+            .lenient()
+            .parse_with_warnings()
+            .unwrap();
+        
+        assert!(metadata.is_empty());
+        assert_eq!(sections.get("Notes").unwrap(), "Deadline: friday\nOwner: bob");
+        assert_eq!(warnings, [ParseWarning::UnterminatedFrontmatter { line: 1 }]);
+    }
+    
+    #[test]
+    fn test_frontmatter_line_without_colon_reports_line() {
+        let content = "---\nid: 123\ncreated_at: 2025-01-01T00:00:00Z\nnot a pair\n---\n";
+        assert_eq!(
+            parse_error(content),
+            ParseError::InvalidFrontmatterLine { line: 4, content: "not a pair".to_string() }
+        );
+    }
+    
+    #[test]
+    fn test_subsections_round_trip() {
+        let body = "Overview\n\n### Output\n\nA CLI\n\n#### Flags\n\n--json\n\n### Design\n\n```md\n### not a heading\n```\n\n### Empty";
+        let subsections = Subsections::parse(body);
+        
+        assert_eq!(subsections.intro, "Overview");
+        assert_eq!(subsections.headings(), ["Output", "Design", "Empty"]);
+        assert_eq!(subsections.get("output"), Some("A CLI\n\n#### Flags\n\n--json"));
+        assert_eq!(subsections.get("Design"), Some("```md\n### not a heading\n```"));
+        assert_eq!(subsections.get("Missing"), None);
+        
+        assert_eq!(subsections.to_markdown(), body);
+        assert_eq!(Subsections::parse(&subsections.to_markdown()), subsections);
+    }
+    
+    #[test]
+    fn test_duplicate_and_unknown_keys_are_warnings() {
+        let content = "---\nid: 1\nowner: bob\nid: 2\n---\n";
+        let (metadata, _, warnings) = MarkdownParser::new(content)
+            .known_keys(&["id"])
+            .parse_with_warnings()
+            .unwrap();
+        
+        assert_eq!(metadata.get("id").unwrap(), "2");
+        assert_eq!(warnings, [
+            ParseWarning::UnknownKey { key: "owner".to_string(), line: 3 },
+            ParseWarning::DuplicateKey { key: "id".to_string(), line: 4, first: 2 },
+        ]);
+        assert!(warnings[0].is_informational() && !warnings[1].is_informational());
+        
+        let (_, _, warnings) = MarkdownParser::new(content).parse_with_warnings().unwrap();
+        assert_eq!(warnings.len(), 1, "unknown keys are only flagged when keys are declared");
+    }
+}
@@ -1,15 +1,204 @@
+use crate::config::{IdScheme, MetaGraphLayout};
+use crate::id::ActionId;
+use crate::template::{self, ActionType, DEFAULT_ACTION_TYPE};
+use crate::parser::MarkdownParser;
+use crate::utils::{self, FileChange};
 use crate::Action;
-use crate::Status;
+use crate::{Lifecycle, Priority, Status, Workflow};
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use rayon::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::BTreeMap;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Limits applied while discovering projects and action files, so that
+/// symlinks can't pull in files from outside the workspace or loop back into it
+#[derive(Debug, Clone)]
+pub struct DiscoveryGuard {
+    root: PathBuf,
+    follow_symlinks: bool,
+}
+
+impl DiscoveryGuard {
+    pub fn new<P: AsRef<Path>>(root: P, follow_symlinks: bool) -> Result<Self> {
+        let root = root.as_ref().canonicalize().with_context(|| {
+            format!("Failed to resolve workspace root: {}", root.as_ref().display())
+        })?;
+        Ok(Self { root, follow_symlinks })
+    }
+
+    /// Whether `path` may be loaded: it must not be a symlink we were told to
+    /// skip, and must resolve to somewhere strictly inside the root
+    pub fn allows(&self, path: &Path) -> bool {
+        if !self.follow_symlinks && is_symlink(path) {
+            return false;
+        }
+
+        match path.canonicalize() {
+            Ok(resolved) => resolved != self.root && utils::is_subpath(&resolved, &self.root),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Optional file describing a project, not loaded as an action
+pub const README_FILE: &str = "README.md";
+
+/// Optional per-project action template whose sections replace the action
+/// type's when creating actions; not loaded as an action
+pub const ACTION_TEMPLATE_FILE: &str = ".template.md";
+
+/// A meta-graph directory to be moved, with the link fixes its files need
+#[derive(Debug, Clone)]
+pub struct MetaGraphMove {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// Rewrites of files inside the meta-graph, at their new paths
+    pub changes: Vec<FileChange>,
+}
+
+/// Fix up relative links in a meta-graph that has already been moved on disk
+/// from `old_dir` to `new_dir`, so they keep pointing at the same files.
+///
+/// Links to the action that owns the meta-graph follow it from `old_action`
+/// to `new_action`. Links between files inside the meta-graph moved with it
+/// and are left alone, as are URLs and workspace-rooted `/` links. Returns
+/// how many files were rewritten.
+pub fn relink_meta_graph(old_dir: &Path, new_dir: &Path, old_action: &Path, new_action: &Path) -> Result<usize> {
+    let changes = relinked_files(new_dir, old_dir, new_dir, old_action, new_action)?;
+    write_changes(&changes)?;
+    Ok(changes.len())
+}
+
+/// The link rewrites `relink_meta_graph` would make, reading the meta-graph
+/// from `source` (which is `old_dir` before the move and `new_dir` after it)
+fn relinked_files(
+    source: &Path,
+    old_dir: &Path,
+    new_dir: &Path,
+    old_action: &Path,
+    new_action: &Path,
+) -> Result<Vec<FileChange>> {
+    let old_dir = utils::normalize_path(old_dir);
+    let old_action = utils::normalize_path(old_action);
+    let mut changes = Vec::new();
+
+    for entry in WalkDir::new(source).into_iter().filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("md") {
+            continue;
+        }
+
+        let relative = path.strip_prefix(source).unwrap_or(path);
+        let old_parent = old_dir.join(relative).parent().map(Path::to_path_buf).unwrap_or_default();
+        let new_path = new_dir.join(relative);
+        let new_parent = new_path.parent().unwrap_or(new_dir);
+
+        let original = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut content = original.clone();
+
+        for (_, target) in MarkdownParser::new(&original).extract_labeled_links() {
+            if target.contains("://") || target.starts_with('/') {
+                continue;
+            }
+
+            let resolved = utils::normalize_path(old_parent.join(&target));
+            if resolved.starts_with(&old_dir) {
+                continue;
+            }
+
+            let destination = if resolved == old_action { new_action.to_path_buf() } else { resolved };
+            let updated = utils::relative_path(new_parent, &destination)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if updated != target {
+                content = content.replace(&format!("]({})", target), &format!("]({})", updated));
+            }
+        }
+
+        if content != original {
+            changes.push(FileChange { path: new_path, old: original, new: content });
+        }
+    }
+
+    Ok(changes)
+}
+
+pub(crate) fn write_changes(changes: &[FileChange]) -> Result<()> {
+    for change in changes {
+        utils::write_atomic(&change.path, &change.new)
+            .with_context(|| format!("Failed to write {}", change.path.display()))?;
+    }
+    Ok(())
+}
+
+/// `title` as its file stem: lowercase, with underscores for spaces. Titles
+/// are read back from file stems, so this is how a typed title is matched.
+fn loose_title(title: &str) -> String {
+    title.replace(" ", "_").to_lowercase()
+}
+
+/// File stems that `name` could mean when it names an action by its file,
+/// like a tab-completed `set_up_ci.md`: as given, and slugified
+fn file_stems(name: &str) -> [String; 2] {
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    [name.to_string(), utils::title_to_filename(name)]
+}
+
+/// Print what went wrong parsing `action`, leaving informational warnings
+/// like unknown keys to `validate`
+fn print_parse_warnings(action: &Action) {
+    for warning in action.parse_warnings.iter().filter(|warning| !warning.is_informational()) {
+        eprintln!("Warning: {}: {}", action.file_path.display(), warning);
+    }
+}
+
+/// The single item of `items`, or `None` if there are none or several
+fn only<T>(mut items: impl Iterator<Item = T>) -> Option<T> {
+    match (items.next(), items.next()) {
+        (Some(item), None) => Some(item),
+        _ => None,
+    }
+}
+
+/// `dir/name`, or `dir/stem-N.ext` with the first free `N` if that exists
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|n| dir.join(format!("{}-{}{}", stem, n, extension)))
+        .find(|path| !path.exists())
+        .expect("some suffix is free")
+}
+
+pub(crate) fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(false)
+}
 
 #[derive(Debug, Clone)]
 pub struct Project {
     pub name: String,
     pub path: PathBuf,
-    actions: HashMap<String, Action>,
+    actions: BTreeMap<String, Action>,
+    load_errors: Vec<(PathBuf, String)>,
+    meta_graph_layout: MetaGraphLayout,
+    id_scheme: IdScheme,
+    workflow: Workflow,
+    /// Status for new actions when not the workflow's first
+    initial_status: Option<Status>,
 }
 
 impl Project {
@@ -26,11 +215,21 @@ impl Project {
         Ok(Self {
             name,
             path,
-            actions: HashMap::new(),
+            actions: BTreeMap::new(),
+            load_errors: Vec::new(),
+            meta_graph_layout: MetaGraphLayout::default(),
+            id_scheme: IdScheme::default(),
+            workflow: Workflow::default(),
+            initial_status: None,
         })
     }
 
     pub fn load<P: AsRef<Path>>(project_path: P) -> Result<Self> {
+        Self::load_with(project_path, None)
+    }
+
+    /// Load a project, skipping action files rejected by `guard`
+    pub fn load_with<P: AsRef<Path>>(project_path: P, guard: Option<&DiscoveryGuard>) -> Result<Self> {
         let path = project_path.as_ref().to_path_buf();
         let name = path
             .file_name()
@@ -41,100 +240,486 @@ impl Project {
         let mut project = Self {
             name,
             path: path.clone(),
-            actions: HashMap::new(),
+            actions: BTreeMap::new(),
+            load_errors: Vec::new(),
+            meta_graph_layout: MetaGraphLayout::default(),
+            id_scheme: IdScheme::default(),
+            workflow: Workflow::default(),
+            initial_status: None,
         };
 
-        project.load_actions()?;
+        project.load_actions(guard)?;
         Ok(project)
     }
 
-    fn load_actions(&mut self) -> Result<()> {
+    /// Use `layout` to locate meta-graphs for this project's actions
+    pub fn with_meta_graph_layout(mut self, layout: MetaGraphLayout) -> Self {
+        self.meta_graph_layout = layout;
+        for action in self.actions.values_mut() {
+            action.meta_graph_layout = layout;
+        }
+        self
+    }
+
+    /// Start new actions at `status` rather than the workflow's first
+    pub fn with_initial_status(mut self, status: Option<Status>) -> Self {
+        self.initial_status = status;
+        self
+    }
+
+    /// Generate ids for new actions with `scheme`
+    pub fn with_id_scheme(mut self, scheme: IdScheme) -> Self {
+        self.id_scheme = scheme;
+        self
+    }
+
+    /// Start new actions at the first status of `workflow` and treat its
+    /// last status as done
+    pub fn with_workflow(mut self, workflow: &Workflow) -> Self {
+        for action in self.actions.values_mut() {
+            action.final_status = workflow.last().clone();
+        }
+        self.workflow = workflow.clone();
+        self
+    }
+
+    /// A fresh id for a new action in this project, unused by its actions
+    pub fn next_id(&self) -> ActionId {
+        ActionId::generate(self.id_scheme, &self.name, self.actions.values().map(|action| &action.id))
+    }
+
+    fn load_actions(&mut self, guard: Option<&DiscoveryGuard>) -> Result<()> {
         if !self.path.exists() {
             return Ok(());
         }
 
+        let mut paths = Vec::new();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let path = entry.path();
 
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
-                match Action::from_file(&path) {
-                    Ok(action) => {
-                        self.actions.insert(action.title().to_string(), action);
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "Warning: Failed to load action from {}: {}",
-                            path.display(),
-                            e
-                        );
-                    }
+            if guard.is_some_and(|guard| !guard.allows(&path)) {
+                continue;
+            }
+
+            // Only top-level markdown files are actions. Sub-directories are never
+            // scanned: they hold meta-graphs (`foo/` beside `foo.md`, or `.meta/`),
+            // and the markdown inside belongs to the meta-graph, not the project.
+            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("md") {
+                continue;
+            }
+
+            // The project README and action template aren't actions
+            let file_name = path.file_name().and_then(|s| s.to_str());
+            if file_name == Some(README_FILE) || file_name == Some(ACTION_TEMPLATE_FILE) {
+                continue;
+            }
+
+            paths.push(path);
+        }
+
+        // Parse in parallel; sorting first keeps `load_errors` in a stable order
+        paths.sort();
+        let loaded: Vec<(PathBuf, Result<Action>)> = paths
+            .into_par_iter()
+            .map(|path| {
+                let action = Action::from_file(&path);
+                (path, action)
+            })
+            .collect();
+        for (path, action) in loaded {
+            match action {
+                Ok(action) => {
+                    self.actions.insert(action.title().to_string(), action);
                 }
+                Err(e) => self.load_errors.push((path, e.to_string())),
             }
         }
 
         Ok(())
     }
 
-    pub fn create_action(&mut self, title: &str, priority: bool) -> Result<Action> {
-        let filename = format!("{}.md", title.replace(" ", "_").to_lowercase());
-        let file_path = self.path.join(&filename);
+    /// Print the parse warnings of loaded actions and the files that failed
+    /// to load. Loading doesn't print, so that projects loaded in parallel
+    /// don't interleave their output.
+    pub fn print_load_warnings(&self) {
+        let mut actions: Vec<&Action> = self.actions.values().collect();
+        actions.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        for action in actions {
+            print_parse_warnings(action);
+        }
+        for (path, error) in &self.load_errors {
+            eprintln!("Warning: Failed to load action from {}: {}", path.display(), error);
+        }
+    }
+
+    pub fn create_action(&mut self, title: &str, priority: Priority) -> Result<Action> {
+        let action_type = ActionType::builtin(DEFAULT_ACTION_TYPE)
+            .expect("default action type is built in");
+        self.create_typed_action(title, priority, DEFAULT_ACTION_TYPE, &action_type)
+    }
+
+    /// Create an action seeded with the sections of `action_type`
+    pub fn create_typed_action(
+        &mut self,
+        title: &str,
+        priority: Priority,
+        type_name: &str,
+        action_type: &ActionType,
+    ) -> Result<Action> {
+        utils::validate_action_title(title)?;
+        let file_path = self.path.join(format!("{}.md", utils::title_to_filename(title)));
+
+        let mut action = Action::new(self.name.clone(), title.to_string(), priority);
+        action.id = self.next_id();
+        action.file_path = file_path;
+        action.meta_graph_layout = self.meta_graph_layout;
+        action.action_type = type_name.to_string();
+        action.status = self.initial_status.clone().unwrap_or_else(|| self.workflow.first().clone());
+        action.final_status = self.workflow.last().clone();
+
+        // Create initial content from the type's template
+        for section in &action_type.sections {
+            let content = template::expand_placeholders(&action_type.placeholder(section), &action, &self.path);
+            action.update_section(section, Some(content));
+        }
+
+        match action.save_new() {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                anyhow::bail!(
+                    "Action '{}' already exists in project '{}'",
+                    title,
+                    self.name
+                );
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to write action file: {}", action.file_path.display())
+                });
+            }
+        }
+        self.actions.insert(title.to_string(), action.clone());
+
+        Ok(action)
+    }
 
-        if file_path.exists() {
+    /// Write an action from an export into this project, keeping its id,
+    /// timestamps and content. An action with the same title is replaced when
+    /// `overwrite` is set and is an error otherwise. Returns whether one was
+    /// replaced. The id is regenerated if another action here already uses it.
+    pub fn import_action(&mut self, mut action: Action, overwrite: bool) -> Result<bool> {
+        let existing = self.actions.get(&action.title).map(|existing| existing.file_path.clone());
+        if existing.is_some() && !overwrite {
             anyhow::bail!(
                 "Action '{}' already exists in project '{}'",
-                title,
+                action.title,
                 self.name
             );
         }
 
-        let mut action = Action::new(self.name.clone(), title.to_string(), priority);
-        action.file_path = file_path;
+        if self.actions.values().any(|other| other.id == action.id && other.title != action.title) {
+            action.id = self.next_id();
+        }
+        action.project = self.name.clone();
+        action.file_path = existing
+            .clone()
+            .unwrap_or_else(|| self.path.join(format!("{}.md", utils::title_to_filename(&action.title))));
+        action.meta_graph_layout = self.meta_graph_layout;
+        action.final_status = self.workflow.last().clone();
+
+        if existing.is_none() && action.file_path.exists() {
+            anyhow::bail!("File already exists: {}", action.file_path.display());
+        }
+        // Written directly rather than through `save` so `updated_at` survives
+        utils::write_atomic(&action.file_path, action.to_markdown())
+            .with_context(|| format!("Failed to write action file: {}", action.file_path.display()))?;
+        self.actions.insert(action.title.clone(), action);
 
-        // Create initial content from template
-        action.notes = Some("General notes on the task".to_string());
-        action.statement_of_action = Some("The task to be performed, more in depth than title, may include why the action is needed".to_string());
-        action.statement_of_inputs =
-            Some("A list of .md links to other markdown files".to_string());
+        Ok(existing.is_some())
+    }
 
-        action.save()?;
-        self.actions.insert(title.to_string(), action.clone());
+    /// Remove an action's file, its meta-graph directory and its entry
+    pub fn delete_action(&mut self, title: &str) -> Result<Action> {
+        let key = self.stored_title(title)?;
+        let action = self.actions.remove(&key).expect("resolved title is a key");
 
+        fs::remove_file(&action.file_path).with_context(|| {
+            format!("Failed to remove action file: {}", action.file_path.display())
+        })?;
+        if action.has_meta_graph() {
+            let meta_graph = action.meta_graph_path();
+            fs::remove_dir_all(&meta_graph).with_context(|| {
+                format!("Failed to remove meta-graph: {}", meta_graph.display())
+            })?;
+        }
         Ok(action)
     }
 
+    /// Give an action a new title, renaming its file and meta-graph to match.
+    /// Links inside the meta-graph are fixed up; links from other actions are not.
+    pub fn rename_action(&mut self, title: &str, new_title: &str) -> Result<()> {
+        utils::validate_action_title(new_title)?;
+        let title = self.stored_title(title)?;
+        let action = &self.actions[&title];
+        if title != new_title && self.actions.contains_key(new_title) {
+            anyhow::bail!("Action '{}' already exists in project '{}'", new_title, self.name);
+        }
+
+        let old_path = action.file_path.clone();
+        let new_path = self
+            .path
+            .join(format!("{}.md", utils::title_to_filename(new_title)));
+        let old_meta_graph = action.meta_graph_path();
+        let new_meta_graph = self.meta_graph_layout.path_for(&new_path);
+        let moves_meta_graph = action.has_meta_graph() && old_meta_graph != new_meta_graph;
+
+        // Check every destination before touching anything
+        if new_path != old_path && new_path.exists() {
+            anyhow::bail!("Cannot rename to '{}': {} already exists", new_title, new_path.display());
+        }
+        if moves_meta_graph && new_meta_graph.exists() {
+            anyhow::bail!(
+                "Cannot rename to '{}': {} already exists",
+                new_title,
+                new_meta_graph.display()
+            );
+        }
+
+        fs::rename(&old_path, &new_path).with_context(|| {
+            format!("Failed to rename {} to {}", old_path.display(), new_path.display())
+        })?;
+        if moves_meta_graph {
+            fs::rename(&old_meta_graph, &new_meta_graph).with_context(|| {
+                format!(
+                    "Failed to rename meta-graph {} to {}",
+                    old_meta_graph.display(),
+                    new_meta_graph.display()
+                )
+            })?;
+            relink_meta_graph(&old_meta_graph, &new_meta_graph, &old_path, &new_path)?;
+        }
+
+        let mut action = self.actions.remove(&title).expect("action was found above");
+        action.title = new_title.to_string();
+        action.file_path = new_path;
+        action.save()?;
+        self.actions.insert(new_title.to_string(), action);
+        Ok(())
+    }
+
+    /// Remove an action from the project without touching its file
+    pub(crate) fn take_action(&mut self, title: &str) -> Option<Action> {
+        let key = self.resolve_title(title)?.to_string();
+        self.actions.remove(&key)
+    }
+
+    /// Add an action whose file is already in the project directory
+    pub(crate) fn insert_action(&mut self, mut action: Action) {
+        action.meta_graph_layout = self.meta_graph_layout;
+        action.final_status = self.workflow.last().clone();
+        self.actions.insert(action.title().to_string(), action);
+    }
+
+    /// The action titled `title`, or failing that the only one whose title
+    /// matches ignoring case and spaces versus underscores, or whose file
+    /// `title` names
     pub fn get_action(&self, title: &str) -> Option<&Action> {
-        self.actions.get(title)
+        self.resolve_title(title).and_then(|key| self.actions.get(key))
+    }
+
+    /// The stored title `title` refers to, as `get_action` matches it
+    fn resolve_title(&self, title: &str) -> Option<&str> {
+        if let Some((key, _)) = self.actions.get_key_value(title) {
+            return Some(key);
+        }
+        let loose = loose_title(title);
+        if let Some(key) = only(self.actions.keys().filter(|key| loose_title(key) == loose)) {
+            return Some(key);
+        }
+
+        let stems = file_stems(title);
+        only(
+            self.actions
+                .iter()
+                .filter(|(_, action)| {
+                    let stem = action.file_path.file_stem().and_then(|stem| stem.to_str());
+                    stem.is_some_and(|stem| stems.iter().any(|candidate| candidate == stem))
+                })
+                .map(|(key, _)| key),
+        )
+        .map(String::as_str)
+    }
+
+    /// The file of an action that isn't loaded yet, named by title or file
+    fn find_action_file(&self, title: &str, guard: Option<&DiscoveryGuard>) -> Option<PathBuf> {
+        file_stems(title)
+            .into_iter()
+            .map(|stem| format!("{}.md", stem))
+            .filter(|name| !name.contains(['/', '\\']))
+            .filter(|name| *name != README_FILE.to_lowercase() && name != ACTION_TEMPLATE_FILE)
+            .map(|name| self.path.join(name))
+            .find(|path| path.is_file() && guard.is_none_or(|guard| guard.allows(path)))
+    }
+
+    /// Up to three titles close to `title`, closest first, for "did you mean"
+    pub fn similar_titles(&self, title: &str) -> Vec<&str> {
+        let title = loose_title(title);
+        let limit = (title.chars().count() / 3).max(2);
+        let mut candidates: Vec<(usize, &str)> = self
+            .actions
+            .keys()
+            .map(|key| (strsim::levenshtein(&title, &loose_title(key)), key.as_str()))
+            .filter(|(distance, _)| *distance <= limit)
+            .collect();
+        candidates.sort();
+        candidates.into_iter().take(3).map(|(_, key)| key).collect()
+    }
+
+    /// The error for a missing action, suggesting similar titles
+    pub fn action_not_found(&self, title: &str) -> anyhow::Error {
+        let similar = self.similar_titles(title);
+        if similar.is_empty() {
+            anyhow::anyhow!("Action '{}' not found in project '{}'", title, self.name)
+        } else {
+            let similar: Vec<String> = similar.iter().map(|title| format!("'{}'", title)).collect();
+            anyhow::anyhow!(
+                "Action '{}' not found in project '{}'; did you mean: {}?",
+                title,
+                self.name,
+                similar.join(", ")
+            )
+        }
+    }
+
+    fn action_mut(&mut self, title: &str) -> Result<&mut Action> {
+        let key = self.stored_title(title)?;
+        Ok(self.actions.get_mut(&key).expect("resolved title is a key"))
+    }
+
+    /// The key `title` resolves to, as `resolve_title` finds it
+    fn stored_title(&self, title: &str) -> Result<String> {
+        self.resolve_title(title)
+            .map(str::to_string)
+            .ok_or_else(|| self.action_not_found(title))
+    }
+
+    /// The action titled `title`, reading its file first if it isn't loaded
+    /// yet, as in a lazily loaded workspace. `None` if there's no such file
+    /// or `guard` rejects it.
+    pub fn load_action(&mut self, title: &str, guard: Option<&DiscoveryGuard>) -> Result<Option<&Action>> {
+        if self.resolve_title(title).is_none() {
+            let Some(path) = self.find_action_file(title, guard) else {
+                return Ok(None);
+            };
+
+            let mut action = Action::from_file(&path)?;
+            action.meta_graph_layout = self.meta_graph_layout;
+            action.final_status = self.workflow.last().clone();
+            print_parse_warnings(&action);
+            self.actions.insert(action.title().to_string(), action);
+        }
+        Ok(self.get_action(title))
     }
 
     pub fn get_action_mut(&mut self, title: &str) -> Option<&mut Action> {
-        self.actions.get_mut(title)
+        self.action_mut(title).ok()
     }
 
     pub fn update_action_status(&mut self, title: &str, status: Status) -> Result<()> {
-        let action = self
-            .actions
-            .get_mut(title)
-            .with_context(|| format!("Action '{}' not found in project '{}'", title, self.name))?;
+        self.update_action_status_with(title, status, &[])
+    }
+
+    /// Change an action's status and add `scaffolds` as `(section, content)`
+    /// pairs, saving once
+    pub fn update_action_status_with(
+        &mut self,
+        title: &str,
+        status: Status,
+        scaffolds: &[(String, String)],
+    ) -> Result<()> {
+        let action = self.action_mut(title)?;
+
+        if !action.is_active() {
+            anyhow::bail!(
+                "Action '{}' in project '{}' is {}; reactivate it before changing its status",
+                title,
+                action.project(),
+                action.lifecycle()
+            );
+        }
 
         action.set_status(status);
+        for (section, content) in scaffolds {
+            action.update_section(section, Some(content.clone()));
+        }
         action.save()?;
         Ok(())
     }
 
-    pub fn set_action_priority(&mut self, title: &str, priority: bool) -> Result<()> {
-        let action = self
-            .actions
-            .get_mut(title)
-            .with_context(|| format!("Action '{}' not found in project '{}'", title, self.name))?;
+    pub fn set_action_lifecycle(&mut self, title: &str, lifecycle: Lifecycle) -> Result<()> {
+        let action = self.action_mut(title)?;
+
+        action.set_lifecycle(lifecycle);
+        action.save()?;
+        Ok(())
+    }
+
+    pub fn set_action_priority(&mut self, title: &str, priority: Priority) -> Result<()> {
+        let action = self.action_mut(title)?;
 
         action.set_priority(priority);
         action.save()?;
         Ok(())
     }
 
+    /// Set or clear an action's due date; returns `Action::set_due`'s warning
+    pub fn set_action_due(&mut self, title: &str, due: Option<DateTime<Utc>>) -> Result<Option<String>> {
+        let action = self.action_mut(title)?;
+
+        let warning = action.set_due(due, Utc::now());
+        action.save()?;
+        Ok(warning)
+    }
+
+    pub fn set_action_assignee(&mut self, title: &str, assignee: Option<&str>) -> Result<()> {
+        let action = self.action_mut(title)?;
+
+        action.assignee = assignee.map(str::to_string);
+        action.save()?;
+        Ok(())
+    }
+
+    pub fn set_action_estimate(&mut self, title: &str, estimate: Option<Duration>) -> Result<()> {
+        let action = self.action_mut(title)?;
+
+        action.estimate = estimate;
+        action.save()?;
+        Ok(())
+    }
+
+    /// Add to an action's time spent, returning the new total
+    pub fn log_action_time(&mut self, title: &str, duration: Duration) -> Result<Duration> {
+        let action = self.action_mut(title)?;
+
+        action.log_time(duration)?;
+        action.save()?;
+        Ok(action.time_spent.unwrap_or_default())
+    }
+
+    pub fn comment_on_action(&mut self, title: &str, author: &str, text: &str) -> Result<()> {
+        let action = self.action_mut(title)?;
+
+        action.append_log(author, text, Utc::now());
+        action.save()?;
+        Ok(())
+    }
+
+    /// Actions by priority, most urgent first, then by title
     pub fn list_actions(&self) -> Vec<&Action> {
-        self.actions.values().collect()
+        let mut actions: Vec<&Action> = self.actions.values().collect();
+        actions.sort_by_key(|action| std::cmp::Reverse(action.priority()));
+        actions
     }
 
     pub fn actions_by_status(&self, status: &Status) -> Vec<&Action> {
@@ -152,12 +737,9 @@ impl Project {
     }
 
     pub fn create_meta_graph(&self, action_title: &str) -> Result<PathBuf> {
-        let action = self.actions.get(action_title).with_context(|| {
-            format!(
-                "Action '{}' not found in project '{}'",
-                action_title, self.name
-            )
-        })?;
+        let action = self
+            .get_action(action_title)
+            .ok_or_else(|| self.action_not_found(action_title))?;
 
         let meta_graph_path = action.meta_graph_path();
 
@@ -173,6 +755,162 @@ impl Project {
         Ok(meta_graph_path)
     }
 
+    /// Copy `source` into the action's meta-graph, creating it if needed.
+    /// A name already taken gets a numeric suffix (`shot-1.png`).
+    pub fn attach(&self, action_title: &str, source: &Path) -> Result<PathBuf> {
+        if !source.is_file() {
+            anyhow::bail!("Attachment not found: {}", source.display());
+        }
+        let name = source
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("Invalid attachment name: {}", source.display()))?;
+        if name.ends_with(".md") {
+            anyhow::bail!(
+                "Markdown files in a meta-graph are sub-actions, not attachments: {}",
+                source.display()
+            );
+        }
+
+        let meta_graph_path = self.create_meta_graph(action_title)?;
+        let target = unique_path(&meta_graph_path, name);
+        fs::copy(source, &target).with_context(|| {
+            format!("Failed to copy {} to {}", source.display(), target.display())
+        })?;
+
+        Ok(target)
+    }
+
+    /// Delete an attachment by file name, returning its path
+    pub fn detach(&self, action_title: &str, name: &str) -> Result<PathBuf> {
+        let action = self
+            .get_action(action_title)
+            .ok_or_else(|| self.action_not_found(action_title))?;
+
+        let path = action
+            .attachments()
+            .into_iter()
+            .find(|path| path.file_name().and_then(|n| n.to_str()) == Some(name))
+            .with_context(|| {
+                format!("'{}' is not attached to {}/{}", name, self.name, action_title)
+            })?;
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove attachment: {}", path.display()))?;
+
+        Ok(path)
+    }
+
+    /// Carry out `moves`, as planned by `meta_graph_moves`, and switch the
+    /// project to `layout`
+    pub fn apply_meta_graph_moves(&mut self, moves: &[MetaGraphMove], layout: MetaGraphLayout) -> Result<()> {
+        for planned in moves {
+            if let Some(parent) = planned.to.parent() {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!("Failed to create directory: {}", parent.display())
+                })?;
+            }
+            fs::rename(&planned.from, &planned.to).with_context(|| {
+                format!(
+                    "Failed to move meta-graph {} to {}",
+                    planned.from.display(),
+                    planned.to.display()
+                )
+            })?;
+            write_changes(&planned.changes)?;
+        }
+        for action in self.actions.values_mut() {
+            action.meta_graph_layout = layout;
+        }
+
+        // Drop the hidden container once it no longer holds anything
+        let hidden_root = self.path.join(".meta");
+        if layout == MetaGraphLayout::Sibling && hidden_root.is_dir() {
+            let _ = fs::remove_dir(&hidden_root);
+        }
+
+        self.meta_graph_layout = layout;
+        Ok(())
+    }
+
+    /// What `apply_meta_graph_moves` should move and rewrite, without touching anything
+    pub fn meta_graph_moves(&self, layout: MetaGraphLayout) -> Result<Vec<MetaGraphMove>> {
+        let mut moves = Vec::new();
+
+        for action in self.actions.values() {
+            let from = action.meta_graph_path();
+            let to = layout.path_for(&action.file_path);
+
+            if from == to || !from.is_dir() {
+                continue;
+            }
+            if to.exists() {
+                anyhow::bail!("Cannot move meta-graph to {}: path already exists", to.display());
+            }
+
+            let changes = relinked_files(&from, &from, &to, &action.file_path, &action.file_path)?;
+            moves.push(MetaGraphMove { from, to, changes });
+        }
+
+        Ok(moves)
+    }
+
+    /// Meta-graph directories that don't belong to any action in this project
+    pub fn orphan_meta_graphs(&self) -> Vec<PathBuf> {
+        let container = match self.meta_graph_layout {
+            MetaGraphLayout::Sibling => self.path.clone(),
+            MetaGraphLayout::Hidden => self.path.join(".meta"),
+        };
+
+        let Ok(entries) = fs::read_dir(&container) else {
+            return Vec::new();
+        };
+
+        let mut orphans: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .filter(|path| {
+                let hidden = path.file_name()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|name| name.starts_with('.'));
+                !hidden && !self.actions.values().any(|action| &action.meta_graph_path() == path)
+            })
+            .collect();
+        orphans.sort();
+        orphans
+    }
+
+    pub fn readme_path(&self) -> PathBuf {
+        self.path.join(README_FILE)
+    }
+
+    /// The first paragraph of the project's README that isn't a heading,
+    /// joined onto one line. `None` without a README or such a paragraph.
+    pub fn description(&self) -> Option<String> {
+        let content = fs::read_to_string(self.readme_path()).ok()?;
+        let paragraph = content
+            .split("\n\n")
+            .map(|block| block.trim())
+            .find(|block| !block.is_empty() && !block.starts_with('#'))?;
+
+        Some(paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
+    /// Contents of the project's `.template.md`, if it has one
+    pub fn action_template(&self) -> Result<Option<String>> {
+        let path = self.path.join(ACTION_TEMPLATE_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        fs::read_to_string(&path)
+            .map(Some)
+            .with_context(|| format!("Failed to read action template: {}", path.display()))
+    }
+
+    /// Action files that could not be parsed, with the reason
+    pub fn load_errors(&self) -> &[(PathBuf, String)] {
+        &self.load_errors
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
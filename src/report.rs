@@ -0,0 +1,126 @@
+use crate::{Action, Lifecycle, Workspace};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+/// A human-readable markdown summary of the workspace for sharing in a wiki
+/// or PR description: every action in a table per project, then the
+/// priority actions, then the ones that are blocked or overdue.
+pub fn render(workspace: &Workspace, now: DateTime<Utc>) -> Result<String> {
+    let actions = workspace.list_actions(None, None, false)?;
+    let mut report = String::new();
+    
+    writeln!(report, "# Action Report")?;
+    writeln!(report)?;
+    writeln!(report, "Generated {}", now.format("%Y-%m-%d %H:%M UTC"))?;
+    
+    writeln!(report)?;
+    writeln!(report, "## Actions")?;
+    if actions.is_empty() {
+        writeln!(report)?;
+        writeln!(report, "_None_")?;
+    }
+    let mut current_project = None;
+    for action in &actions {
+        if current_project != Some(action.project()) {
+            current_project = Some(action.project());
+            writeln!(report)?;
+            writeln!(report, "### {}", action.project())?;
+            writeln!(report)?;
+            writeln!(report, "| Action | Status | Priority |")?;
+            writeln!(report, "|--------|--------|----------|")?;
+        }
+        writeln!(
+            report,
+            "| {} | {} | {} |",
+            table_cell(action.title()),
+            table_cell(&status_label(action)),
+            action.priority()
+        )?;
+    }
+    
+    writeln!(report)?;
+    writeln!(report, "## Priority")?;
+    writeln!(report)?;
+    let priority = workspace.list_actions(None, None, true)?;
+    if priority.is_empty() {
+        writeln!(report, "_None_")?;
+    }
+    for action in priority {
+        writeln!(report, "- {}/{} ({}, {})", action.project(), action.title(), action.priority(), status_label(action))?;
+    }
+    
+    writeln!(report)?;
+    writeln!(report, "## Blocked and Overdue")?;
+    writeln!(report)?;
+    let waiting = workspace.blocked_actions();
+    let mut listed = false;
+    for action in &actions {
+        let mut reasons = Vec::new();
+        if action.lifecycle() == Lifecycle::Blocked {
+            reasons.push("blocked".to_string());
+        }
+        if let Some((_, dependencies)) = waiting.iter().find(|(blocked, _)| blocked.file_path == action.file_path) {
+            let names: Vec<String> = dependencies
+                .iter()
+                .map(|dependency| format!("{}/{}", dependency.project(), dependency.title()))
+                .collect();
+            reasons.push(format!("waiting on {}", names.join(", ")));
+        }
+        if action.is_overdue(now) {
+            if let Some(due) = action.due {
+                reasons.push(format!("overdue since {}", due.format("%Y-%m-%d")));
+            }
+        }
+        
+        if !reasons.is_empty() {
+            writeln!(report, "- {}/{}: {}", action.project(), action.title(), reasons.join("; "))?;
+            listed = true;
+        }
+    }
+    if !listed {
+        writeln!(report, "_None_")?;
+    }
+    
+    Ok(report)
+}
+
+/// Status plus the lifecycle when the action is out of the pipeline
+fn status_label(action: &Action) -> String {
+    match action.lifecycle() {
+        Lifecycle::Active => action.status().to_string(),
+        lifecycle => format!("{} ({})", action.status(), lifecycle),
+    }
+}
+
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_report_sections() {
+        let dir = TempDir::new().unwrap();
+        let mut workspace = Workspace::init(dir.path()).unwrap();
+        workspace.create_action("web", "setup", Priority::High).unwrap();
+        workspace.create_action("web", "deploy", Priority::None).unwrap();
+        workspace.create_action("api", "a|b", Priority::None).unwrap();
+        workspace.add_dependency("web", "deploy", "setup").unwrap();
+        workspace.set_action_lifecycle("api", "a|b", Lifecycle::Blocked).unwrap();
+        let now = Utc::now();
+        workspace.set_action_due("web", "setup", Some(now - chrono::Duration::days(1))).unwrap();
+        
+        let report = render(&workspace, now).unwrap();
+        assert!(report.starts_with(&format!("# Action Report\n\nGenerated {}", now.format("%Y-%m-%d %H:%M UTC"))));
+        assert!(report.contains("### api\n\n| Action | Status | Priority |\n|--------|--------|----------|\n| a\\|b | discovery (blocked) | none |\n"), "{}", report);
+        assert!(report.contains("## Priority\n\n- web/setup (high, discovery)\n"), "{}", report);
+        assert!(report.contains("- web/deploy: waiting on web/setup\n"), "{}", report);
+        assert!(report.contains("- web/setup: overdue since "), "{}", report);
+        assert!(report.contains("- api/a|b: blocked\n"), "{}", report);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::config::{self, STATE_DIR};
+use crate::{Status, Workspace};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const METRICS_FILE: &str = "metrics.jsonl";
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Days without an update after which an action counts as stale
+pub const DEFAULT_STALE_DAYS: i64 = 30;
+
+/// Aggregate counts over every action in a workspace.
+///
+/// Fields after `completion_ratio` were added later and default to zero
+/// when reading older snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceStats {
+    pub total: usize,
+    /// Action count per status name
+    pub by_status: BTreeMap<String, usize>,
+    /// Share of actions at the workflow's final status, between 0 and 1
+    pub completion_ratio: f64,
+    /// Action count per project name
+    #[serde(default)]
+    pub by_project: BTreeMap<String, usize>,
+    #[serde(default)]
+    pub priority: usize,
+    #[serde(default)]
+    pub with_meta_graph: usize,
+    /// Actions not updated within `stale_days`
+    #[serde(default)]
+    pub stale: usize,
+    #[serde(default)]
+    pub stale_days: i64,
+    /// Estimated and logged time per project, for projects that have either
+    #[serde(default)]
+    pub time_by_project: BTreeMap<String, TimeTotals>,
+}
+
+/// Summed estimates and logged time, in minutes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeTotals {
+    pub estimate_minutes: i64,
+    pub spent_minutes: i64,
+}
+
+/// Stats recorded at a point in time, one per line in `.action-lite/metrics.jsonl`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub stats: WorkspaceStats,
+}
+
+impl WorkspaceStats {
+    /// Stats with the default staleness threshold, as of now
+    pub fn collect(workspace: &Workspace) -> Self {
+        Self::collect_with(workspace, DEFAULT_STALE_DAYS, Utc::now())
+    }
+    
+    /// Count the actions already loaded in `workspace`; those last updated
+    /// more than `stale_days` before `now` are stale
+    pub fn collect_with(workspace: &Workspace, stale_days: i64, now: DateTime<Utc>) -> Self {
+        let workflow = workspace.workflow();
+        let mut by_status: BTreeMap<String, usize> = workflow.statuses()
+            .iter()
+            .map(|status| (status.to_string(), 0))
+            .collect();
+        let mut stats = Self { stale_days, ..Default::default() };
+        
+        for project in workspace.projects().values() {
+            stats.by_project.insert(project.name.clone(), project.list_actions().len());
+            for action in project.list_actions() {
+                stats.total += 1;
+                *by_status.entry(action.status().to_string()).or_default() += 1;
+                stats.priority += action.is_priority() as usize;
+                stats.with_meta_graph += action.has_meta_graph() as usize;
+                stats.stale += ((now - action.updated_at).num_days() > stale_days) as usize;
+                if action.estimate.is_some() || action.time_spent.is_some() {
+                    let totals = stats.time_by_project.entry(project.name.clone()).or_default();
+                    totals.estimate_minutes = totals.estimate_minutes.saturating_add(action.estimate.unwrap_or_default().num_minutes());
+                    totals.spent_minutes = totals.spent_minutes.saturating_add(action.time_spent.unwrap_or_default().num_minutes());
+                }
+            }
+        }
+        
+        let published = by_status.get(&workflow.last().to_string()).copied().unwrap_or(0);
+        stats.completion_ratio = if stats.total == 0 { 0.0 } else { published as f64 / stats.total as f64 };
+        stats.by_status = by_status;
+        stats
+    }
+    
+    pub fn count(&self, status: &Status) -> usize {
+        self.by_status.get(&status.to_string()).copied().unwrap_or(0)
+    }
+}
+
+/// Append a snapshot to the metrics history, replacing any taken the same (UTC) day
+pub fn record_snapshot<P: AsRef<Path>>(root: P, snapshot: Snapshot) -> Result<()> {
+    let mut history = load_history(&root)?;
+    let day = snapshot.timestamp.date_naive();
+    history.retain(|existing| existing.timestamp.date_naive() != day);
+    history.push(snapshot);
+    history.sort_by_key(|existing| existing.timestamp);
+    
+    let mut content = String::new();
+    for entry in &history {
+        content.push_str(&serde_json::to_string(entry)?);
+        content.push('\n');
+    }
+    
+    let path = config::ensure_state_dir(&root)?.join(METRICS_FILE);
+    fs::write(&path, content)
+        .with_context(|| format!("Failed to write metrics file: {}", path.display()))
+}
+
+/// All recorded snapshots, oldest first
+pub fn load_history<P: AsRef<Path>>(root: P) -> Result<Vec<Snapshot>> {
+    let path = root.as_ref().join(STATE_DIR).join(METRICS_FILE);
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read metrics file: {}", path.display()))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid snapshot at {}:{}", path.display(), index + 1))
+        })
+        .collect()
+}
+
+/// Render values as a unicode sparkline scaled between their min and max
+pub fn sparkline(values: &[usize]) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    
+    values
+        .iter()
+        .map(|&value| {
+            if max == min {
+                return SPARK_CHARS[0];
+            }
+            let scaled = (value - min) * (SPARK_CHARS.len() - 1) / (max - min);
+            SPARK_CHARS[scaled]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+    
+    fn snapshot(day: u32, hour: u32, total: usize) -> Snapshot {
+        Snapshot {
+            timestamp: Utc.with_ymd_and_hms(2025, 3, day, hour, 0, 0).unwrap(),
+            stats: WorkspaceStats {
+                total,
+                by_status: BTreeMap::from([("discovery".to_string(), total)]),
+                ..Default::default()
+            },
+        }
+    }
+    
+    #[test]
+    fn test_snapshots_are_deduplicated_per_day() {
+        let dir = TempDir::new().unwrap();
+        record_snapshot(dir.path(), snapshot(2, 9, 3)).unwrap();
+        record_snapshot(dir.path(), snapshot(1, 9, 1)).unwrap();
+        record_snapshot(dir.path(), snapshot(2, 17, 5)).unwrap();
+        
+        let history = load_history(dir.path()).unwrap();
+        let totals: Vec<usize> = history.iter().map(|s| s.stats.total).collect();
+        assert_eq!(totals, [1, 5]);
+    }
+    
+    #[test]
+    fn test_collect_counts_loaded_actions() {
+        let dir = TempDir::new().unwrap();
+        let mut workspace = Workspace::init(dir.path()).unwrap();
+        workspace.create_action("web", "setup", Priority::High).unwrap();
+        workspace.create_action("web", "deploy", Priority::None).unwrap();
+        workspace.create_action("api", "auth", Priority::None).unwrap();
+        workspace.update_action_status("api", "auth", Status::Design).unwrap();
+        workspace.set_action_estimate("web", "setup", Some(chrono::Duration::minutes(150))).unwrap();
+        workspace.set_action_estimate("web", "deploy", Some(chrono::Duration::hours(1))).unwrap();
+        workspace.log_action_time("web", "deploy", chrono::Duration::minutes(45)).unwrap();
+        
+        let stats = WorkspaceStats::collect_with(&workspace, 30, Utc::now() + chrono::Duration::days(31));
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.count(&Status::Discovery), 2);
+        assert_eq!(stats.by_project, BTreeMap::from([("api".to_string(), 1), ("web".to_string(), 2)]));
+        assert_eq!((stats.priority, stats.with_meta_graph, stats.stale), (1, 0, 3));
+        assert_eq!(workspace.stats(30).stale, 0);
+        let web = TimeTotals { estimate_minutes: 210, spent_minutes: 45 };
+        assert_eq!(stats.time_by_project, BTreeMap::from([("web".to_string(), web)]));
+        
+        let old: WorkspaceStats = serde_json::from_str(r#"{"total": 2, "by_status": {}, "completion_ratio": 0.5}"#).unwrap();
+        assert_eq!((old.total, old.stale), (2, 0), "snapshots from before the extra counts still load");
+    }
+    
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[4, 4]), "▁▁");
+        assert_eq!(sparkline(&[0, 7, 14]), "▁▄█");
+    }
+}
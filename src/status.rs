@@ -1,10 +1,18 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 use thiserror::Error;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+/// Pipeline stage of an action. Variants are declared in pipeline order,
+/// so comparisons follow it (`Discovery < Design < ... < Published`).
+///
+/// Workspaces with a custom workflow (see `Workflow`) use `Custom` for
+/// statuses that aren't built in; those sort after the built-ins, so
+/// order them with `Workflow::position` instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Status {
+    #[default]
     Discovery,
     Design,
     Implement,
@@ -12,6 +20,8 @@ pub enum Status {
     Document,
     Publish,
     Published,
+    /// A status defined by the workspace's `[[statuses]]`, stored lowercase
+    Custom(String),
 }
 
 #[derive(Error, Debug)]
@@ -20,8 +30,10 @@ pub enum StatusError {
     InvalidStatus(String),
 }
 
-impl Status {
-    pub fn from_str(s: &str) -> Result<Self, StatusError> {
+impl FromStr for Status {
+    type Err = StatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "discovery" => Ok(Status::Discovery),
             "design" => Ok(Status::Design),
@@ -33,11 +45,46 @@ impl Status {
             _ => Err(StatusError::InvalidStatus(s.to_string())),
         }
     }
+}
+
+impl From<String> for Status {
+    fn from(name: String) -> Self {
+        Status::named(&name)
+    }
+}
+
+impl From<Status> for String {
+    fn from(status: Status) -> Self {
+        status.to_string()
+    }
+}
+
+impl Status {
+    /// The built-in status called `name`, or a custom one. Unlike `from_str`
+    /// this never fails; check custom names against the workspace's `Workflow`.
+    pub fn named(name: &str) -> Status {
+        Status::from_str(name).unwrap_or_else(|_| Status::Custom(name.trim().to_lowercase()))
+    }
+    
+    /// All built-in statuses in pipeline order
+    pub fn all() -> Vec<Status> {
+        vec![
+            Status::Discovery,
+            Status::Design,
+            Status::Implement,
+            Status::Test,
+            Status::Document,
+            Status::Publish,
+            Status::Published,
+        ]
+    }
     
     pub fn as_tag(&self) -> String {
         format!("#{}", self.to_string().to_lowercase())
     }
     
+    /// The next built-in status; `None` for custom statuses, whose order
+    /// only the workspace's `Workflow` knows
     pub fn next_status(&self) -> Option<Status> {
         match self {
             Status::Discovery => Some(Status::Design),
@@ -46,13 +93,13 @@ impl Status {
             Status::Test => Some(Status::Document),
             Status::Document => Some(Status::Publish),
             Status::Publish => Some(Status::Published),
-            Status::Published => None,
+            Status::Published | Status::Custom(_) => None,
         }
     }
     
     pub fn previous_status(&self) -> Option<Status> {
         match self {
-            Status::Discovery => None,
+            Status::Discovery | Status::Custom(_) => None,
             Status::Design => Some(Status::Discovery),
             Status::Implement => Some(Status::Design),
             Status::Test => Some(Status::Implement),
@@ -62,6 +109,17 @@ impl Status {
         }
     }
     
+    /// Sections that must be filled in before an action may enter this status.
+    ///
+    /// Outputs describe what a stage produces and are checked before leaving
+    /// it; inputs are entry gates checked before arriving. None of the
+    /// built-in statuses gate entry, so workspaces opt in through
+    /// `[entry_requirements]` in the config (see `Config::required_inputs`).
+    pub fn required_inputs(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+    
+    /// Sections an action must have while at this status
     pub fn required_outputs(&self) -> Vec<&'static str> {
         match self {
             Status::Discovery => vec![
@@ -76,7 +134,7 @@ impl Status {
             Status::Test => vec![],
             Status::Document => vec!["Analysis of Impact"],
             Status::Publish => vec![],
-            Status::Published => vec![],
+            Status::Published | Status::Custom(_) => vec![],
         }
     }
 }
@@ -91,12 +149,149 @@ impl fmt::Display for Status {
             Status::Document => write!(f, "document"),
             Status::Publish => write!(f, "publish"),
             Status::Published => write!(f, "published"),
+            Status::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+/// Whether an action is being worked on, independent of its pipeline status
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lifecycle {
+    #[default]
+    Active,
+    /// Stalled; keeps its status but can't move through the pipeline
+    Blocked,
+    /// Abandoned; hidden from listings unless asked for
+    Cancelled,
+}
+
+#[derive(Error, Debug)]
+pub enum LifecycleError {
+    #[error("Invalid lifecycle: {0}. Valid lifecycles are: active, blocked, cancelled")]
+    InvalidLifecycle(String),
+}
+
+impl FromStr for Lifecycle {
+    type Err = LifecycleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(Lifecycle::Active),
+            "blocked" => Ok(Lifecycle::Blocked),
+            "cancelled" | "canceled" => Ok(Lifecycle::Cancelled),
+            _ => Err(LifecycleError::InvalidLifecycle(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Lifecycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lifecycle::Active => write!(f, "active"),
+            Lifecycle::Blocked => write!(f, "blocked"),
+            Lifecycle::Cancelled => write!(f, "cancelled"),
         }
     }
 }
 
-impl Default for Status {
-    fn default() -> Self {
-        Status::Discovery
+/// How urgent an action is. Variants are declared from least to most
+/// urgent, so `High` compares greatest.
+///
+/// Files written before levels existed used a `priority: true`/`false`
+/// flag; `true` reads as `High` and `false` as `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", try_from = "PriorityValue")]
+pub enum Priority {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Error, Debug)]
+pub enum PriorityError {
+    #[error("Invalid priority: {0}. Valid priorities are: none, low, medium, high")]
+    InvalidPriority(String),
+}
+
+impl Priority {
+    /// Every level, from least to most urgent
+    pub fn all() -> Vec<Priority> {
+        vec![Priority::None, Priority::Low, Priority::Medium, Priority::High]
+    }
+    
+    /// Whether any priority is set
+    pub fn is_set(self) -> bool {
+        self != Priority::None
+    }
+}
+
+impl From<bool> for Priority {
+    fn from(flag: bool) -> Self {
+        if flag { Priority::High } else { Priority::None }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = PriorityError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" | "false" => Ok(Priority::None),
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" | "true" => Ok(Priority::High),
+            _ => Err(PriorityError::InvalidPriority(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Priority::None => write!(f, "none"),
+            Priority::Low => write!(f, "low"),
+            Priority::Medium => write!(f, "medium"),
+            Priority::High => write!(f, "high"),
+        }
     }
-}
\ No newline at end of file
+}
+
+/// A priority as serialized: a level name or a legacy boolean flag
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PriorityValue {
+    Flag(bool),
+    Level(String),
+}
+
+impl TryFrom<PriorityValue> for Priority {
+    type Error = PriorityError;
+    
+    fn try_from(value: PriorityValue) -> Result<Self, Self::Error> {
+        match value {
+            PriorityValue::Flag(flag) => Ok(Priority::from(flag)),
+            PriorityValue::Level(level) => level.parse(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_status_from_str() {
+        assert_eq!("Design".parse::<Status>().unwrap(), Status::Design);
+        assert_eq!("PUBLISHED".parse::<Status>().unwrap(), Status::Published);
+        assert!(matches!("dsign".parse::<Status>(), Err(StatusError::InvalidStatus(name)) if name == "dsign"));
+        for status in Status::all() {
+            assert_eq!(status.to_string().parse::<Status>().unwrap(), status);
+        }
+        
+        assert_eq!(Status::default(), Status::Discovery);
+        assert_eq!(Status::named(" Review "), Status::Custom("review".to_string()));
+        assert_eq!(Status::named("Test"), Status::Test);
+    }
+}
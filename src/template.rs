@@ -1,78 +1,151 @@
-use crate::Status;
-use chrono::{DateTime, Utc};
-use uuid::Uuid;
+use crate::utils;
+use crate::Action;
+use chrono::Utc;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
 
-pub struct ActionTemplate {
-    pub id: Uuid,
-    pub title: String,
-    pub project: String,
-    pub status: Status,
-    pub priority: bool,
-    pub created_at: DateTime<Utc>,
+/// Name of the type used when none is given
+pub const DEFAULT_ACTION_TYPE: &str = "action";
+
+/// A kind of action (bug, feature, ...) with its own starting sections and
+/// extra required outputs.
+///
+/// Built-in types can be overridden and new ones added under `[types.<name>]`
+/// in the workspace config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionType {
+    /// Sections seeded with placeholder text when the action is created
+    pub sections: Vec<String>,
+    /// Sections that must be present regardless of status
+    #[serde(default)]
+    pub required: Vec<String>,
+    /// Placeholder text per section, overriding the defaults
+    #[serde(default)]
+    pub placeholders: BTreeMap<String, String>,
 }
 
-impl ActionTemplate {
-    pub fn new(project: String, title: String, priority: bool) -> Self {
-        Self {
-            id: Uuid::new_v4(),
-            title,
-            project,
-            status: Status::Discovery,
-            priority,
-            created_at: Utc::now(),
+impl ActionType {
+    pub fn builtin(name: &str) -> Option<Self> {
+        let standard = ["Notes", "Statement of Action", "Statement of Inputs"];
+        let sections = |extra: &[&str]| -> Vec<String> {
+            standard.iter().chain(extra).map(|s| s.to_string()).collect()
+        };
+        let placeholders = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+        
+        match name {
+            DEFAULT_ACTION_TYPE => Some(Self {
+                sections: sections(&[]),
+                required: Vec::new(),
+                placeholders: BTreeMap::new(),
+            }),
+            "bug" => Some(Self {
+                sections: sections(&[]),
+                required: vec!["Statement of Action".to_string()],
+                placeholders: placeholders(&[(
+                    "Statement of Action",
+                    "What is broken, how to reproduce it, and what should happen instead",
+                )]),
+            }),
+            "feature" => Some(Self {
+                sections: sections(&["Statement of Design"]),
+                required: Vec::new(),
+                placeholders: BTreeMap::new(),
+            }),
+            "research" => Some(Self {
+                sections: sections(&[]),
+                required: vec!["Notes".to_string()],
+                placeholders: placeholders(&[
+                    ("Notes", "Questions to answer and findings so far"),
+                    ("Statement of Action", "What needs to be learned and which decision it informs"),
+                ]),
+            }),
+            _ => None,
         }
     }
     
-    pub fn to_markdown(&self) -> String {
-        let mut content = String::new();
-        
-        // Frontmatter
-        content.push_str("---\n");
-        content.push_str(&format!("id: {}\n", self.id));
-        content.push_str(&format!("created_at: {}\n", self.created_at.to_rfc3339()));
-        content.push_str(&format!("updated_at: {}\n", self.created_at.to_rfc3339()));
-        content.push_str("---\n\n");
-        
-        // Title and tags
-        content.push_str(&format!("# {}\n\n", self.title));
-        content.push_str(&format!("#project #action #{} #{}", 
-            self.status.to_string(),
-            self.project.replace(" ", "-").to_lowercase()
-        ));
-        
-        if self.priority {
-            content.push_str(" #priority");
-        }
-        content.push_str("\n\n");
-        
-        // Template sections based on discovery status
-        content.push_str("## Notes\n\n");
-        content.push_str("General notes on the task\n\n");
-        
-        content.push_str("## Statement of Action\n\n");
-        content.push_str("The task to be performed, more in depth than title, may include why the action is needed\n\n");
+    /// The sections of a project's action template, in the order their
+    /// `## ` headings appear, each with its body as the placeholder text.
+    /// Anything before the first heading is ignored.
+    pub fn from_template(content: &str) -> Self {
+        let mut sections: Vec<String> = Vec::new();
+        let mut placeholders = BTreeMap::new();
+        let mut current: Option<String> = None;
+        let mut body = String::new();
         
-        content.push_str("## Statement of Inputs\n\n");
-        content.push_str("A list of .md links to other markdown files:\n\n");
-        content.push_str("- [Related Action](../other-project/related-action.md)\n\n");
-        
-        // Only include design section template if not in discovery
-        if self.status != Status::Discovery {
-            content.push_str("## Statement of Design\n\n");
-            content.push_str("### Output\n\n");
-            content.push_str("The action produced by the design\n\n");
-            content.push_str("### Design\n\n");
-            content.push_str("A detailed design for how to proceed\n\n");
+        for line in content.lines().map(|line| line.trim_end_matches('\r')) {
+            if let Some(heading) = line.strip_prefix("## ") {
+                if let Some(section) = current.take() {
+                    placeholders.insert(section, body.trim().to_string());
+                }
+                body.clear();
+                let heading = heading.trim().to_string();
+                if !sections.iter().any(|seen| seen.eq_ignore_ascii_case(&heading)) {
+                    sections.push(heading.clone());
+                }
+                current = Some(heading);
+            } else if current.is_some() {
+                body.push_str(line);
+                body.push('\n');
+            }
         }
-        
-        // Only include impact analysis if in document stage or later
-        if matches!(self.status, Status::Document | Status::Publish | Status::Published) {
-            content.push_str("## Analysis of Impact\n\n");
-            content.push_str("Analysis of the impact and outcomes of this action\n\n");
+        if let Some(section) = current {
+            placeholders.insert(section, body.trim().to_string());
         }
         
-        content
+        Self { sections, required: Vec::new(), placeholders }
     }
+    
+    pub fn builtin_names() -> &'static [&'static str] {
+        &[DEFAULT_ACTION_TYPE, "bug", "feature", "research"]
+    }
+    
+    /// Placeholder content for `section` when creating an action of this type
+    pub fn placeholder(&self, section: &str) -> String {
+        self.placeholders
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(section))
+            .map(|(_, text)| text.clone())
+            .unwrap_or_else(|| default_placeholder(section).to_string())
+    }
+}
+
+/// Built-in placeholder text for a known section
+pub fn default_placeholder(section: &str) -> &'static str {
+    match section.to_lowercase().as_str() {
+        "notes" => "General notes on the task",
+        "statement of action" => "The task to be performed, more in depth than title, may include why the action is needed",
+        "statement of inputs" => "A list of .md links to other markdown files",
+        "statement of design" => "### Output\n\nThe action produced by the design\n\n### Design\n\nA detailed design for how to proceed",
+        "analysis of impact" => "Analysis of the impact and outcomes of this action",
+        _ => "",
+    }
+}
+
+/// Expand `{{title}}`, `{{project}}`, `{{id}}`, `{{created_at}}` (RFC 3339),
+/// `{{date}}` (today, UTC), `{{git_branch}}` (of the repository holding `dir`)
+/// and `{{env:NAME}}` in `text`, as used in project templates and
+/// `default_sections`. An unset variable or a directory outside git expands
+/// to nothing; other text, including unknown `{{...}}` names, is left as written.
+pub fn expand_placeholders(text: &str, action: &Action, dir: &Path) -> String {
+    let pattern = Regex::new(r"\{\{\s*(title|project|id|created_at|date|git_branch|env:[A-Za-z_][A-Za-z0-9_]*)\s*\}\}")
+        .expect("placeholder pattern is valid");
+    let mut branch = None;
+    
+    pattern
+        .replace_all(text, |captures: &Captures| match &captures[1] {
+            "title" => action.title().to_string(),
+            "project" => action.project().to_string(),
+            "id" => action.id.to_string(),
+            "created_at" => action.created_at.to_rfc3339(),
+            "date" => Utc::now().format("%Y-%m-%d").to_string(),
+            "git_branch" => branch.get_or_insert_with(|| utils::git_branch(dir).unwrap_or_default()).clone(),
+            name => std::env::var(&name["env:".len()..]).unwrap_or_default(),
+        })
+        .into_owned()
 }
 
 pub fn get_workspace_readme() -> &'static str {
@@ -1,5 +1,94 @@
+use crate::config::{Config, DEFAULT_DATE_FORMAT};
 use anyhow::Result;
-use std::path::Path;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, Local, Utc};
+use similar::TextDiff;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+/// A file's content before and after a rewrite that hasn't been written yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub old: String,
+    pub new: String,
+}
+
+impl FileChange {
+    /// Unified diff of the change, with the path shown relative to `root`
+    pub fn diff<P: AsRef<Path>>(&self, root: P) -> String {
+        render_diff(&self.old, &self.new, Path::new(&format_relative_path(&self.path, root)))
+    }
+}
+
+/// Whether `pattern` is a strftime pattern chrono can render
+pub fn is_valid_date_format(pattern: &str) -> bool {
+    !StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error))
+}
+
+/// Render a timestamp for people, using the configured `date_format`
+/// (or the default when that pattern is invalid), in UTC or local time
+pub fn format_timestamp(timestamp: &DateTime<Utc>, config: &Config, local: bool) -> String {
+    let pattern = if is_valid_date_format(&config.date_format) {
+        config.date_format.as_str()
+    } else {
+        DEFAULT_DATE_FORMAT
+    };
+    
+    if local {
+        timestamp.with_timezone(&Local).format(pattern).to_string()
+    } else {
+        timestamp.format(pattern).to_string()
+    }
+}
+
+/// Roughly how long `duration` is, in its largest whole unit: "5 minutes",
+/// "1 day", "3 weeks". Negative durations count as zero.
+pub fn humanize_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let days = duration.num_days().max(0);
+    let (count, unit) = if minutes < 1 {
+        return "less than a minute".to_string();
+    } else if minutes < 60 {
+        (minutes, "minute")
+    } else if minutes < 24 * 60 {
+        (minutes / 60, "hour")
+    } else if days < 14 {
+        (days, "day")
+    } else if days < 60 {
+        (days / 7, "week")
+    } else if days < 365 {
+        (days / 30, "month")
+    } else {
+        (days / 365, "year")
+    };
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+/// How long before `now` the `timestamp` was, e.g. "3 days ago"
+pub fn time_ago(timestamp: &DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now - *timestamp;
+    if elapsed < Duration::minutes(1) {
+        "just now".to_string()
+    } else {
+        format!("{} ago", humanize_duration(elapsed))
+    }
+}
+
+/// Unified diff between `old` and `new` labelled with `path`; empty when they are equal
+pub fn render_diff(old: &str, new: &str, path: &Path) -> String {
+    if old == new {
+        return String::new();
+    }
+    
+    let path = path.display().to_string();
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
 
 /// Convert a title to a valid filename
 pub fn title_to_filename(title: &str) -> String {
@@ -42,6 +131,14 @@ pub fn validate_action_title(title: &str) -> Result<()> {
         anyhow::bail!("Action title cannot be longer than 100 characters");
     }
     
+    if title.contains('/') || title.contains('\\') {
+        anyhow::bail!("Action title cannot contain path separators");
+    }
+    
+    if matches!(title.trim(), "." | "..") {
+        anyhow::bail!("Action title cannot be '.' or '..'");
+    }
+    
     Ok(())
 }
 
@@ -57,6 +154,138 @@ pub fn is_subpath<P: AsRef<Path>, Q: AsRef<Path>>(path: P, parent: Q) -> bool {
     }
 }
 
+/// Determine who is making a change: git's `user.name` as configured for the
+/// workspace at `root` (so a repository-local name wins), then `$USER`/`$USERNAME`
+pub fn detect_author<P: AsRef<Path>>(root: P) -> String {
+    let git_name = Command::new("git")
+        .args(["config", "user.name"])
+        .current_dir(root.as_ref())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    
+    git_name
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// The checked-out branch of the git repository containing `dir`, if any
+pub fn git_branch<P: AsRef<Path>>(dir: P) -> Option<String> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir.as_ref())
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|branch| branch.trim().to_string())
+        .filter(|branch| !branch.is_empty() && branch != "HEAD")
+}
+
+/// The editor used for `action edit`: `$EDITOR`, then `$VISUAL`, then a platform default
+pub fn detect_editor() -> String {
+    std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .unwrap_or_else(|_| {
+            if cfg!(target_os = "windows") {
+                "notepad".to_string()
+            } else {
+                "nano".to_string()
+            }
+        })
+}
+
+/// Parse a duration written in hours and minutes: `2h30m`, `1h`, `45m`.
+/// Hours come before minutes and each appears at most once.
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let invalid = || anyhow::anyhow!("Invalid duration: {}. Use hours and minutes like 2h30m, 1h or 45m", input);
+    let mut minutes: i64 = 0;
+    let mut number = String::new();
+    let mut units = String::new();
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: i64 = number.parse().map_err(|_| invalid())?;
+        number.clear();
+        let value = match c.to_ascii_lowercase() {
+            'h' if units.is_empty() => value.checked_mul(60),
+            'm' if !units.contains('m') => Some(value),
+            _ => None,
+        };
+        minutes = value.and_then(|value| minutes.checked_add(value)).ok_or_else(invalid)?;
+        units.push(c.to_ascii_lowercase());
+    }
+    if units.is_empty() || !number.is_empty() {
+        return Err(invalid());
+    }
+    Duration::try_minutes(minutes).ok_or_else(invalid)
+}
+
+/// Write a duration the way `parse_duration` reads it, to the minute
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h{}m", hours, minutes),
+    }
+}
+
+/// Shorten `text` to at most `max` characters, ending with `…` when cut
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// The path from directory `from_dir` to `to`, using `..` where needed.
+/// Both are normalized lexically first, so they should share a base.
+pub fn relative_path<P: AsRef<Path>, Q: AsRef<Path>>(from_dir: P, to: Q) -> PathBuf {
+    let from = normalize_path(from_dir);
+    let to = normalize_path(to);
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..from.len() {
+        relative.push("..");
+    }
+    for component in &to[common..] {
+        relative.push(component.as_os_str());
+    }
+    relative
+}
+
+/// Lexically normalize a path, resolving `.` and `..` without touching the filesystem
+pub fn normalize_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    
+    for component in path.as_ref().components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    
+    normalized
+}
+
 /// Format a relative path for display
 pub fn format_relative_path<P: AsRef<Path>, Q: AsRef<Path>>(path: P, base: Q) -> String {
     let path = path.as_ref();
@@ -69,6 +298,37 @@ pub fn format_relative_path<P: AsRef<Path>, Q: AsRef<Path>>(path: P, base: Q) ->
     }
 }
 
+/// A fresh temporary file next to `path` for `write_atomic` to write to
+/// before renaming it over `path`. The process id and a random suffix keep
+/// concurrent writers of the same file from sharing one.
+pub fn atomic_temp_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    path.with_file_name(format!(".{}.{}.{}.tmp", name, std::process::id(), &suffix[..8]))
+}
+
+/// Write `content` to `path` so that readers see either the old file or the
+/// new one, never a truncated mix: the content goes to a temporary file in
+/// the same directory, which is then renamed over `path`.
+pub fn write_atomic<P: AsRef<Path>>(path: P, content: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    write_via(&atomic_temp_path(path), path, content.as_ref())
+}
+
+/// `write_atomic` through `temp`, which must not exist yet: a file someone
+/// else left there is neither written to nor removed
+fn write_via(temp: &Path, path: &Path, content: &[u8]) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(temp)?;
+    let written = file.write_all(content).and_then(|_| file.sync_all());
+    drop(file);
+    
+    let result = written.and_then(|_| fs::rename(temp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(temp);
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,10 +349,127 @@ mod tests {
         assert!(validate_project_name(".hidden").is_err());
     }
     
+    #[test]
+    fn test_duration_round_trip() {
+        assert_eq!(parse_duration("2h30m").unwrap(), Duration::minutes(150));
+        assert_eq!(parse_duration("2h 30m").unwrap(), Duration::minutes(150));
+        assert_eq!(parse_duration("45M").unwrap(), Duration::minutes(45));
+        for text in ["1h", "45m", "2h30m", "0m", "100h1m"] {
+            assert_eq!(format_duration(parse_duration(text).unwrap()), text);
+        }
+        assert_eq!(format_duration(Duration::minutes(90)), "1h30m");
+        for bad in ["", "2", "h", "30m2h", "1h1h", "1.5h", "1d", "-1h", "99999999999999999h"] {
+            assert!(parse_duration(bad).is_err(), "{}", bad);
+        }
+    }
+    
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(relative_path("/ws/web/setup", "/ws/web/setup.md"), PathBuf::from("../setup.md"));
+        assert_eq!(relative_path("/ws/api/.meta/setup", "/ws/web/x.md"), PathBuf::from("../../../web/x.md"));
+        assert_eq!(relative_path("/ws/web", "/ws/web/./sub/../a.md"), PathBuf::from("a.md"));
+    }
+    
+    #[test]
+    fn test_format_timestamp() {
+        use chrono::TimeZone;
+        let timestamp = Utc.with_ymd_and_hms(2025, 3, 9, 14, 5, 0).unwrap();
+        
+        let mut config = Config::default();
+        assert_eq!(format_timestamp(&timestamp, &config, false), "2025-03-09 14:05 UTC");
+        
+        config.date_format = "%d %b %Y".to_string();
+        assert_eq!(format_timestamp(&timestamp, &config, false), "09 Mar 2025");
+        
+        config.date_format = "%H:%M %z".to_string();
+        let expected = timestamp.with_timezone(&Local).format("%H:%M %z").to_string();
+        assert_eq!(format_timestamp(&timestamp, &config, true), expected);
+        
+        config.date_format = "%Y-%Q".to_string();
+        assert!(!is_valid_date_format(&config.date_format));
+        assert_eq!(format_timestamp(&timestamp, &config, false), "2025-03-09 14:05 UTC");
+    }
+    
+    #[test]
+    fn test_humanize_duration() {
+        assert_eq!(humanize_duration(Duration::seconds(30)), "less than a minute");
+        assert_eq!(humanize_duration(Duration::minutes(1)), "1 minute");
+        assert_eq!(humanize_duration(Duration::minutes(150)), "2 hours");
+        assert_eq!(humanize_duration(Duration::days(3)), "3 days");
+        assert_eq!(humanize_duration(Duration::days(20)), "2 weeks");
+        assert_eq!(humanize_duration(Duration::days(100)), "3 months");
+        assert_eq!(humanize_duration(Duration::days(800)), "2 years");
+        assert_eq!(humanize_duration(Duration::days(-2)), "less than a minute");
+        
+        let now = Utc::now();
+        assert_eq!(time_ago(&(now - Duration::days(3)), now), "3 days ago");
+        assert_eq!(time_ago(&now, now), "just now");
+    }
+    
+    #[test]
+    fn test_render_diff() {
+        assert_eq!(render_diff("same\n", "same\n", Path::new("web/a.md")), "");
+        
+        let diff = render_diff("one\ntwo\n", "one\n2\n", Path::new("web/a.md"));
+        assert!(diff.starts_with("--- a/web/a.md\n+++ b/web/a.md\n"), "{}", diff);
+        assert!(diff.contains("-two\n+2\n"), "{}", diff);
+    }
+    
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("exactly10!", 10), "exactly10!");
+        assert_eq!(truncate("a bit too long", 10), "a bit too…");
+    }
+    
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("ws/web/./../api/setup.md"), PathBuf::from("ws/api/setup.md"));
+        assert_eq!(normalize_path("./web/setup.md"), PathBuf::from("web/setup.md"));
+        assert_eq!(normalize_path("../setup.md"), PathBuf::from("../setup.md"));
+    }
+    
     #[test]
     fn test_validate_action_title() {
         assert!(validate_action_title("Valid Title").is_ok());
         assert!(validate_action_title("").is_err());
         assert!(validate_action_title(&"x".repeat(101)).is_err());
+        assert!(validate_action_title("../../escaped").is_err());
+        assert!(validate_action_title("a\\b").is_err());
+        assert!(validate_action_title("..").is_err());
+        assert!(validate_action_title("Release 1.2").is_ok());
+    }
+    
+    #[test]
+    fn test_write_atomic_uses_a_fresh_temp_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("setup.md");
+        let temp = atomic_temp_path(&path);
+        assert_ne!(temp, atomic_temp_path(&path));
+        assert_eq!(temp.parent(), Some(dir.path()));
+        let name = temp.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with(&format!(".setup.md.{}.", std::process::id())) && name.ends_with(".tmp"), "{}", name);
+        
+        write_atomic(&path, "first").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+        
+        // A temp name already taken is left alone, and so is the original
+        fs::write(&temp, "someone else's").unwrap();
+        assert!(write_via(&temp, &path, b"second").is_err());
+        assert_eq!(fs::read_to_string(&temp).unwrap(), "someone else's");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+    }
+    
+    #[test]
+    fn test_detect_author_reads_the_workspace_repository() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git").arg("-C").arg(dir.path()).args(args).output().unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        };
+        git(&["init", "--quiet"]);
+        git(&["config", "user.name", "Workspace Author"]);
+        
+        assert_eq!(detect_author(dir.path()), "Workspace Author");
     }
 }
\ No newline at end of file
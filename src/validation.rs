@@ -0,0 +1,77 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single problem found while validating a workspace.
+///
+/// `code` is a stable identifier (e.g. `missing-section`) that tools can
+/// filter on; `message` is the human-readable description.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub project: Option<String>,
+    pub title: Option<String>,
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn error(code: &'static str, message: String) -> Self {
+        Self {
+            project: None,
+            title: None,
+            severity: Severity::Error,
+            code,
+            message,
+        }
+    }
+    
+    pub fn warning(code: &'static str, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(code, message)
+        }
+    }
+    
+    pub fn for_project(mut self, project: &str) -> Self {
+        self.project = Some(project.to_string());
+        self
+    }
+    
+    pub fn for_action(mut self, project: &str, title: &str) -> Self {
+        self.project = Some(project.to_string());
+        self.title = Some(title.to_string());
+        self
+    }
+    
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.code)?;
+        match (&self.project, &self.title) {
+            (Some(project), Some(title)) => write!(f, "{}/{}: ", project, title)?,
+            (Some(project), None) => write!(f, "{}: ", project)?,
+            _ => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::config::STATE_DIR;
+use anyhow::{Context, Result};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Directory that archived actions are moved to; never watched
+pub const ARCHIVE_DIR: &str = ".archive";
+
+/// How long the workspace must be quiet before a batch of changes is reported
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Recursive watch on a workspace that reports changed action files in batches
+pub struct WorkspaceWatcher {
+    root: PathBuf,
+    events: Receiver<notify::Result<Event>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl WorkspaceWatcher {
+    pub fn new<P: AsRef<Path>>(root: P) -> Result<Self> {
+        // Events carry absolute paths, so match them against an absolute root
+        let root = root.as_ref().canonicalize()
+            .with_context(|| format!("Failed to resolve {}", root.as_ref().display()))?;
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .context("Failed to start watching the workspace")?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", root.display()))?;
+        Ok(Self { root, events, _watcher: watcher })
+    }
+    
+    /// The watched directory, as reported in event paths
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    
+    /// Block until an action file changes, then keep collecting changes until
+    /// none arrive for `debounce`. Returns the changed files, sorted and
+    /// without duplicates, or `None` once the watcher has stopped.
+    pub fn next_batch(&self, debounce: Duration) -> Result<Option<Vec<PathBuf>>> {
+        let mut changed = BTreeSet::new();
+        loop {
+            let event = if changed.is_empty() {
+                match self.events.recv() {
+                    Ok(event) => event,
+                    Err(_) => return Ok(None),
+                }
+            } else {
+                match self.events.recv_timeout(debounce) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => {
+                        return Ok(Some(changed.into_iter().collect()));
+                    }
+                }
+            };
+            
+            let event = event.context("Error while watching the workspace")?;
+            // Reads, including our own reloads, and metadata updates aren't edits
+            if matches!(event.kind, EventKind::Access(_) | EventKind::Modify(ModifyKind::Metadata(_))) {
+                continue;
+            }
+            changed.extend(event.paths.into_iter().filter(|path| is_watched(&self.root, path)));
+        }
+    }
+}
+
+/// Whether a change to `path` matters: a markdown file under `root` that
+/// isn't inside the state or archive directory
+pub fn is_watched(root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    path.extension().is_some_and(|ext| ext == "md")
+        && !relative
+            .components()
+            .any(|component| component.as_os_str() == STATE_DIR || component.as_os_str() == ARCHIVE_DIR)
+}
+
+/// Names of the projects the changed `paths` belong to
+pub fn affected_projects(root: &Path, paths: &[PathBuf]) -> BTreeSet<String> {
+    paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(root).ok())
+        .filter(|relative| relative.components().count() > 1)
+        .filter_map(|relative| relative.components().next())
+        .filter_map(|component| component.as_os_str().to_str())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_only_action_files_outside_state_dirs_are_watched() {
+        let root = Path::new("/ws");
+        assert!(is_watched(root, Path::new("/ws/web/setup.md")));
+        assert!(is_watched(root, Path::new("/ws/web/.meta/setup/notes.md")));
+        assert!(!is_watched(root, Path::new("/ws/web/setup.md.swp")));
+        assert!(!is_watched(root, Path::new("/ws/.action-lite/templates/bug.md")));
+        assert!(!is_watched(root, Path::new("/ws/.archive/web/old.md")));
+        assert!(!is_watched(root, Path::new("/elsewhere/web/setup.md")));
+    }
+    
+    #[test]
+    fn test_affected_projects() {
+        let root = Path::new("/ws");
+        let paths = vec![
+            PathBuf::from("/ws/web/setup.md"),
+            PathBuf::from("/ws/web/deploy.md"),
+            PathBuf::from("/ws/api/.meta/auth/notes.md"),
+            PathBuf::from("/ws/README.md"),
+        ];
+        let projects: Vec<String> = affected_projects(root, &paths).into_iter().collect();
+        assert_eq!(projects, ["api", "web"]);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::Status;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// One stage of a custom workflow, configured as a `[[statuses]]` table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowStatus {
+    pub name: String,
+    /// Sections an action must have while at this status
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_outputs: Vec<String>,
+}
+
+/// The ordered statuses actions move through. Defaults to the seven
+/// built-in statuses; a workspace replaces them by listing `[[statuses]]`
+/// in its config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workflow {
+    statuses: Vec<(Status, Vec<String>)>,
+}
+
+impl Default for Workflow {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Workflow {
+    /// `Status::all()` with each status's built-in required outputs
+    pub fn builtin() -> Self {
+        let statuses = Status::all()
+            .into_iter()
+            .map(|status| {
+                let outputs = status.required_outputs().iter().map(|s| s.to_string()).collect();
+                (status, outputs)
+            })
+            .collect();
+        Self { statuses }
+    }
+    
+    /// Build a workflow from configured statuses, in the order given.
+    /// An empty list means the built-in workflow. Names are case-insensitive
+    /// and must be unique single words.
+    pub fn from_config(statuses: &[WorkflowStatus]) -> Result<Self> {
+        if statuses.is_empty() {
+            return Ok(Self::builtin());
+        }
+        
+        let mut seen = HashSet::new();
+        let mut workflow = Vec::with_capacity(statuses.len());
+        for configured in statuses {
+            let name = configured.name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) || name.contains(':') {
+                anyhow::bail!("Invalid status name '{}' in workflow: names must be single words", configured.name);
+            }
+            if !seen.insert(name.to_lowercase()) {
+                anyhow::bail!("Status '{}' appears more than once in the workflow", name);
+            }
+            workflow.push((Status::named(name), configured.required_outputs.clone()));
+        }
+        Ok(Self { statuses: workflow })
+    }
+    
+    /// Whether this is the built-in workflow
+    pub fn is_builtin(&self) -> bool {
+        *self == Self::builtin()
+    }
+    
+    /// Statuses in workflow order
+    pub fn statuses(&self) -> Vec<Status> {
+        self.statuses.iter().map(|(status, _)| status.clone()).collect()
+    }
+    
+    /// Where new actions start
+    pub fn first(&self) -> &Status {
+        &self.statuses[0].0
+    }
+    
+    /// The status that marks an action as done
+    pub fn last(&self) -> &Status {
+        &self.statuses[self.statuses.len() - 1].0
+    }
+    
+    /// Index of `status` in the workflow; `None` if it isn't part of it
+    pub fn position(&self, status: &Status) -> Option<usize> {
+        self.statuses.iter().position(|(s, _)| s == status)
+    }
+    
+    /// Resolve a status name against the workflow
+    pub fn parse(&self, name: &str) -> Result<Status> {
+        let status = Status::named(name);
+        if self.position(&status).is_none() {
+            anyhow::bail!(
+                "Invalid status: {}. Valid statuses are: {}",
+                name,
+                self.statuses().iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ")
+            );
+        }
+        Ok(status)
+    }
+    
+    pub fn next(&self, status: &Status) -> Option<Status> {
+        let position = self.position(status)?;
+        self.statuses.get(position + 1).map(|(s, _)| s.clone())
+    }
+    
+    pub fn previous(&self, status: &Status) -> Option<Status> {
+        let position = self.position(status)?.checked_sub(1)?;
+        Some(self.statuses[position].0.clone())
+    }
+    
+    /// Statuses strictly between `from` and `to`, in the order a move from
+    /// one to the other passes them; empty if either is outside the workflow
+    pub fn between(&self, from: &Status, to: &Status) -> Vec<Status> {
+        let (Some(from), Some(to)) = (self.position(from), self.position(to)) else {
+            return Vec::new();
+        };
+        let statuses = self.statuses.iter().map(|(s, _)| s.clone());
+        if from < to {
+            statuses.skip(from + 1).take(to - from - 1).collect()
+        } else {
+            statuses.skip(to + 1).take(from.saturating_sub(to + 1)).rev().collect()
+        }
+    }
+    
+    /// Sections an action must have while at `status`
+    pub fn required_outputs(&self, status: &Status) -> Vec<String> {
+        self.statuses
+            .iter()
+            .find(|(s, _)| s == status)
+            .map(|(_, outputs)| outputs.clone())
+            .unwrap_or_default()
+    }
+    
+    /// Order two statuses by workflow position; statuses outside the
+    /// workflow sort after every status in it
+    pub fn compare(&self, a: &Status, b: &Status) -> std::cmp::Ordering {
+        let key = |status: &Status| self.position(status).unwrap_or(usize::MAX);
+        key(a).cmp(&key(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn configured(names: &[&str]) -> Vec<WorkflowStatus> {
+        names
+            .iter()
+            .map(|name| WorkflowStatus { name: name.to_string(), required_outputs: Vec::new() })
+            .collect()
+    }
+    
+    #[test]
+    fn test_builtin_matches_status() {
+        let workflow = Workflow::from_config(&[]).unwrap();
+        assert!(workflow.is_builtin());
+        assert_eq!(workflow.statuses(), Status::all());
+        for status in Status::all() {
+            assert_eq!(workflow.next(&status), status.next_status());
+            assert_eq!(workflow.previous(&status), status.previous_status());
+            assert_eq!(workflow.required_outputs(&status), status.required_outputs());
+        }
+        assert_eq!(workflow.between(&Status::Discovery, &Status::Test), [Status::Design, Status::Implement]);
+        assert_eq!(workflow.between(&Status::Test, &Status::Discovery), [Status::Implement, Status::Design]);
+    }
+    
+    #[test]
+    fn test_custom_workflow() {
+        let mut statuses = configured(&["Todo", "review", "done"]);
+        statuses[1].required_outputs = vec!["Notes".to_string()];
+        let workflow = Workflow::from_config(&statuses).unwrap();
+        
+        let todo = Status::Custom("todo".to_string());
+        let review = workflow.parse("REVIEW").unwrap();
+        assert_eq!(workflow.first(), &todo);
+        assert_eq!(workflow.last().to_string(), "done");
+        assert_eq!(workflow.next(&todo), Some(review.clone()));
+        assert_eq!(workflow.previous(&todo), None);
+        assert_eq!(workflow.required_outputs(&review), ["Notes"]);
+        assert!(workflow.required_outputs(&todo).is_empty());
+        assert!(workflow.compare(&review, &todo).is_gt());
+        assert!(workflow.between(&todo, &review).is_empty());
+        
+        let error = workflow.parse("design").unwrap_err();
+        assert!(error.to_string().contains("todo, review, done"), "{}", error);
+    }
+    
+    #[test]
+    fn test_rejects_duplicate_names() {
+        let error = Workflow::from_config(&configured(&["todo", "done", "Todo"])).unwrap_err();
+        assert!(error.to_string().contains("more than once"), "{}", error);
+        assert!(Workflow::from_config(&configured(&["in review"])).is_err());
+    }
+}
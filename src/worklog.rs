@@ -0,0 +1,168 @@
+use crate::Action;
+use anyhow::{Context, Result};
+use chrono::{FixedOffset, NaiveDate};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// What happened to an action on a given day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Activity {
+    Created,
+    /// Changed without a more specific record of what changed
+    Updated,
+    /// A `Log` entry added with `action comment`
+    Comment { author: String, text: String },
+}
+
+impl fmt::Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Activity::Created => write!(f, "created"),
+            Activity::Updated => write!(f, "updated"),
+            Activity::Comment { author, text } => write!(f, "{}: {}", author, text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorklogEntry {
+    pub project: String,
+    pub title: String,
+    pub activity: Activity,
+}
+
+/// Activity grouped by calendar day, oldest day first
+pub type Worklog = BTreeMap<NaiveDate, Vec<WorklogEntry>>;
+
+#[derive(Debug, Clone)]
+pub struct WorklogOptions {
+    /// Leave out days before this one
+    pub since: Option<NaiveDate>,
+    /// Only comments by this author (case-insensitive). Creation and updates
+    /// carry no attribution, so they are left out when this is set.
+    pub author: Option<String>,
+    /// Where day boundaries fall for `created_at`/`updated_at`.
+    /// Log entries are already dated and are used as written.
+    pub offset: FixedOffset,
+}
+
+impl Default for WorklogOptions {
+    fn default() -> Self {
+        Self {
+            since: None,
+            author: None,
+            offset: FixedOffset::east_opt(0).unwrap(),
+        }
+    }
+}
+
+/// Parse a `--tz` value: `UTC` or an offset such as `+02:00` or `-0500`
+pub fn parse_offset(value: &str) -> Result<FixedOffset> {
+    if value.eq_ignore_ascii_case("utc") || value == "Z" {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+    
+    value.parse::<FixedOffset>()
+        .ok()
+        .with_context(|| format!("Invalid time zone: {}. Use UTC or an offset like +02:00", value))
+}
+
+/// Bucket the activity of `actions` by day.
+///
+/// An action's creation and its log entries are reported on their own days;
+/// its last update is only reported on a day with nothing else for it, since
+/// creating or commenting on an action also updates it.
+pub fn collect<'a>(actions: impl IntoIterator<Item = &'a Action>, options: &WorklogOptions) -> Worklog {
+    let mut worklog = Worklog::new();
+    
+    for action in actions {
+        let mut activity: Vec<(NaiveDate, Activity)> = Vec::new();
+        
+        if options.author.is_none() {
+            activity.push((action.created_at.with_timezone(&options.offset).date_naive(), Activity::Created));
+        }
+        
+        for entry in action.log_entries() {
+            if options.author.as_ref().is_some_and(|author| !author.eq_ignore_ascii_case(&entry.author)) {
+                continue;
+            }
+            activity.push((entry.date, Activity::Comment { author: entry.author, text: entry.text }));
+        }
+        
+        let updated = action.updated_at.with_timezone(&options.offset).date_naive();
+        if options.author.is_none() && !activity.iter().any(|(day, _)| *day == updated) {
+            activity.push((updated, Activity::Updated));
+        }
+        
+        for (day, activity) in activity {
+            if options.since.is_some_and(|since| day < since) {
+                continue;
+            }
+            worklog.entry(day).or_default().push(WorklogEntry {
+                project: action.project().to_string(),
+                title: action.title().to_string(),
+                activity,
+            });
+        }
+    }
+    
+    worklog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+    use chrono::{TimeZone, Utc};
+    
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+    
+    fn action(title: &str, created: (u32, u32), updated: (u32, u32)) -> Action {
+        let mut action = Action::new("web".to_string(), title.to_string(), Priority::None);
+        action.created_at = Utc.with_ymd_and_hms(2025, 3, created.0, created.1, 0, 0).unwrap();
+        action.updated_at = Utc.with_ymd_and_hms(2025, 3, updated.0, updated.1, 0, 0).unwrap();
+        action
+    }
+    
+    fn summary(worklog: &Worklog) -> Vec<(NaiveDate, String, String)> {
+        worklog.iter()
+            .flat_map(|(day, entries)| {
+                entries.iter().map(|entry| (*day, entry.title.clone(), entry.activity.to_string()))
+            })
+            .collect()
+    }
+    
+    #[test]
+    fn test_activity_is_grouped_by_day() {
+        let mut setup = action("setup", (1, 9), (3, 12));
+        setup.log = Some("- 2025-03-03 (alice): Installed deps\nfree text\n- 2025-03-02 (bob): Started".to_string());
+        let deploy = action("deploy", (2, 9), (4, 12));
+        let worklog = collect([&setup, &deploy], &WorklogOptions::default());
+        
+        assert_eq!(summary(&worklog), [
+            (day(1), "setup".to_string(), "created".to_string()),
+            (day(2), "setup".to_string(), "bob: Started".to_string()),
+            (day(2), "deploy".to_string(), "created".to_string()),
+            (day(3), "setup".to_string(), "alice: Installed deps".to_string()),
+            (day(4), "deploy".to_string(), "updated".to_string()),
+        ]);
+        
+        let options = WorklogOptions { since: Some(day(3)), author: Some("ALICE".to_string()), ..Default::default() };
+        assert_eq!(summary(&collect([&setup, &deploy], &options)), [
+            (day(3), "setup".to_string(), "alice: Installed deps".to_string()),
+        ]);
+    }
+    
+    #[test]
+    fn test_offset_shifts_day_boundaries() {
+        let late = action("late", (1, 23), (1, 23));
+        let options = WorklogOptions { offset: parse_offset("+02:00").unwrap(), ..Default::default() };
+        assert_eq!(summary(&collect([&late], &options)), [(day(2), "late".to_string(), "created".to_string())]);
+        
+        assert_eq!(parse_offset("UTC").unwrap().local_minus_utc(), 0);
+        assert_eq!(parse_offset("-0500").unwrap().local_minus_utc(), -5 * 3600);
+        assert!(parse_offset("Europe/Paris").is_err());
+    }
+}
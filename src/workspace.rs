@@ -1,260 +1,3913 @@
-use crate::{Action, Project, Status};
+use crate::action::{ActionLink, LinkTarget, SECTION_NAMES};
+use crate::config::{self, Config, MetaGraphLayout};
+use crate::export::{ExportFormat, ExportedAction, ImportData, ProjectExport, WorkspaceExport};
+use crate::git;
+use crate::grep;
+use crate::history::{self, Change, Field};
+use crate::hooks;
+use crate::id::ActionId;
+use crate::template::{self, DEFAULT_ACTION_TYPE};
+use crate::utils::{self, FileChange};
+use crate::project::{self, is_symlink, DiscoveryGuard, MetaGraphMove};
+use crate::parser::MarkdownParser;
+use crate::{Action, ActionGraph, Direction, FilterExpr, Lifecycle, Priority, Project, Status, ValidationIssue, Workflow, WorkspaceStats};
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use rayon::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::str::FromStr;
+use thiserror::Error;
 
 #[derive(Debug)]
 pub struct Workspace {
     pub root: PathBuf,
-    projects: HashMap<String, Project>,
+    projects: BTreeMap<String, Project>,
+    config: Config,
+    /// Statuses from the config, or the built-in ones
+    workflow: Workflow,
+    /// Refuse every operation that would write to disk
+    read_only: bool,
+    /// Project directories that couldn't be loaded at all
+    load_errors: Vec<(PathBuf, String)>,
+}
+
+/// `ActionFilter::assignee` value matching actions nobody is assigned to
+pub const UNASSIGNED: &str = "unassigned";
+
+#[derive(Error, Debug)]
+pub enum WorkspaceError {
+    #[error("Not an Action Lite workspace. Run 'action init' to initialize a workspace in: {}", .0.display())]
+    NotAWorkspace(PathBuf),
+    #[error("Workspace is read-only; refusing to modify it")]
+    ReadOnly,
+    #[error("Workspace is locked by another process")]
+    Locked,
+}
+
+/// Exclusive hold on a workspace, released when dropped
+#[derive(Debug)]
+pub struct WorkspaceLock {
+    _file: fs::File,
+}
+
+/// Options controlling how `Workspace::init_with` sets up a workspace
+#[derive(Debug, Clone, Default)]
+pub struct InitOptions {
+    /// Only write the marker, no README
+    pub bare: bool,
+    /// Custom README to seed the workspace with instead of the bundled one
+    pub template: Option<PathBuf>,
+    /// Re-initialize even if a workspace already exists
+    pub force: bool,
+    /// Don't write `.action-lite/.gitignore`
+    pub no_gitignore: bool,
+    /// Run `git init` and write a top-level `.gitignore`
+    pub git: bool,
+}
+
+/// Result of trying to move one action to its next status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdvanceOutcome {
+    Advanced { title: String, from: Status, to: Status },
+    /// The current status still has required outputs missing
+    Blocked { title: String, status: Status, missing: Vec<String> },
+    /// Already at the final status
+    Finished { title: String },
+    /// Blocked or cancelled, so left where it is
+    Inactive { title: String, lifecycle: Lifecycle },
+}
+
+/// Summary of a workspace's location and settings, from `Workspace::info`
+#[derive(Debug, Clone)]
+pub struct WorkspaceInfo {
+    pub root: PathBuf,
+    /// The config file, if the workspace has one; defaults apply otherwise
+    pub config_file: Option<PathBuf>,
+    /// Whether the marker is the legacy empty file rather than a directory
+    pub legacy_marker: bool,
+    pub projects: usize,
+    pub actions: usize,
+    pub meta_graph_layout: MetaGraphLayout,
+    pub follow_symlinks: bool,
+    /// Action types defined in the config
+    pub custom_types: Vec<String>,
+    pub editor: String,
+}
+
+/// Which actions use each tag, and how often tags appear together
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagIndex {
+    /// Tag to the `(project, title)` of every action carrying it
+    pub actions: BTreeMap<String, Vec<(String, String)>>,
+    /// For each tag, how many actions carry it together with each other tag
+    pub co_occurrence: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl TagIndex {
+    /// Every tag with its action count, most used first; ties by name
+    pub fn counts(&self) -> Vec<(&str, usize)> {
+        let mut counts: Vec<(&str, usize)> = self.actions
+            .iter()
+            .map(|(tag, actions)| (tag.as_str(), actions.len()))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+    
+    /// Tags appearing alongside `tag`, most frequent first; ties by name
+    pub fn related(&self, tag: &str) -> Vec<(&str, usize)> {
+        let mut related: Vec<(&str, usize)> = self.co_occurrence
+            .get(tag)
+            .map(|others| others.iter().map(|(other, &count)| (other.as_str(), count)).collect())
+            .unwrap_or_default();
+        related.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        related
+    }
+}
+
+/// Progress of a parent action computed from its children's statuses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rollup {
+    /// `(project, title, status)` of each resolved child
+    pub children: Vec<(String, String, Status)>,
+    /// Child link targets that don't point at an action
+    pub unresolved: Vec<String>,
+    /// The least advanced child status; `None` without children
+    pub status: Option<Status>,
+    /// The workflow's final status
+    pub final_status: Status,
+}
+
+impl Rollup {
+    /// Children that have reached the final status
+    pub fn published(&self) -> usize {
+        self.children.iter().filter(|(_, _, status)| *status == self.final_status).count()
+    }
+}
+
+/// What `Workspace::import` wrote
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub projects_created: usize,
+    pub actions_created: usize,
+    /// Existing actions replaced because `overwrite` was set
+    pub actions_overwritten: usize,
+}
+
+/// Options for `Workspace::create_action_with`
+#[derive(Debug, Clone, Default)]
+pub struct CreateOptions {
+    pub priority: Priority,
+    /// Action type name; the default type when `None`
+    pub action_type: Option<String>,
+    /// Skip the type's placeholder sections and the configured default sections
+    pub blank: bool,
+    /// Remove the action again if the `on_create` hook fails
+    pub rollback_on_hook_failure: bool,
+    /// Content for sections, used instead of their placeholder text
+    pub sections: Vec<(String, String)>,
+}
+
+/// Criteria used to select actions for listing and grouping
+#[derive(Debug, Clone, Default)]
+pub struct ActionFilter {
+    pub project: Option<String>,
+    pub status: Option<Status>,
+    /// Statuses to leave out
+    pub excluded_statuses: Vec<Status>,
+    /// Earliest pipeline stage to include (inclusive)
+    pub min_status: Option<Status>,
+    /// Latest pipeline stage to include (inclusive)
+    pub max_status: Option<Status>,
+    pub priority_only: bool,
+    /// Only actions in this lifecycle; when `None`, everything except cancelled actions
+    pub lifecycle: Option<Lifecycle>,
+    pub action_type: Option<String>,
+    /// Only actions that have a meta-graph directory
+    pub has_meta_graph: bool,
+    /// Sections that must be present; custom `## ` sections count too
+    pub has_sections: Vec<String>,
+    /// Sections that must be absent
+    pub missing_sections: Vec<String>,
+    /// Only actions carrying this tag anywhere in their content (case-insensitive, no `#`)
+    pub tag: Option<String>,
+    /// Only actions past their due date (see `Action::is_overdue`)
+    pub overdue: bool,
+    /// Only actions due before this moment
+    pub due_before: Option<DateTime<Utc>>,
+    /// Only actions assigned to this person (case-insensitive), or with
+    /// `UNASSIGNED`, only those assigned to nobody
+    pub assignee: Option<String>,
+    /// Boolean expression from `list --filter`, checked on top of the other criteria
+    pub expr: Option<FilterExpr>,
+}
+
+impl ActionFilter {
+    fn validate(&self, workflow: &Workflow) -> Result<()> {
+        if let Some(status) = self.status.as_ref().filter(|status| self.excluded_statuses.contains(status)) {
+            anyhow::bail!("Status '{}' is both included and excluded; nothing can match", status);
+        }
+        
+        if let (Some(min), Some(max)) = (&self.min_status, &self.max_status) {
+            if workflow.compare(min, max).is_gt() {
+                anyhow::bail!("Minimum status '{}' comes after maximum status '{}'", min, max);
+            }
+        }
+        
+        if self.has_sections.iter().chain(&self.missing_sections).any(|name| name.trim().is_empty()) {
+            anyhow::bail!("Section name cannot be empty");
+        }
+        
+        Ok(())
+    }
+    
+    /// Whether `action` meets every criterion; status ranges follow `workflow`'s order
+    pub fn matches(&self, action: &Action, workflow: &Workflow) -> bool {
+        if let Some(project) = &self.project {
+            if action.project() != project {
+                return false;
+            }
+        }
+        
+        if let Some(status) = &self.status {
+            if action.status() != status {
+                return false;
+            }
+        }
+        
+        if self.excluded_statuses.contains(action.status()) {
+            return false;
+        }
+        
+        if self.min_status.as_ref().is_some_and(|min| workflow.compare(action.status(), min).is_lt())
+            || self.max_status.as_ref().is_some_and(|max| workflow.compare(action.status(), max).is_gt())
+        {
+            return false;
+        }
+        
+        if self.priority_only && !action.is_priority() {
+            return false;
+        }
+        
+        match self.lifecycle {
+            Some(lifecycle) if action.lifecycle() != lifecycle => return false,
+            None if action.lifecycle() == Lifecycle::Cancelled => return false,
+            _ => {}
+        }
+        
+        if let Some(action_type) = &self.action_type {
+            if action.action_type() != action_type {
+                return false;
+            }
+        }
+        
+        if self.has_meta_graph && !action.has_meta_graph() {
+            return false;
+        }
+        
+        if self.has_sections.iter().any(|name| action.section(name).is_none()) {
+            return false;
+        }
+        
+        if self.missing_sections.iter().any(|name| action.section(name).is_some()) {
+            return false;
+        }
+        
+        if let Some(tag) = &self.tag {
+            if !action.tags().iter().any(|t| t.eq_ignore_ascii_case(tag.trim_start_matches('#'))) {
+                return false;
+            }
+        }
+        
+        if self.overdue && !action.is_overdue(Utc::now()) {
+            return false;
+        }
+        
+        if let Some(before) = self.due_before {
+            if action.due.is_none_or(|due| due >= before) {
+                return false;
+            }
+        }
+        
+        if self.assignee.as_ref().is_some_and(|assignee| !action.is_assigned_to(assignee)) {
+            return false;
+        }
+        
+        if let Some(expr) = &self.expr {
+            if !expr.matches(action) {
+                return false;
+            }
+        }
+        
+        true
+    }
+}
+
+/// Dimension used to group actions in `list --group-by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupKey {
+    Status,
+    Project,
+    Priority,
+    /// Actions with no assignee land in a trailing `UNASSIGNED` group
+    Assignee,
+}
+
+#[derive(Error, Debug)]
+pub enum GroupKeyError {
+    #[error("Invalid group key: {0}. Valid keys are: status, project, priority, assignee")]
+    InvalidKey(String),
+}
+
+impl FromStr for GroupKey {
+    type Err = GroupKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "status" => Ok(GroupKey::Status),
+            "project" => Ok(GroupKey::Project),
+            "priority" => Ok(GroupKey::Priority),
+            "assignee" => Ok(GroupKey::Assignee),
+            _ => Err(GroupKeyError::InvalidKey(s.to_string())),
+        }
+    }
+}
+
+/// Field used to order actions in `list --sort`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Oldest first
+    Created,
+    /// Least recently updated first
+    Updated,
+    /// Workflow order
+    Status,
+    /// Most urgent first
+    Priority,
+    Title,
+}
+
+#[derive(Error, Debug)]
+pub enum SortKeyError {
+    #[error("Invalid sort key: {0}. Valid keys are: created, updated, status, priority, title")]
+    InvalidKey(String),
+}
+
+impl FromStr for SortKey {
+    type Err = SortKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "created" => Ok(SortKey::Created),
+            "updated" => Ok(SortKey::Updated),
+            "status" => Ok(SortKey::Status),
+            "priority" => Ok(SortKey::Priority),
+            "title" => Ok(SortKey::Title),
+            _ => Err(SortKeyError::InvalidKey(s.to_string())),
+        }
+    }
 }
 
 impl Workspace {
     pub fn init<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::init_with(path, &InitOptions::default())
+    }
+    
+    pub fn init_with<P: AsRef<Path>>(path: P, options: &InitOptions) -> Result<Self> {
         let root = path.as_ref().to_path_buf();
         
+        // Refuse to silently re-initialize an existing workspace
+        let marker_file = root.join(".action-lite");
+        if marker_file.exists() && !options.force {
+            anyhow::bail!(
+                "An Action Lite workspace already exists at {}. Use --force to re-initialize it",
+                root.display()
+            );
+        }
+        
+        // Read the custom README template up front so a bad path leaves nothing behind
+        let readme_content = match &options.template {
+            Some(template) => fs::read_to_string(template)
+                .with_context(|| format!("Failed to read README template: {}", template.display()))?,
+            None => include_str!("../templates/workspace_readme.md").to_string(),
+        };
+        
         // Create workspace directory if it doesn't exist
         if !root.exists() {
             fs::create_dir_all(&root)
                 .with_context(|| format!("Failed to create workspace directory: {}", root.display()))?;
         }
         
-        // Create .action-lite marker file
-        let marker_file = root.join(".action-lite");
-        fs::write(&marker_file, "# Action Lite Workspace\n")
-            .with_context(|| "Failed to create workspace marker file")?;
+        // Create .action-lite marker directory
+        config::ensure_state_dir(&root)
+            .with_context(|| "Failed to create workspace marker")?;
+        
+        // Keep the settings of a workspace that is being re-initialized
+        let mut config = Config::load(&root)?;
+        if options.no_gitignore {
+            // Saved so later writes, like a meta-graph migration, don't add one back
+            config.gitignore = false;
+            config.save(&root)?;
+        } else {
+            config::ensure_gitignore(&root)?;
+        }
         
-        // Create README
+        if options.git {
+            git::init(&root)?;
+            config::ensure_root_gitignore(&root)?;
+        }
+        
+        // Create README; an existing one is only replaced by an explicit
+        // --template together with --force
         let readme_path = root.join("README.md");
-        if !readme_path.exists() {
-            let readme_content = include_str!("../templates/workspace_readme.md");
+        if !options.bare && (!readme_path.exists() || (options.force && options.template.is_some())) {
             fs::write(&readme_path, readme_content)
                 .with_context(|| "Failed to create workspace README")?;
         }
         
         Ok(Self {
             root,
-            projects: HashMap::new(),
+            projects: BTreeMap::new(),
+            workflow: config.workflow()?,
+            config,
+            read_only: false,
+            load_errors: Vec::new(),
         })
     }
     
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::open(path, false)
+    }
+    
+    /// Like `load`, but only find the project directories; each action file
+    /// is read when `load_action` first asks for it. Meant for commands that
+    /// touch a single action: anything that looks across actions (listing,
+    /// links, validation, new ids) only sees the ones loaded so far.
+    pub fn load_lazy<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::open(path, true)
+    }
+    
+    fn open<P: AsRef<Path>>(path: P, lazy: bool) -> Result<Self> {
         let root = path.as_ref().to_path_buf();
         
         // Check if this is a valid workspace
         let marker_file = root.join(".action-lite");
         if !marker_file.exists() {
-            anyhow::bail!(
-                "Not an Action Lite workspace. Run 'action init' to initialize a workspace in: {}", 
-                root.display()
-            );
+            return Err(WorkspaceError::NotAWorkspace(root).into());
         }
         
+        let config = Config::load(&root)?;
+        let workflow = config.workflow()?;
         let mut workspace = Self {
             root: root.clone(),
-            projects: HashMap::new(),
+            projects: BTreeMap::new(),
+            config,
+            workflow,
+            read_only: false,
+            load_errors: Vec::new(),
         };
         
-        workspace.load_projects()?;
+        workspace.load_projects(lazy)?;
         Ok(workspace)
     }
     
-    fn load_projects(&mut self) -> Result<()> {
-        for entry in fs::read_dir(&self.root)? {
-            let entry = entry?;
-            let path = entry.path();
-            
-            if path.is_dir() && !path.file_name().unwrap().to_str().unwrap().starts_with('.') {
-                let project_name = path.file_name()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("unknown")
-                    .to_string();
-                
-                match Project::load(&path) {
-                    Ok(project) => {
-                        self.projects.insert(project_name, project);
-                    }
-                    Err(e) => {
-                        eprintln!("Warning: Failed to load project from {}: {}", path.display(), e);
-                    }
-                }
-            }
-        }
-        
-        Ok(())
+    /// Open the workspace read-only: every method that would write to disk,
+    /// launch an editor or create directories fails with `WorkspaceError::ReadOnly`
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
     }
     
-    pub fn create_action(&mut self, project_name: &str, title: &str, priority: bool) -> Result<Action> {
-        // Get or create project
-        let project = if let Some(project) = self.projects.get_mut(project_name) {
-            project
-        } else {
-            let project_path = self.root.join(project_name);
-            let project = Project::new(project_name.to_string(), &project_path)?;
-            self.projects.insert(project_name.to_string(), project);
-            self.projects.get_mut(project_name).unwrap()
-        };
-        
-        project.create_action(title, priority)
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
     }
     
-    pub fn get_action(&self, project_name: &str, title: &str) -> Result<&Action> {
-        let project = self.projects.get(project_name)
-            .with_context(|| format!("Project '{}' not found", project_name))?;
-        
-        project.get_action(title)
-            .with_context(|| format!("Action '{}' not found in project '{}'", title, project_name))
+    /// Fail with `WorkspaceError::ReadOnly` if writes are not allowed
+    pub fn ensure_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(WorkspaceError::ReadOnly.into());
+        }
+        Ok(())
     }
     
-    pub fn update_action_status(&mut self, project_name: &str, title: &str, status: Status) -> Result<()> {
-        let project = self.projects.get_mut(project_name)
-            .with_context(|| format!("Project '{}' not found", project_name))?;
+    /// Take the workspace lock (`.action-lite/lock`) without waiting,
+    /// failing with `WorkspaceError::Locked` if another process holds it.
+    ///
+    /// Only `batch::apply` and `import` take it, so it keeps those two from
+    /// overlapping; single-action commands don't check it and can still write
+    /// while a batch runs.
+    pub fn lock(&self) -> Result<WorkspaceLock> {
+        self.ensure_writable()?;
+        let path = config::ensure_state_dir(&self.root)?.join(config::LOCK_FILE);
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open lock file: {}", path.display()))?;
         
-        project.update_action_status(title, status)
+        match file.try_lock() {
+            Ok(()) => Ok(WorkspaceLock { _file: file }),
+            Err(fs::TryLockError::WouldBlock) => Err(WorkspaceError::Locked.into()),
+            Err(fs::TryLockError::Error(e)) => {
+                Err(e).with_context(|| format!("Failed to lock {}", path.display()))
+            }
+        }
     }
     
-    pub fn set_action_priority(&mut self, project_name: &str, title: &str, priority: bool) -> Result<()> {
-        let project = self.projects.get_mut(project_name)
-            .with_context(|| format!("Project '{}' not found", project_name))?;
+    /// Re-read one project from disk, dropping it if its directory is gone
+    pub fn reload_project(&mut self, name: &str) -> Result<()> {
+        let path = self.root.join(name);
+        if !path.is_dir() {
+            self.projects.remove(name);
+            return Ok(());
+        }
         
-        project.set_action_priority(title, priority)
+        let guard = DiscoveryGuard::new(&self.root, self.config.follow_symlinks)?;
+        self.load_errors.retain(|(failed, _)| *failed != path);
+        let project = match Project::load_with(&path, Some(&guard)) {
+            Ok(project) => project,
+            Err(e) => {
+                self.load_errors.push((path, e.to_string()));
+                return Err(e);
+            }
+        };
+        let project = self.configure(project);
+        project.print_load_warnings();
+        self.projects.insert(name.to_string(), project);
+        Ok(())
     }
     
-    pub fn list_actions(
-        &self, 
-        project_filter: Option<&str>, 
-        status_filter: Option<&str>, 
-        priority_only: bool
-    ) -> Result<Vec<&Action>> {
-        let mut actions = Vec::new();
+    fn load_projects(&mut self, lazy: bool) -> Result<()> {
+        let guard = DiscoveryGuard::new(&self.root, self.config.follow_symlinks)?;
         
-        for (project_name, project) in &self.projects {
-            // Filter by project if specified
-            if let Some(filter) = project_filter {
-                if project_name != filter {
-                    continue;
-                }
+        // Visit real directories before symlinks so an alias never shadows its target
+        let mut candidates: Vec<PathBuf> = fs::read_dir(&self.root)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<_>>()?;
+        candidates.sort_by_key(|path| (is_symlink(path), path.clone()));
+        
+        let mut seen = HashSet::new();
+        let mut project_dirs = Vec::new();
+        for path in candidates {
+            if !path.is_dir() || path.file_name().unwrap().to_str().unwrap().starts_with('.') {
+                continue;
             }
             
-            for action in project.list_actions() {
-                // Filter by status if specified
-                if let Some(status_str) = status_filter {
-                    let status = Status::from_str(status_str)?;
-                    if action.status() != &status {
-                        continue;
-                    }
+            if !guard.allows(&path) {
+                if self.config.follow_symlinks {
+                    eprintln!("Warning: Skipping {}: it resolves outside the workspace", path.display());
                 }
-                
-                // Filter by priority if specified
-                if priority_only && !action.is_priority() {
+                continue;
+            }
+            
+            if let Ok(resolved) = path.canonicalize() {
+                if !seen.insert(resolved) {
+                    eprintln!("Warning: Skipping {}: it points at an already loaded project", path.display());
                     continue;
                 }
-                
-                actions.push(action);
             }
+            
+            project_dirs.push(path);
         }
         
-        // Sort by project, then by title
-        actions.sort_by(|a, b| {
-            a.project().cmp(b.project())
-                .then_with(|| a.title().cmp(b.title()))
-        });
+        // Read and parse projects in parallel, then report in directory order
+        let loaded: Vec<(PathBuf, Result<Project>)> = project_dirs
+            .into_par_iter()
+            .map(|path| {
+                let project = if lazy {
+                    let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("unknown").to_string();
+                    Project::new(name, &path)
+                } else {
+                    Project::load_with(&path, Some(&guard))
+                };
+                (path, project)
+            })
+            .collect();
+        for (path, project) in loaded {
+            match project {
+                Ok(project) => {
+                    let project = self.configure(project);
+                    project.print_load_warnings();
+                    self.projects.insert(project.name.clone(), project);
+                }
+                Err(e) => {
+                    eprintln!("Warning: Failed to load project from {}: {}", path.display(), e);
+                    self.load_errors.push((path, e.to_string()));
+                }
+            }
+        }
         
-        Ok(actions)
+        Ok(())
     }
     
-    pub fn edit_action(&self, project_name: &str, title: &str) -> Result<()> {
-        let action = self.get_action(project_name, title)?;
-        let file_path = &action.file_path;
-        
-        // Try to find an editor
-        let editor = std::env::var("EDITOR")
-            .or_else(|_| std::env::var("VISUAL"))
-            .unwrap_or_else(|_| {
-                if cfg!(target_os = "windows") {
-                    "notepad".to_string()
-                } else {
-                    "nano".to_string()
+    pub fn create_action(&mut self, project_name: &str, title: &str, priority: Priority) -> Result<Action> {
+        let options = CreateOptions { priority, ..Default::default() };
+        self.create_action_with(project_name, title, &options)
+    }
+    
+    pub fn create_action_with(&mut self, project_name: &str, title: &str, options: &CreateOptions) -> Result<Action> {
+        self.ensure_writable()?;
+        // Before `project_or_create`, so a bad name leaves no directory behind
+        utils::validate_project_name(project_name)?;
+        utils::validate_action_title(title)?;
+        let type_name = options.action_type.as_deref().unwrap_or(DEFAULT_ACTION_TYPE);
+        let mut action_type = self.config.action_type(type_name)?;
+        if options.blank {
+            action_type.sections.clear();
+        } else {
+            for (section, content) in &self.config.default_sections {
+                if !action_type.sections.iter().any(|name| name.eq_ignore_ascii_case(section)) {
+                    action_type.sections.push(section.clone());
                 }
-            });
+                action_type.placeholders.retain(|name, _| !name.eq_ignore_ascii_case(section));
+                action_type.placeholders.insert(section.clone(), content.clone());
+            }
+            
+            // A project's own template replaces both the type's sections and the defaults
+            let project_template = match self.projects.get(project_name) {
+                Some(project) => project.action_template()?,
+                None => None,
+            };
+            if let Some(content) = project_template {
+                let from_template = template::ActionType::from_template(&content);
+                action_type.sections = from_template.sections;
+                action_type.placeholders = from_template.placeholders;
+            }
+        }
+        for (section, content) in &options.sections {
+            if !Action::is_known_section(section) {
+                anyhow::bail!("Unknown section: {}. Valid sections are: {}", section, SECTION_NAMES.join(", "));
+            }
+            if !action_type.sections.iter().any(|name| name.eq_ignore_ascii_case(section)) {
+                action_type.sections.push(section.clone());
+            }
+            action_type.placeholders.retain(|name, _| !name.eq_ignore_ascii_case(section));
+            action_type.placeholders.insert(section.clone(), content.clone());
+        }
         
-        let status = Command::new(&editor)
-            .arg(file_path)
-            .status()
-            .with_context(|| format!("Failed to open editor: {}", editor))?;
+        let action = self.project_or_create(project_name)?
+            .create_typed_action(title, options.priority, type_name, &action_type)?;
         
-        if !status.success() {
-            anyhow::bail!("Editor exited with non-zero status");
+        // The action stays unless asked otherwise: the hook is an integration
+        // point, and its failure shouldn't lose what the user just created
+        if let Some(command) = &self.config.hooks.on_create {
+            if let Err(e) = hooks::run(command, &action, &self.root) {
+                if options.rollback_on_hook_failure {
+                    if let Some(project) = self.projects.get_mut(project_name) {
+                        project.delete_action(title)?;
+                    }
+                    return Err(e.context(format!("on_create hook failed; removed {}/{}", project_name, title)));
+                }
+                eprintln!("Warning: on_create hook failed for {}/{}: {:#}", project_name, title, e);
+            }
         }
         
-        Ok(())
+        self.auto_commit(&[&action.file_path], &format!("create: {}/{}", project_name, title));
+        Ok(action)
     }
     
-    pub fn create_meta_graph(&self, project_name: &str, title: &str) -> Result<PathBuf> {
-        let project = self.projects.get(project_name)
-            .with_context(|| format!("Project '{}' not found", project_name))?;
-        
-        project.create_meta_graph(title)
+    /// The project called `name`, creating its directory if it doesn't exist yet
+    fn project_or_create(&mut self, name: &str) -> Result<&mut Project> {
+        if !self.projects.contains_key(name) {
+            let project = self.configure(Project::new(name.to_string(), self.root.join(name))?);
+            self.projects.insert(name.to_string(), project);
+        }
+        Ok(self.projects.get_mut(name).unwrap())
     }
     
-    pub fn validate(&self) -> Result<()> {
-        // Check workspace marker
-        let marker_file = self.root.join(".action-lite");
-        if !marker_file.exists() {
-            anyhow::bail!("Missing .action-lite marker file");
+    /// Apply the workspace's settings to a project it loaded or created.
+    /// An invalid `default_status` is ignored here; `check` reports it.
+    fn configure(&self, project: Project) -> Project {
+        let initial_status = self.config.default_status
+            .as_deref()
+            .and_then(|status| self.workflow.parse(status).ok());
+        project
+            .with_meta_graph_layout(self.config.meta_graph_layout)
+            .with_id_scheme(self.config.id_scheme)
+            .with_workflow(&self.workflow)
+            .with_initial_status(initial_status)
+    }
+    
+    /// Move an action, and its meta-graph, into another project, creating
+    /// that project if needed. The file keeps its name. Links inside the
+    /// meta-graph are fixed up; relative links in the action itself are not.
+    pub fn move_action(&mut self, project_name: &str, title: &str, dest_name: &str) -> Result<()> {
+        self.ensure_writable()?;
+        utils::validate_project_name(dest_name)?;
+        // Settle on the stored title before touching any files
+        let action = self.get_action(project_name, title)?;
+        let title = action.title();
+        if project_name == dest_name {
+            anyhow::bail!("Action '{}' is already in project '{}'", title, dest_name);
+        }
+        if self.projects.get(dest_name).is_some_and(|project| project.get_action(title).is_some()) {
+            anyhow::bail!("Action '{}' already exists in project '{}'", title, dest_name);
         }
         
-        // Validate each project
-        for (project_name, project) in &self.projects {
-            if !project.path().exists() {
-                anyhow::bail!("Project directory does not exist: {}", project.path().display());
-            }
-            
-            // Validate actions in project
-            for action in project.list_actions() {
-                if !action.file_path.exists() {
-                    anyhow::bail!("Action file does not exist: {}", action.file_path.display());
-                }
-                
-                // Validate required outputs for current status
-                let required_outputs = action.status().required_outputs();
-                for output in required_outputs {
-                    match output {
-                        "Notes" if action.notes().is_none() => {
-                            anyhow::bail!("Action {}/{} missing required Notes section", project_name, action.title());
-                        }
-                        "Statement of Action" if action.statement_of_action().is_none() => {
-                            anyhow::bail!("Action {}/{} missing required Statement of Action section", project_name, action.title());
-                        }
-                        "Statement of Inputs" if action.statement_of_inputs().is_none() => {
-                            anyhow::bail!("Action {}/{} missing required Statement of Inputs section", project_name, action.title());
-                        }
-                        "Statement of Design" if action.statement_of_design().is_none() => {
-                            anyhow::bail!("Action {}/{} missing required Statement of Design section", project_name, action.title());
-                        }
-                        "Analysis of Impact" if action.analysis_of_impact().is_none() => {
-                            anyhow::bail!("Action {}/{} missing required Analysis of Impact section", project_name, action.title());
-                        }
-                        _ => {}
-                    }
-                }
+        let old_path = action.file_path.clone();
+        let file_name = old_path.file_name().context("Action file has no name")?;
+        let new_path = self.root.join(dest_name).join(file_name);
+        let old_meta_graph = action.meta_graph_path();
+        let new_meta_graph = self.config.meta_graph_layout.path_for(&new_path);
+        let has_meta_graph = action.has_meta_graph();
+        
+        // Check every destination before touching anything
+        if new_path.exists() {
+            anyhow::bail!("Cannot move to '{}': {} already exists", dest_name, new_path.display());
+        }
+        if has_meta_graph && new_meta_graph.exists() {
+            anyhow::bail!("Cannot move to '{}': {} already exists", dest_name, new_meta_graph.display());
+        }
+        
+        let title = title.to_string();
+        self.project_or_create(dest_name)?;
+        fs::rename(&old_path, &new_path)
+            .with_context(|| format!("Failed to move {} to {}", old_path.display(), new_path.display()))?;
+        if has_meta_graph {
+            if let Some(parent) = new_meta_graph.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
             }
+            fs::rename(&old_meta_graph, &new_meta_graph).with_context(|| {
+                format!("Failed to move meta-graph {} to {}", old_meta_graph.display(), new_meta_graph.display())
+            })?;
+            project::relink_meta_graph(&old_meta_graph, &new_meta_graph, &old_path, &new_path)?;
         }
         
+        let mut action = self.projects.get_mut(project_name)
+            .and_then(|project| project.take_action(&title))
+            .with_context(|| format!("Action '{}' disappeared from project '{}' during the move", title, project_name))?;
+        action.project = dest_name.to_string();
+        action.file_path = new_path;
+        action.save()?;
+        self.projects.get_mut(dest_name).unwrap().insert_action(action);
         Ok(())
     }
     
-    pub fn projects(&self) -> &HashMap<String, Project> {
-        &self.projects
+    /// Like `get_action`, but read the action from disk if it isn't loaded
+    /// yet (see `load_lazy`)
+    pub fn load_action(&mut self, project_name: &str, title: &str) -> Result<&Action> {
+        let guard = DiscoveryGuard::new(&self.root, self.config.follow_symlinks)?;
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        if project.load_action(title, Some(&guard))?.is_none() {
+            // Titles that differ in case, or similar ones to suggest, are only
+            // found by reading the whole project
+            self.reload_project(project_name)?;
+        }
+        self.get_action(project_name, title)
     }
     
-    pub fn get_project(&self, name: &str) -> Option<&Project> {
-        self.projects.get(name)
+    pub fn get_action(&self, project_name: &str, title: &str) -> Result<&Action> {
+        let project = self.projects.get(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.get_action(title).ok_or_else(|| project.action_not_found(title))
     }
     
-    pub fn root(&self) -> &Path {
-        &self.root
+    /// Move an action to `status`. Moving forward checks the entry gates of
+    /// the new status (see `missing_inputs`).
+    pub fn update_action_status(&mut self, project_name: &str, title: &str, status: Status) -> Result<()> {
+        self.update_action_status_with(project_name, title, status, false).map(|_| ())
     }
-}
\ No newline at end of file
+    
+    /// Like `update_action_status`. With `scaffold`, required outputs of the
+    /// new status that the action lacks are added with their placeholder text,
+    /// as a prompt to fill them in. Returns the sections that were added.
+    pub fn update_action_status_with(
+        &mut self,
+        project_name: &str,
+        title: &str,
+        status: Status,
+        scaffold: bool,
+    ) -> Result<Vec<String>> {
+        self.change_status(project_name, title, status, scaffold, true)
+    }
+    
+    fn change_status(
+        &mut self,
+        project_name: &str,
+        title: &str,
+        status: Status,
+        scaffold: bool,
+        check_gates: bool,
+    ) -> Result<Vec<String>> {
+        self.ensure_writable()?;
+        let action = self.get_action(project_name, title)?;
+        let title = action.title().to_string();
+        let old = action.status().to_string();
+        if check_gates && self.workflow.compare(&status, action.status()).is_gt() {
+            let missing = self.missing_inputs(action, &status);
+            if !missing.is_empty() {
+                anyhow::bail!(
+                    "Cannot move {}/{} to {}: {} must be filled in first",
+                    project_name, title, status, missing.join(", ")
+                );
+            }
+        }
+        
+        let scaffolds: Vec<(String, String)> = if scaffold {
+            let action_type = self.config.action_type(action.action_type()).ok();
+            self.workflow.required_outputs(&status)
+                .into_iter()
+                .filter(|section| Action::is_known_section(section) && action.section(section).is_none())
+                .map(|section| {
+                    let content = match &action_type {
+                        Some(action_type) => action_type.placeholder(&section),
+                        None => template::default_placeholder(&section).to_string(),
+                    };
+                    (section, content)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        let new = status.to_string();
+        project.update_action_status_with(&title, status, &scaffolds)?;
+        self.record_change(project_name, &title, Field::Status, old, new, false)?;
+        Ok(scaffolds.into_iter().map(|(section, _)| section).collect())
+    }
+    
+    /// Like `update_action_status_with`, but unless `force` is set the move
+    /// must follow the workflow: the new status has to be next to the current
+    /// one, and moving forward needs the required outputs of the status being
+    /// left, as `advance_project` does. `force` also skips the entry gates.
+    pub fn update_action_status_checked(
+        &mut self,
+        project_name: &str,
+        title: &str,
+        status: Status,
+        scaffold: bool,
+        force: bool,
+    ) -> Result<Vec<String>> {
+        if !force {
+            let action = self.get_action(project_name, title)?;
+            let skipped = self.workflow.between(action.status(), &status);
+            if !skipped.is_empty() {
+                anyhow::bail!(
+                    "Cannot move {}/{} from {} to {}: that skips {}; pass --force to move anyway",
+                    project_name,
+                    action.title(),
+                    action.status(),
+                    status,
+                    skipped.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ")
+                );
+            }
+            
+            let missing = self.missing_outputs(action, action.status());
+            if self.workflow.compare(&status, action.status()).is_gt() && !missing.is_empty() {
+                anyhow::bail!(
+                    "Cannot move {}/{} from {} to {}: {} is missing; pass --force to move anyway",
+                    project_name,
+                    action.title(),
+                    action.status(),
+                    status,
+                    missing.join(", ")
+                );
+            }
+        }
+        
+        self.change_status(project_name, title, status, scaffold, !force)
+    }
+    
+    /// Move an action one status forward along the workflow. Returns the new
+    /// status, or `None` if the action is already at the final status.
+    pub fn next_action_status(&mut self, project_name: &str, title: &str) -> Result<Option<Status>> {
+        self.step_action_status(project_name, title, true)
+    }
+    
+    /// Move an action one status back. Returns the new status, or `None` if
+    /// the action is already at the first status.
+    pub fn previous_action_status(&mut self, project_name: &str, title: &str) -> Result<Option<Status>> {
+        self.step_action_status(project_name, title, false)
+    }
+    
+    fn step_action_status(&mut self, project_name: &str, title: &str, forward: bool) -> Result<Option<Status>> {
+        let status = self.get_action(project_name, title)?.status();
+        if self.workflow.position(status).is_none() {
+            anyhow::bail!("Status '{}' of {}/{} is not part of the workflow; set one with 'action status'", status, project_name, title);
+        }
+        
+        let target = if forward { self.workflow.next(status) } else { self.workflow.previous(status) };
+        let Some(target) = target else {
+            return Ok(None);
+        };
+        self.update_action_status(project_name, title, target.clone())?;
+        Ok(Some(target))
+    }
+    
+    pub fn set_action_lifecycle(&mut self, project_name: &str, title: &str, lifecycle: Lifecycle) -> Result<()> {
+        self.ensure_writable()?;
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.set_action_lifecycle(title, lifecycle)
+    }
+    
+    pub fn set_action_priority(&mut self, project_name: &str, title: &str, priority: Priority) -> Result<()> {
+        self.ensure_writable()?;
+        let action = self.get_action(project_name, title)?;
+        // Log the stored title, which may differ in case from the one given
+        let (title, old) = (action.title().to_string(), action.priority().to_string());
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.set_action_priority(&title, priority)?;
+        self.record_change(project_name, &title, Field::Priority, old, priority.to_string(), false)
+    }
+    
+    /// Append a status or priority change to the history log, and commit it
+    /// when `auto_commit` is on; no-ops aren't recorded
+    fn record_change(&self, project_name: &str, title: &str, field: Field, old: String, new: String, undo: bool) -> Result<()> {
+        if old == new {
+            return Ok(());
+        }
+        let change = Change {
+            project: project_name.to_string(),
+            title: title.to_string(),
+            field,
+            old,
+            new,
+            timestamp: Utc::now(),
+            undo,
+        };
+        history::record(&self.root, &change)?;
+        
+        let message = if undo {
+            format!("undo: {}/{} {} -> {}", project_name, title, field, change.new)
+        } else {
+            format!("{}: {}/{} -> {}", field, project_name, title, change.new)
+        };
+        let action = self.get_action(project_name, title)?;
+        self.auto_commit(&[&action.file_path], &message);
+        Ok(())
+    }
+    
+    /// Commit `paths` with `message` when `auto_commit` is on. The change
+    /// is already saved by then, so a failed commit only warns.
+    fn auto_commit(&self, paths: &[&Path], message: &str) {
+        if !self.config.auto_commit {
+            return;
+        }
+        if let Err(e) = git::commit(&self.root, paths, message) {
+            eprintln!("Warning: auto-commit failed: {:#}", e);
+        }
+    }
+    
+    /// Revert the last recorded status or priority change, skipping entry
+    /// gates, and log the revert. Returns the change that was undone, or
+    /// `None` when nothing has been recorded. Only one level is kept: a
+    /// revert can't itself be undone, and the action must still hold the
+    /// value the change set.
+    pub fn undo_last_change(&mut self) -> Result<Option<Change>> {
+        self.ensure_writable()?;
+        let Some(change) = history::load(&self.root)?.pop() else {
+            return Ok(None);
+        };
+        if change.undo {
+            anyhow::bail!("The last change ({}) was already an undo", change);
+        }
+        
+        let action = self.get_action(&change.project, &change.title)?;
+        let current = match change.field {
+            Field::Status => action.status().to_string(),
+            Field::Priority => action.priority().to_string(),
+        };
+        if current != change.new {
+            anyhow::bail!(
+                "Cannot undo '{}': {}/{} has {} '{}' now",
+                change, change.project, change.title, change.field, current
+            );
+        }
+        
+        let project = self.projects.get_mut(&change.project)
+            .with_context(|| format!("Project '{}' not found", change.project))?;
+        match change.field {
+            Field::Status => project.update_action_status_with(&change.title, Status::named(&change.old), &[])?,
+            Field::Priority => project.set_action_priority(&change.title, Priority::from_str(&change.old)?)?,
+        }
+        self.record_change(&change.project, &change.title, change.field, change.new.clone(), change.old.clone(), true)?;
+        Ok(Some(change))
+    }
+    
+    /// Search every action's title and sections, case-insensitively. `query`
+    /// is plain text unless `regex` is set. Actions come in listing order.
+    pub fn search(&self, query: &str, regex: bool) -> Result<Vec<grep::SearchHit<'_>>> {
+        let source = if regex { query.to_string() } else { regex::escape(query) };
+        let pattern = regex::RegexBuilder::new(&source)
+            .case_insensitive(true)
+            .build()
+            .with_context(|| format!("Invalid pattern: {}", query))?;
+        
+        let actions = self.projects.values().flat_map(|project| project.list_actions());
+        Ok(grep::search(actions, &pattern))
+    }
+    
+    /// Set or clear an action's due date. Returns a warning if the date has
+    /// already passed for an action that hasn't started.
+    pub fn set_action_due(&mut self, project_name: &str, title: &str, due: Option<DateTime<Utc>>) -> Result<Option<String>> {
+        self.ensure_writable()?;
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.set_action_due(title, due)
+    }
+    
+    /// Assign an action to someone, or unassign it with `None`
+    pub fn set_action_assignee(&mut self, project_name: &str, title: &str, assignee: Option<&str>) -> Result<()> {
+        self.ensure_writable()?;
+        let assignee = assignee.map(str::trim);
+        if let Some(assignee) = assignee {
+            if assignee.is_empty() || assignee.contains(['\n', '\r']) {
+                anyhow::bail!("Invalid assignee: '{}'", assignee);
+            }
+            if assignee.eq_ignore_ascii_case(UNASSIGNED) {
+                anyhow::bail!("'{}' is reserved for filtering; use unassign instead", UNASSIGNED);
+            }
+        }
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.set_action_assignee(title, assignee)
+    }
+    
+    /// Set or clear how long an action is expected to take
+    pub fn set_action_estimate(&mut self, project_name: &str, title: &str, estimate: Option<Duration>) -> Result<()> {
+        self.ensure_writable()?;
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.set_action_estimate(title, estimate)
+    }
+    
+    /// Add time spent on an action; returns the new total
+    pub fn log_action_time(&mut self, project_name: &str, title: &str, duration: Duration) -> Result<Duration> {
+        self.ensure_writable()?;
+        if duration <= Duration::zero() {
+            anyhow::bail!("Logged time must be more than zero");
+        }
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.log_action_time(title, duration)
+    }
+    
+    /// Delete an action's file and its meta-graph directory
+    pub fn delete_action(&mut self, project_name: &str, title: &str) -> Result<Action> {
+        self.ensure_writable()?;
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.delete_action(title)
+    }
+    
+    pub fn rename_action(&mut self, project_name: &str, title: &str, new_title: &str) -> Result<()> {
+        self.ensure_writable()?;
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.rename_action(title, new_title)
+    }
+    
+    /// Replace a section's content, or remove the section with `None`
+    pub fn set_action_section(&mut self, project_name: &str, title: &str, section: &str, content: Option<String>) -> Result<()> {
+        self.ensure_writable()?;
+        if !SECTION_NAMES.iter().any(|name| name.eq_ignore_ascii_case(section)) {
+            anyhow::bail!("Unknown section: {}. Valid sections are: {}", section, SECTION_NAMES.join(", "));
+        }
+        let action = self.projects.get_mut(project_name)
+            .and_then(|project| project.get_action_mut(title))
+            .with_context(|| format!("Action '{}' not found in project '{}'", title, project_name))?;
+        
+        action.update_section(section, content);
+        action.save()
+    }
+    
+    pub fn comment_on_action(&mut self, project_name: &str, title: &str, author: &str, text: &str) -> Result<()> {
+        self.ensure_writable()?;
+        let project = self.projects.get_mut(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.comment_on_action(title, author, text)
+    }
+    
+    pub fn list_actions(
+        &self, 
+        project_filter: Option<&str>, 
+        status_filter: Option<&str>, 
+        priority_only: bool
+    ) -> Result<Vec<&Action>> {
+        let filter = ActionFilter {
+            project: project_filter.map(|p| p.to_string()),
+            status: status_filter.map(|status| self.parse_status(status)).transpose()?,
+            priority_only,
+            ..Default::default()
+        };
+        
+        self.filter_actions(&filter)
+    }
+    
+    pub fn filter_actions(&self, filter: &ActionFilter) -> Result<Vec<&Action>> {
+        filter.validate(&self.workflow)?;
+        
+        // Projects are stored in a sorted map and list their actions by
+        // priority, then title, so this is already in that order
+        let actions = self.projects
+            .values()
+            .flat_map(|project| project.list_actions())
+            .filter(|action| filter.matches(action, &self.workflow))
+            .collect();
+        
+        Ok(actions)
+    }
+    
+    /// Sort `actions` by `key`, or against it with `reverse`. The sort is
+    /// stable, so ties keep their order; for `filter_actions` results that
+    /// is project, then priority, then title.
+    pub fn sort_actions(&self, actions: &mut [&Action], key: SortKey, reverse: bool) {
+        actions.sort_by(|a, b| {
+            let ordering = match key {
+                SortKey::Created => a.created_at.cmp(&b.created_at),
+                SortKey::Updated => a.updated_at.cmp(&b.updated_at),
+                SortKey::Status => self.workflow.compare(a.status(), b.status()),
+                SortKey::Priority => b.priority().cmp(&a.priority()),
+                SortKey::Title => a.title().to_lowercase().cmp(&b.title().to_lowercase()),
+            };
+            if reverse { ordering.reverse() } else { ordering }
+        });
+    }
+    
+    /// Serialize the projects and actions selected by `filter` for backup or
+    /// processing elsewhere. Every project the filter allows is included, even
+    /// with no matching actions. NDJSON has one action per line and no projects.
+    pub fn export(&self, format: ExportFormat, filter: &ActionFilter) -> Result<String> {
+        let actions = self.filter_actions(filter)?;
+        
+        match format {
+            ExportFormat::Json => {
+                let mut export = WorkspaceExport::default();
+                for (name, project) in &self.projects {
+                    if filter.project.as_ref().is_some_and(|wanted| wanted != name) {
+                        continue;
+                    }
+                    export.projects.insert(name.clone(), ProjectExport {
+                        description: project.description(),
+                        actions: actions
+                            .iter()
+                            .filter(|action| action.project() == name)
+                            .map(|action| ExportedAction::from(*action))
+                            .collect(),
+                    });
+                }
+                Ok(format!("{}\n", serde_json::to_string_pretty(&export)?))
+            }
+            ExportFormat::Ndjson => {
+                let mut output = String::new();
+                for action in actions {
+                    output.push_str(&serde_json::to_string(&ExportedAction::from(action))?);
+                    output.push('\n');
+                }
+                Ok(output)
+            }
+        }
+    }
+    
+    /// Recreate the projects and action files in `data`, as read by
+    /// `export::parse_import`. Every action is checked first, so nothing is
+    /// written if any has a status outside the workflow, appears twice, or
+    /// already exists without `overwrite`. New projects get a README when the
+    /// export carried a description.
+    pub fn import(&mut self, data: ImportData, overwrite: bool) -> Result<ImportSummary> {
+        self.ensure_writable()?;
+        let _lock = self.lock()?;
+        
+        let mut seen = HashSet::new();
+        for action in &data.actions {
+            let name = format!("{}/{}", action.project, action.title);
+            utils::validate_project_name(&action.project).with_context(|| format!("Cannot import {}", name))?;
+            utils::validate_action_title(&action.title).with_context(|| format!("Cannot import {}", name))?;
+            self.workflow.parse(&action.status.to_string()).with_context(|| format!("Cannot import {}", name))?;
+            if !seen.insert(name.clone()) {
+                anyhow::bail!("Action {} appears more than once in the import", name);
+            }
+            if !overwrite && self.get_action(&action.project, &action.title).is_ok() {
+                anyhow::bail!("Action {} already exists. Use --overwrite to replace it", name);
+            }
+        }
+        
+        let mut summary = ImportSummary::default();
+        for (name, description) in &data.projects {
+            if self.projects.contains_key(name) {
+                continue;
+            }
+            utils::validate_project_name(name)?;
+            let project = self.project_or_create(name)?;
+            if let Some(description) = description {
+                let readme = project.readme_path();
+                fs::write(&readme, format!("# {}\n\n{}\n", name, description))
+                    .with_context(|| format!("Failed to write {}", readme.display()))?;
+            }
+            summary.projects_created += 1;
+        }
+        for action in data.actions {
+            let project = self.project_or_create(&action.project.clone())?;
+            if project.import_action(action, overwrite)? {
+                summary.actions_overwritten += 1;
+            } else {
+                summary.actions_created += 1;
+            }
+        }
+        Ok(summary)
+    }
+    
+    /// Group the filtered actions by the given dimension.
+    ///
+    /// Status groups follow workflow order, project groups are sorted by name
+    /// and priority groups run from high to none. Empty groups are omitted.
+    pub fn group_actions_by(&self, key: GroupKey, filter: &ActionFilter) -> Result<Vec<(String, Vec<&Action>)>> {
+        let actions = self.filter_actions(filter)?;
+        let mut groups: Vec<(String, Vec<&Action>)> = Vec::new();
+        
+        match key {
+            GroupKey::Status => {
+                for status in self.workflow.statuses() {
+                    let members: Vec<&Action> = actions.iter()
+                        .copied()
+                        .filter(|action| action.status() == &status)
+                        .collect();
+                    groups.push((status.to_string(), members));
+                }
+            }
+            GroupKey::Project => {
+                for action in actions {
+                    match groups.iter_mut().find(|(name, _)| name == action.project()) {
+                        Some((_, members)) => members.push(action),
+                        None => groups.push((action.project().to_string(), vec![action])),
+                    }
+                }
+            }
+            GroupKey::Priority => {
+                for priority in Priority::all().into_iter().rev() {
+                    let members: Vec<&Action> = actions.iter()
+                        .copied()
+                        .filter(|action| action.priority() == priority)
+                        .collect();
+                    groups.push((priority.to_string(), members));
+                }
+            }
+            GroupKey::Assignee => {
+                let mut unassigned = Vec::new();
+                for action in actions {
+                    let Some(assignee) = action.assignee.as_deref() else {
+                        unassigned.push(action);
+                        continue;
+                    };
+                    match groups.iter_mut().find(|(name, _)| name.eq_ignore_ascii_case(assignee)) {
+                        Some((_, members)) => members.push(action),
+                        None => groups.push((assignee.to_string(), vec![action])),
+                    }
+                }
+                groups.push((UNASSIGNED.to_string(), unassigned));
+            }
+        }
+        
+        groups.retain(|(_, members)| !members.is_empty());
+        Ok(groups)
+    }
+    
+    pub fn edit_action(&self, project_name: &str, title: &str) -> Result<()> {
+        self.ensure_writable()?;
+        let action = self.get_action(project_name, title)?;
+        let file_path = &action.file_path;
+        
+        let editor = self.editor();
+        
+        let status = Command::new(&editor)
+            .arg(file_path)
+            .status()
+            .with_context(|| format!("Failed to open editor: {}", editor))?;
+        
+        if !status.success() {
+            anyhow::bail!("Editor exited with non-zero status");
+        }
+        
+        Ok(())
+    }
+    
+    pub fn create_meta_graph(&self, project_name: &str, title: &str) -> Result<PathBuf> {
+        self.ensure_writable()?;
+        let project = self.projects.get(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.create_meta_graph(title)
+    }
+    
+    pub fn attach(&self, project_name: &str, title: &str, source: &Path) -> Result<PathBuf> {
+        self.ensure_writable()?;
+        let project = self.projects.get(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.attach(title, source)
+    }
+    
+    pub fn detach(&self, project_name: &str, title: &str, name: &str) -> Result<PathBuf> {
+        self.ensure_writable()?;
+        let project = self.projects.get(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        project.detach(title, name)
+    }
+    
+    /// Resolve a link target written in `from` to the action it points at.
+    ///
+    /// Targets are relative to the linking action's directory, except those
+    /// starting with `/`, which are relative to the workspace root. URLs never
+    /// resolve.
+    pub fn resolve_link(&self, from: &Action, raw: &str) -> Option<&Action> {
+        if raw.contains("://") {
+            return None;
+        }
+        
+        let target = match raw.strip_prefix('/') {
+            Some(from_root) => self.root.join(from_root),
+            None => from.file_path.parent().unwrap_or(Path::new("")).join(raw),
+        };
+        self.find_action_by_path(&target)
+    }
+    
+    /// The action stored at `path`, compared after lexical normalization
+    pub fn find_action_by_path(&self, path: &Path) -> Option<&Action> {
+        let target = utils::normalize_path(path);
+        self.projects
+            .values()
+            .flat_map(|project| project.list_actions())
+            .find(|candidate| utils::normalize_path(&candidate.file_path) == target)
+    }
+    
+    /// `action`'s links with the targets that point at actions filled in
+    pub fn resolved_links(&self, action: &Action) -> Vec<ActionLink> {
+        action.links()
+            .into_iter()
+            .map(|mut link| {
+                link.resolved = self.resolve_link(action, &link.target).map(|target| LinkTarget {
+                    project: target.project().to_string(),
+                    title: target.title().to_string(),
+                });
+                link
+            })
+            .collect()
+    }
+    
+    /// Actions linked from `action`'s `Children` section, and targets that didn't resolve
+    pub fn children_of(&self, action: &Action) -> (Vec<&Action>, Vec<String>) {
+        let mut children = Vec::new();
+        let mut unresolved = Vec::new();
+        
+        for link in action.child_links() {
+            match self.resolve_link(action, &link.target) {
+                Some(child) => children.push(child),
+                None => unresolved.push(link.target),
+            }
+        }
+        
+        (children, unresolved)
+    }
+    
+    /// Compute a parent action's progress from its children.
+    ///
+    /// The rolled-up status is the least advanced child's status, so the
+    /// parent only counts as published once every child is. Fails if the
+    /// parent/child hierarchy below the action contains a cycle.
+    pub fn rollup(&self, project_name: &str, title: &str) -> Result<Rollup> {
+        let action = self.get_action(project_name, title)?;
+        self.check_hierarchy(action, &mut Vec::new())?;
+        
+        let (children, unresolved) = self.children_of(action);
+        let status = children
+            .iter()
+            .map(|child| child.status().clone())
+            .min_by(|a, b| self.workflow.compare(a, b));
+        
+        Ok(Rollup {
+            children: children
+                .iter()
+                .map(|child| (child.project().to_string(), child.title().to_string(), child.status().clone()))
+                .collect(),
+            unresolved,
+            status,
+            final_status: self.workflow.last().clone(),
+        })
+    }
+    
+    fn check_hierarchy<'a>(&'a self, action: &'a Action, ancestors: &mut Vec<&'a Action>) -> Result<()> {
+        if let Some(start) = ancestors.iter().position(|a| a.file_path == action.file_path) {
+            let cycle: Vec<String> = ancestors[start..]
+                .iter()
+                .chain(std::iter::once(&action))
+                .map(|a| format!("{}/{}", a.project(), a.title()))
+                .collect();
+            anyhow::bail!("Cycle in children: {}", cycle.join(" -> "));
+        }
+        
+        ancestors.push(action);
+        for child in self.children_of(action).0 {
+            self.check_hierarchy(child, ancestors)?;
+        }
+        ancestors.pop();
+        Ok(())
+    }
+    
+    /// Actions linked from `action`'s markdown that resolve to other actions in the workspace
+    pub fn linked_actions(&self, action: &Action) -> Vec<&Action> {
+        action.links()
+            .iter()
+            .filter_map(|link| self.resolve_link(action, &link.target))
+            .collect()
+    }
+    
+    /// Actions linked from `action`'s Statement of Inputs, in the order they're listed
+    pub fn dependencies(&self, action: &Action) -> Vec<&Action> {
+        let Some(inputs) = action.statement_of_inputs() else {
+            return Vec::new();
+        };
+        
+        let mut dependencies: Vec<&Action> = Vec::new();
+        for (_, target) in MarkdownParser::new(inputs).extract_labeled_links() {
+            if let Some(dependency) = self.resolve_link(action, &target) {
+                if !dependencies.iter().any(|a| a.file_path == dependency.file_path) {
+                    dependencies.push(dependency);
+                }
+            }
+        }
+        dependencies
+    }
+    
+    /// Actions named in `action`'s `depends_on`, and references that don't
+    /// match an action. A reference without a `/` is a title in the same project.
+    pub fn declared_dependencies(&self, action: &Action) -> (Vec<&Action>, Vec<String>) {
+        let mut resolved = Vec::new();
+        let mut dangling = Vec::new();
+        
+        for reference in &action.depends_on {
+            let (project_name, title) = reference.split_once('/').unwrap_or((action.project(), reference));
+            match self.get_action(project_name, title) {
+                Ok(dependency) => resolved.push(dependency),
+                Err(_) => dangling.push(reference.clone()),
+            }
+        }
+        
+        (resolved, dangling)
+    }
+    
+    /// Actions that can't proceed because something in their `depends_on`
+    /// hasn't reached the workflow's final status, each with those
+    /// dependencies. Cancelled actions and dangling references are skipped.
+    pub fn blocked_actions(&self) -> Vec<(&Action, Vec<&Action>)> {
+        self.projects
+            .values()
+            .flat_map(|project| project.list_actions())
+            .filter(|action| action.lifecycle() != Lifecycle::Cancelled)
+            .filter_map(|action| {
+                let (dependencies, _) = self.declared_dependencies(action);
+                let pending: Vec<&Action> = dependencies
+                    .into_iter()
+                    .filter(|dependency| dependency.status() != self.workflow.last())
+                    .collect();
+                (!pending.is_empty()).then_some((action, pending))
+            })
+            .collect()
+    }
+    
+    /// Record that `project_name/title` depends on `target` (`project/title`,
+    /// or a title in the same project). The target must exist. Returns false
+    /// if the dependency was already recorded.
+    pub fn add_dependency(&mut self, project_name: &str, title: &str, target: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        let (target_project, target_title) = target.split_once('/').unwrap_or((project_name, target));
+        self.get_action(target_project, target_title)
+            .with_context(|| format!("Dependency target '{}' doesn't exist", target))?;
+        if (target_project, target_title) == (project_name, title) {
+            anyhow::bail!("{}/{} can't depend on itself", project_name, title);
+        }
+        
+        let reference = format!("{}/{}", target_project, target_title);
+        let action = self.projects.get_mut(project_name)
+            .and_then(|project| project.get_action_mut(title))
+            .with_context(|| format!("Action '{}' not found in project '{}'", title, project_name))?;
+        if !action.add_dependency(&reference) {
+            return Ok(false);
+        }
+        action.save()?;
+        Ok(true)
+    }
+    
+    /// Drop a dependency recorded with `add_dependency`, dangling or not.
+    /// Returns false if there was none on `target`.
+    pub fn remove_dependency(&mut self, project_name: &str, title: &str, target: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        let action = self.projects.get_mut(project_name)
+            .and_then(|project| project.get_action_mut(title))
+            .with_context(|| format!("Action '{}' not found in project '{}'", title, project_name))?;
+        
+        let qualified = match target.split_once('/') {
+            Some(_) => target.to_string(),
+            None => format!("{}/{}", project_name, target),
+        };
+        if !action.remove_dependency(&qualified) && !action.remove_dependency(target) {
+            return Ok(false);
+        }
+        action.save()?;
+        Ok(true)
+    }
+    
+    /// Actions with a link that resolves to `action`, ordered by project, then title
+    pub fn backlinks(&self, action: &Action) -> Vec<&Action> {
+        self.projects
+            .values()
+            .flat_map(|project| project.list_actions())
+            .filter(|candidate| candidate.file_path != action.file_path)
+            .filter(|candidate| {
+                self.linked_actions(candidate)
+                    .iter()
+                    .any(|target| target.file_path == action.file_path)
+            })
+            .collect()
+    }
+    
+    /// The full link graph across every action in the workspace
+    pub fn graph(&self) -> ActionGraph<'_> {
+        let nodes: Vec<&Action> = self.projects
+            .values()
+            .flat_map(|project| project.list_actions())
+            .collect();
+        let edges = self.edges_between(&nodes);
+        let dangling = self.dangling_links(&nodes);
+        
+        ActionGraph { nodes, edges, dangling }
+    }
+    
+    /// The link graph of one project's actions. Links to actions in other
+    /// projects are left out.
+    pub fn project_graph(&self, project_name: &str) -> Result<ActionGraph<'_>> {
+        let project = self.projects
+            .get(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        let nodes = project.list_actions();
+        let edges = self.edges_between(&nodes);
+        let dangling = self.dangling_links(&nodes);
+        
+        Ok(ActionGraph { nodes, edges, dangling })
+    }
+    
+    /// The part of the link graph reachable from `start` within `depth` hops.
+    ///
+    /// `Direction::Forward` follows the action's own links, `Direction::Reverse`
+    /// follows backlinks. Edges between all reached actions are included.
+    pub fn subgraph<'a>(&'a self, start: &'a Action, depth: usize, direction: Direction) -> ActionGraph<'a> {
+        let all = self.graph();
+        let index_of = |action: &Action| all.nodes.iter().position(|node| node.file_path == action.file_path);
+        
+        let Some(start_index) = index_of(start) else {
+            return ActionGraph { nodes: vec![start], ..Default::default() };
+        };
+        
+        let mut visited = HashSet::from([start_index]);
+        let mut order = vec![start_index];
+        let mut queue = VecDeque::from([(start_index, 0)]);
+        
+        while let Some((current, distance)) = queue.pop_front() {
+            if distance >= depth {
+                continue;
+            }
+            
+            let neighbours = all.edges.iter().filter_map(|&(from, to)| match direction {
+                Direction::Forward if from == current => Some(to),
+                Direction::Reverse if to == current => Some(from),
+                _ => None,
+            });
+            
+            for next in neighbours {
+                if visited.insert(next) {
+                    order.push(next);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        
+        let nodes: Vec<&Action> = order.iter().map(|&index| all.nodes[index]).collect();
+        let edges = all.edges
+            .iter()
+            .filter_map(|&(from, to)| {
+                let from = order.iter().position(|&index| index == from)?;
+                let to = order.iter().position(|&index| index == to)?;
+                Some((from, to))
+            })
+            .collect();
+        let dangling = self.dangling_links(&nodes);
+        
+        ActionGraph { nodes, edges, dangling }
+    }
+    
+    fn edges_between(&self, nodes: &[&Action]) -> Vec<(usize, usize)> {
+        let mut edges = Vec::new();
+        
+        for (from, action) in nodes.iter().enumerate() {
+            for target in self.linked_actions(action) {
+                if let Some(to) = nodes.iter().position(|node| node.file_path == target.file_path) {
+                    if !edges.contains(&(from, to)) {
+                        edges.push((from, to));
+                    }
+                }
+            }
+        }
+        
+        edges
+    }
+    
+    fn dangling_links(&self, nodes: &[&Action]) -> Vec<(usize, String)> {
+        nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(from, action)| self.broken_links(action).into_iter().map(move |target| (from, target)))
+            .collect()
+    }
+    
+    /// Change a setting (see `Config::set`) and save the config
+    pub fn set_config(&mut self, key: &str, value: &str) -> Result<()> {
+        self.ensure_writable()?;
+        ensure_settable(key)?;
+        self.config.set(key, value)?;
+        self.config.save(&self.root)
+    }
+    
+    /// Put a setting back to its default and save the config
+    pub fn unset_config(&mut self, key: &str) -> Result<()> {
+        self.ensure_writable()?;
+        ensure_settable(key)?;
+        self.config.unset(key)?;
+        self.config.save(&self.root)
+    }
+    
+    /// The editor `edit_action` opens: the configured `default_editor`,
+    /// then `$EDITOR` and the other fallbacks of `utils::detect_editor`
+    pub fn editor(&self) -> String {
+        self.config.default_editor.clone().unwrap_or_else(utils::detect_editor)
+    }
+    
+    /// Switch the meta-graph layout, moving existing meta-graphs and saving the config.
+    /// Returns how many meta-graph directories were moved.
+    pub fn migrate_meta_graphs(&mut self, layout: MetaGraphLayout) -> Result<usize> {
+        self.ensure_writable()?;
+        // Plan every project before moving anything, so a collision anywhere
+        // leaves the whole workspace in its old layout
+        let plans = self.projects
+            .iter()
+            .map(|(name, project)| Ok((name.clone(), project.meta_graph_moves(layout)?)))
+            .collect::<Result<Vec<_>>>()?;
+        
+        let mut moved = 0;
+        for (name, moves) in plans {
+            let project = self.projects.get_mut(&name).expect("planned from this workspace's projects");
+            project.apply_meta_graph_moves(&moves, layout)?;
+            moved += moves.len();
+        }
+        
+        self.config.meta_graph_layout = layout;
+        self.config.save(&self.root)?;
+        if self.config.gitignore {
+            config::ensure_gitignore(&self.root)?;
+        }
+        Ok(moved)
+    }
+    
+    /// What `migrate_meta_graphs` would move and rewrite, without touching anything
+    pub fn meta_graph_moves(&self, layout: MetaGraphLayout) -> Result<Vec<MetaGraphMove>> {
+        let mut moves = Vec::new();
+        for project in self.projects.values() {
+            moves.extend(project.meta_graph_moves(layout)?);
+        }
+        Ok(moves)
+    }
+    
+    /// Action files whose content differs from the canonical rendering
+    pub fn format_changes(&self, project_name: Option<&str>) -> Result<Vec<FileChange>> {
+        let mut changes = Vec::new();
+        
+        for project in self.projects.values() {
+            if project_name.is_some_and(|name| name != project.name) {
+                continue;
+            }
+            for action in project.list_actions() {
+                let old = fs::read_to_string(&action.file_path)
+                    .with_context(|| format!("Failed to read action file: {}", action.file_path.display()))?;
+                let new = action.to_markdown();
+                if old != new {
+                    changes.push(FileChange { path: action.file_path.clone(), old, new });
+                }
+            }
+        }
+        
+        Ok(changes)
+    }
+    
+    /// Rewrite action files in canonical form, keeping `updated_at` as is.
+    /// Returns what changed; with `dry_run` nothing is written.
+    pub fn format_actions(&self, project_name: Option<&str>, dry_run: bool) -> Result<Vec<FileChange>> {
+        let changes = self.format_changes(project_name)?;
+        if !dry_run {
+            self.ensure_writable()?;
+            project::write_changes(&changes)?;
+        }
+        Ok(changes)
+    }
+    
+    /// Required sections `action` lacks for `status`, including those its type always requires
+    pub fn missing_outputs(&self, action: &Action, status: &Status) -> Vec<String> {
+        self.required_outputs(action, status)
+            .into_iter()
+            .filter(|output| action.section(output).is_none())
+            .collect()
+    }
+    
+    /// Known sections `action` must have at `status`, without duplicates
+    fn required_outputs(&self, action: &Action, status: &Status) -> Vec<String> {
+        let mut required = self.workflow.required_outputs(status);
+        if let Ok(action_type) = self.config.action_type(action.action_type()) {
+            required.extend(action_type.required);
+        }
+        
+        let mut outputs: Vec<String> = Vec::new();
+        for output in required {
+            if Action::is_known_section(&output) && !outputs.contains(&output) {
+                outputs.push(output);
+            }
+        }
+        outputs
+    }
+    
+    /// Entry gates `action` doesn't meet for `status`: required inputs that
+    /// are absent or still hold their placeholder text
+    pub fn missing_inputs(&self, action: &Action, status: &Status) -> Vec<String> {
+        self.unfilled(action, self.config.required_inputs(status))
+    }
+    
+    /// Those of `sections` that `action` lacks, leaves empty or still has
+    /// the placeholder text for
+    fn unfilled(&self, action: &Action, sections: Vec<String>) -> Vec<String> {
+        let action_type = self.config.action_type(action.action_type()).ok();
+        
+        sections
+            .into_iter()
+            .filter(|section| {
+                let placeholder = match &action_type {
+                    Some(action_type) => action_type.placeholder(section),
+                    None => template::default_placeholder(section).to_string(),
+                };
+                action.section(section).is_none_or(|content| content.trim().is_empty() || content.trim() == placeholder.trim())
+            })
+            .collect()
+    }
+    
+    /// Move every action in a project one step along the pipeline.
+    ///
+    /// An action only advances once its current status has all of its
+    /// required outputs and it meets the next status's entry gates.
+    /// With `dry_run` nothing is written; `scaffold` is passed on to
+    /// `update_action_status_with`.
+    pub fn advance_project(&mut self, project_name: &str, dry_run: bool, scaffold: bool) -> Result<Vec<AdvanceOutcome>> {
+        if !dry_run {
+            self.ensure_writable()?;
+        }
+        let project = self.projects.get(project_name)
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        
+        let outcomes: Vec<AdvanceOutcome> = project.list_actions()
+            .into_iter()
+            .map(|action| {
+                let title = action.title().to_string();
+                if !action.is_active() {
+                    return AdvanceOutcome::Inactive { title, lifecycle: action.lifecycle() };
+                }
+                let mut missing = self.missing_outputs(action, action.status());
+                let next = self.workflow.next(action.status());
+                if let Some(next) = &next {
+                    for input in self.missing_inputs(action, next) {
+                        if !missing.contains(&input) {
+                            missing.push(input);
+                        }
+                    }
+                }
+                match next {
+                    None => AdvanceOutcome::Finished { title },
+                    Some(_) if !missing.is_empty() => AdvanceOutcome::Blocked {
+                        title,
+                        status: action.status().clone(),
+                        missing,
+                    },
+                    Some(next) => AdvanceOutcome::Advanced {
+                        title,
+                        from: action.status().clone(),
+                        to: next,
+                    },
+                }
+            })
+            .collect();
+        
+        if !dry_run {
+            for outcome in &outcomes {
+                if let AdvanceOutcome::Advanced { title, to, .. } = outcome {
+                    self.update_action_status_with(project_name, title, to.clone(), scaffold)?;
+                }
+            }
+        }
+        
+        Ok(outcomes)
+    }
+    
+    /// Validate the workspace, failing on the first error found
+    pub fn validate(&self) -> Result<()> {
+        if let Some(issue) = self.check().into_iter().find(|issue| issue.is_error()) {
+            anyhow::bail!("{}", issue);
+        }
+        
+        Ok(())
+    }
+    
+    /// Collect every validation issue in the workspace
+    pub fn check(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        // Reported together after everything else
+        let mut broken_links = Vec::new();
+        
+        // Check workspace marker
+        let marker_file = self.root.join(".action-lite");
+        if !marker_file.exists() {
+            issues.push(ValidationIssue::error("missing-marker", "Missing .action-lite marker file".to_string()));
+        }
+        
+        for (path, error) in &self.load_errors {
+            issues.push(ValidationIssue::error(
+                "load-error",
+                format!("Failed to load project from {}: {}", path.display(), error),
+            ));
+        }
+        
+        // Validate each project
+        for (project_name, project) in &self.projects {
+            if !project.path().exists() {
+                issues.push(ValidationIssue::error(
+                    "missing-project-dir",
+                    format!("Project directory does not exist: {}", project.path().display()),
+                ).for_project(project_name));
+                continue;
+            }
+            
+            for path in project.orphan_meta_graphs() {
+                issues.push(ValidationIssue::warning(
+                    "orphan-meta-graph",
+                    format!("Meta-graph directory has no matching action: {}", path.display()),
+                ).for_project(project_name));
+            }
+            
+            for (path, error) in project.load_errors() {
+                issues.push(ValidationIssue::error(
+                    "parse-error",
+                    format!("Failed to parse {}: {}", path.display(), error),
+                ).for_project(project_name));
+            }
+            
+            // Validate actions in project
+            for action in project.list_actions() {
+                if !action.file_path.exists() {
+                    issues.push(ValidationIssue::error(
+                        "missing-file",
+                        format!("Action file does not exist: {}", action.file_path.display()),
+                    ).for_action(project_name, action.title()));
+                    continue;
+                }
+                
+                for warning in &action.parse_warnings {
+                    issues.push(ValidationIssue::warning(warning.code(), warning.to_string())
+                        .for_action(project_name, action.title()));
+                }
+                
+                // Validate required outputs for current status and action type
+                if let Err(e) = self.config.action_type(action.action_type()) {
+                    issues.push(ValidationIssue::warning("unknown-type", e.to_string())
+                        .for_action(project_name, action.title()));
+                }
+                
+                for target in self.broken_links(action) {
+                    broken_links.push(ValidationIssue::warning(
+                        "broken-link",
+                        format!("Link to {} does not resolve to a file", target),
+                    ).for_action(project_name, action.title()));
+                }
+                
+                for reference in self.declared_dependencies(action).1 {
+                    issues.push(ValidationIssue::warning(
+                        "dangling-dependency",
+                        format!("Depends on '{}', which is not an action", reference),
+                    ).for_action(project_name, action.title()));
+                }
+                
+                if self.workflow.position(action.status()).is_none() {
+                    issues.push(ValidationIssue::warning(
+                        "unknown-status",
+                        format!("Status '{}' is not part of the workflow", action.status()),
+                    ).for_action(project_name, action.title()));
+                }
+                
+                for output in self.missing_outputs(action, action.status()) {
+                    issues.push(ValidationIssue::error(
+                        "missing-section",
+                        format!("Missing required {} section", output),
+                    ).for_action(project_name, action.title()));
+                }
+            }
+        }
+        
+        if let Some(status) = &self.config.default_status {
+            if let Err(e) = self.workflow.parse(status) {
+                issues.push(ValidationIssue::warning(
+                    "invalid-default-status",
+                    format!("Invalid default_status in config: {}; new actions start at {}", e, self.workflow.first()),
+                ));
+            }
+        }
+        
+        if !utils::is_valid_date_format(&self.config.date_format) {
+            issues.push(ValidationIssue::warning(
+                "invalid-date-format",
+                format!("Invalid date_format in config: {}; using the default", self.config.date_format),
+            ));
+        }
+        
+        for (id, actions) in self.duplicate_ids() {
+            let paths: Vec<String> = actions.iter()
+                .map(|action| utils::format_relative_path(&action.file_path, &self.root))
+                .collect();
+            for action in &actions[1..] {
+                issues.push(ValidationIssue::error(
+                    "duplicate-id",
+                    format!("Action id {} is shared by {}", id, paths.join(", ")),
+                ).for_action(action.project(), action.title()));
+            }
+        }
+        
+        issues.extend(broken_links);
+        issues
+    }
+    
+    /// Relative `.md` link targets in `action`'s sections whose file doesn't
+    /// exist. Paths resolve from the action's directory, or from the
+    /// workspace root when they start with `/`. Sections still holding their
+    /// placeholder text are skipped, since the template's example link is
+    /// meant to be replaced.
+    pub fn broken_links(&self, action: &Action) -> Vec<String> {
+        let action_type = self.config.action_type(action.action_type()).ok();
+        let dir = action.file_path.parent().unwrap_or(Path::new(""));
+        let mut broken = Vec::new();
+        
+        for &section in SECTION_NAMES {
+            let Some(content) = action.section(section) else {
+                continue;
+            };
+            let placeholder = match &action_type {
+                Some(action_type) => action_type.placeholder(section),
+                None => template::default_placeholder(section).to_string(),
+            };
+            if content.trim() == placeholder.trim() {
+                continue;
+            }
+            
+            for target in MarkdownParser::new(content).extract_links() {
+                if target.contains("://") || target.starts_with("mailto:") || broken.contains(&target) {
+                    continue;
+                }
+                let resolved = match target.strip_prefix('/') {
+                    Some(from_root) => self.root.join(from_root),
+                    None => dir.join(&target),
+                };
+                if !resolved.is_file() {
+                    broken.push(target);
+                }
+            }
+        }
+        broken
+    }
+    
+    /// Ids used by more than one action, each with its actions oldest first
+    pub fn duplicate_ids(&self) -> Vec<(ActionId, Vec<&Action>)> {
+        let mut by_id: BTreeMap<ActionId, Vec<&Action>> = BTreeMap::new();
+        for action in self.projects.values().flat_map(|project| project.list_actions()) {
+            by_id.entry(action.id.clone()).or_default().push(action);
+        }
+        
+        by_id.into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(id, mut actions)| {
+                actions.sort_by_key(|action| action.created_at);
+                (id, actions)
+            })
+            .collect()
+    }
+    
+    /// Give every action that shares an id with an older one a fresh id.
+    /// Returns the `(project, title)` of each action that changed.
+    pub fn fix_duplicate_ids(&mut self) -> Result<Vec<(String, String)>> {
+        self.ensure_writable()?;
+        let copies: Vec<(String, String)> = self.duplicate_ids()
+            .into_iter()
+            .flat_map(|(_, actions)| {
+                actions.into_iter()
+                    .skip(1)
+                    .map(|action| (action.project().to_string(), action.title().to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        
+        for (project_name, title) in &copies {
+            let project = self.projects.get_mut(project_name)
+                .with_context(|| format!("Project '{}' not found", project_name))?;
+            let id = project.next_id();
+            let action = project.get_action_mut(title)
+                .with_context(|| format!("Action '{}' not found in project '{}'", title, project_name))?;
+            action.id = id;
+            action.save()?;
+        }
+        
+        Ok(copies)
+    }
+    
+    /// Add a user tag to one action's tag line. Returns false if it was already there.
+    pub fn add_action_tag(&mut self, project_name: &str, title: &str, tag: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        let tag = normalize_tag(tag)?;
+        let action = self.projects.get_mut(project_name)
+            .and_then(|project| project.get_action_mut(title))
+            .with_context(|| format!("Action '{}' not found in project '{}'", title, project_name))?;
+        
+        if action.is_reserved_tag(&tag) {
+            anyhow::bail!("#{} is generated by action-lite and can't be added by hand", tag);
+        }
+        if !action.add_tag(&tag) {
+            return Ok(false);
+        }
+        action.save()?;
+        Ok(true)
+    }
+    
+    /// Remove a user tag from one action's tag line. Returns false if it wasn't there.
+    pub fn remove_action_tag(&mut self, project_name: &str, title: &str, tag: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        let tag = normalize_tag(tag)?;
+        let action = self.projects.get_mut(project_name)
+            .and_then(|project| project.get_action_mut(title))
+            .with_context(|| format!("Action '{}' not found in project '{}'", title, project_name))?;
+        
+        if !action.remove_tag(&tag) {
+            return Ok(false);
+        }
+        action.save()?;
+        Ok(true)
+    }
+    
+    /// Remove and/or add a tag-line tag on every action `filter` selects,
+    /// saving each one that changed. A rename is a remove plus an add, and
+    /// only touches actions that carried the old tag.
+    ///
+    /// Returns the changed actions as `(project, title)`; with `dry_run`
+    /// nothing is saved.
+    pub fn retag(
+        &mut self,
+        filter: &ActionFilter,
+        remove: Option<&str>,
+        add: Option<&str>,
+        dry_run: bool,
+    ) -> Result<Vec<(String, String)>> {
+        if !dry_run {
+            self.ensure_writable()?;
+        }
+        let remove = remove.map(normalize_tag).transpose()?;
+        let add = add.map(normalize_tag).transpose()?;
+        
+        let selected: Vec<(String, String)> = self.filter_actions(filter)?
+            .into_iter()
+            .map(|action| (action.project().to_string(), action.title().to_string()))
+            .collect();
+        
+        let mut changed = Vec::new();
+        for (project_name, title) in selected {
+            let action = self.projects.get_mut(&project_name)
+                .and_then(|project| project.get_action_mut(&title))
+                .with_context(|| format!("Action '{}' not found in project '{}'", title, project_name))?;
+            
+            if let Some(tag) = add.as_deref().filter(|tag| action.is_reserved_tag(tag)) {
+                anyhow::bail!("#{} is generated by action-lite and can't be added by hand", tag);
+            }
+            
+            let mut updated = action.clone();
+            let mut modified = false;
+            if let Some(tag) = &remove {
+                modified = updated.remove_tag(tag);
+                if !modified && add.is_some() {
+                    // Renaming only applies to actions that had the old tag
+                    continue;
+                }
+            }
+            if let Some(tag) = &add {
+                modified |= updated.add_tag(tag);
+            }
+            if !modified {
+                continue;
+            }
+            
+            if !dry_run {
+                updated.save()?;
+                *action = updated;
+            }
+            changed.push((project_name, title));
+        }
+        
+        Ok(changed)
+    }
+    
+    /// Every file or project directory that failed to load, with the reason.
+    /// These are left out of the workspace rather than failing the load.
+    pub fn load_errors(&self) -> Vec<(PathBuf, String)> {
+        self.load_errors
+            .iter()
+            .cloned()
+            .chain(self.projects.values().flat_map(|project| project.load_errors().iter().cloned()))
+            .collect()
+    }
+    
+    /// Projects keyed by name, iterated in name order
+    pub fn projects(&self) -> &BTreeMap<String, Project> {
+        &self.projects
+    }
+    
+    pub fn get_project(&self, name: &str) -> Option<&Project> {
+        self.projects.get(name)
+    }
+    
+    /// Index the user tags of every action (see `Action::user_tags`)
+    pub fn tag_index(&self) -> TagIndex {
+        let mut index = TagIndex::default();
+        
+        for action in self.projects.values().flat_map(|project| project.list_actions()) {
+            let tags = action.user_tags();
+            for tag in &tags {
+                index.actions
+                    .entry(tag.clone())
+                    .or_default()
+                    .push((action.project().to_string(), action.title().to_string()));
+                
+                for other in tags.iter().filter(|other| *other != tag) {
+                    *index.co_occurrence
+                        .entry(tag.clone())
+                        .or_default()
+                        .entry(other.clone())
+                        .or_default() += 1;
+                }
+            }
+        }
+        
+        index
+    }
+    
+    /// Pairs of actions that look alike, most similar first.
+    ///
+    /// Scores run from 0 to 1. Titles are compared by edit distance and
+    /// `Statement of Action` bodies by shared character pairs, and the two are
+    /// averaged when both actions have a statement beyond the placeholder.
+    /// Only actions in the same project are paired unless `cross_project` is set.
+    pub fn near_duplicates(&self, threshold: f64, cross_project: bool) -> Vec<(f64, &Action, &Action)> {
+        let normalize = |text: &str| -> String {
+            text.to_lowercase()
+                .replace(['_', '-'], " ")
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        
+        let actions: Vec<(&Action, String, Option<String>)> = self.projects
+            .values()
+            .flat_map(|project| project.list_actions())
+            .map(|action| {
+                let placeholder = self.config.action_type(action.action_type())
+                    .map(|action_type| action_type.placeholder("Statement of Action"))
+                    .unwrap_or_default();
+                let statement = action.statement_of_action()
+                    .filter(|statement| statement.trim() != placeholder.trim())
+                    .map(|statement| normalize(statement))
+                    .filter(|statement| !statement.is_empty());
+                (action, normalize(action.title()), statement)
+            })
+            .collect();
+        
+        let mut pairs = Vec::new();
+        for (i, (action, title, statement)) in actions.iter().enumerate() {
+            for (other, other_title, other_statement) in &actions[i + 1..] {
+                if !cross_project && action.project() != other.project() {
+                    continue;
+                }
+                
+                let title_score = strsim::normalized_levenshtein(title, other_title);
+                let score = match (statement, other_statement) {
+                    (Some(a), Some(b)) => (title_score + strsim::sorensen_dice(a, b)) / 2.0,
+                    _ => title_score,
+                };
+                if score >= threshold {
+                    pairs.push((score, *action, *other));
+                }
+            }
+        }
+        
+        pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+        pairs
+    }
+    
+    /// Summarize where the workspace is and how it's configured
+    pub fn info(&self) -> WorkspaceInfo {
+        let config_file = Config::path(&self.root);
+        
+        WorkspaceInfo {
+            root: self.root.clone(),
+            config_file: config_file.is_file().then_some(config_file),
+            legacy_marker: self.root.join(config::STATE_DIR).is_file(),
+            projects: self.projects.len(),
+            actions: self.projects.values().map(|project| project.list_actions().len()).sum(),
+            meta_graph_layout: self.config.meta_graph_layout,
+            follow_symlinks: self.config.follow_symlinks,
+            custom_types: self.config.types.keys().cloned().collect(),
+            editor: self.editor(),
+        }
+    }
+    
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+    
+    /// Counts over the loaded actions; see `WorkspaceStats::collect_with`
+    pub fn stats(&self, stale_days: i64) -> WorkspaceStats {
+        WorkspaceStats::collect_with(self, stale_days, Utc::now())
+    }
+    
+    /// The statuses actions move through, from `[[statuses]]` or built in
+    pub fn workflow(&self) -> &Workflow {
+        &self.workflow
+    }
+    
+    /// Resolve a status name against the workspace's workflow
+    pub fn parse_status(&self, name: &str) -> Result<Status> {
+        self.workflow.parse(name)
+    }
+}
+
+/// Settings that `set_config` mustn't change on its own
+fn ensure_settable(key: &str) -> Result<()> {
+    if key == "meta_graph_layout" {
+        anyhow::bail!("Changing meta_graph_layout moves meta-graphs; use `action migrate --meta-graph-layout` instead");
+    }
+    Ok(())
+}
+
+/// Strip a leading `#` and check that what's left can be written as a tag
+fn normalize_tag(tag: &str) -> Result<String> {
+    let tag = tag.trim_start_matches('#');
+    if tag.is_empty() || !tag.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        anyhow::bail!("Invalid tag: '{}'. Tags may only contain letters, digits, '-' and '_'", tag);
+    }
+    Ok(tag.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::relink_meta_graph;
+    use crate::Severity;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+    
+    fn workspace_with(actions: &[(&str, &str)]) -> (TempDir, Workspace) {
+        let dir = TempDir::new().unwrap();
+        let mut workspace = Workspace::init(dir.path()).unwrap();
+        for (project, title) in actions {
+            workspace.create_action(project, title, Priority::None).unwrap();
+        }
+        (dir, workspace)
+    }
+    
+    #[test]
+    fn test_ordering_is_stable_across_loads() {
+        let (dir, _) = workspace_with(&[
+            ("zeta", "b"), ("alpha", "c"), ("mid", "a"), ("alpha", "a"), ("zeta", "a"),
+        ]);
+        
+        let order = |workspace: &Workspace| -> Vec<String> {
+            workspace.list_actions(None, None, false).unwrap()
+                .iter()
+                .map(|a| format!("{}/{}", a.project(), a.title()))
+                .collect()
+        };
+        
+        let first = Workspace::load(dir.path()).unwrap();
+        let names: Vec<&String> = first.projects().keys().collect();
+        assert_eq!(names, ["alpha", "mid", "zeta"]);
+        assert_eq!(order(&first), ["alpha/a", "alpha/c", "mid/a", "zeta/a", "zeta/b"]);
+        
+        for _ in 0..5 {
+            let again = Workspace::load(dir.path()).unwrap();
+            assert_eq!(order(&again), order(&first));
+        }
+    }
+    
+    #[test]
+    fn test_check_reports_structured_issues() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        assert!(workspace.check().is_empty());
+        
+        workspace.update_action_status("web", "setup", Status::Design).unwrap();
+        let issues = workspace.check();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "missing-section");
+        assert_eq!(issues[0].project.as_deref(), Some("web"));
+        assert_eq!(issues[0].title.as_deref(), Some("setup"));
+        assert!(issues[0].is_error());
+        assert!(workspace.validate().is_err());
+    }
+    
+    #[test]
+    fn test_init_refuses_existing_workspace() {
+        let dir = TempDir::new().unwrap();
+        Workspace::init(dir.path()).unwrap();
+        
+        let err = Workspace::init(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        
+        fs::write(dir.path().join("README.md"), "# Ours\n").unwrap();
+        let force = InitOptions { force: true, ..Default::default() };
+        assert!(Workspace::init_with(dir.path(), &force).is_ok());
+        assert_eq!(fs::read_to_string(dir.path().join("README.md")).unwrap(), "# Ours\n");
+    }
+    
+    #[test]
+    fn test_init_bare_and_template() {
+        let bare_dir = TempDir::new().unwrap();
+        let bare = InitOptions { bare: true, ..Default::default() };
+        Workspace::init_with(bare_dir.path(), &bare).unwrap();
+        assert!(bare_dir.path().join(".action-lite").exists());
+        assert!(!bare_dir.path().join("README.md").exists());
+        
+        let dir = TempDir::new().unwrap();
+        let template = dir.path().join("readme-template.md");
+        fs::write(&template, "# Team workspace\n").unwrap();
+        let root = dir.path().join("ws");
+        let options = InitOptions { template: Some(template), ..Default::default() };
+        Workspace::init_with(&root, &options).unwrap();
+        assert_eq!(fs::read_to_string(root.join("README.md")).unwrap(), "# Team workspace\n");
+        
+        // A README that is already there survives --template without --force
+        let existing = dir.path().join("existing");
+        fs::create_dir_all(&existing).unwrap();
+        fs::write(existing.join("README.md"), "# Ours\n").unwrap();
+        Workspace::init_with(&existing, &options).unwrap();
+        assert_eq!(fs::read_to_string(existing.join("README.md")).unwrap(), "# Ours\n");
+        let options = InitOptions { force: true, ..options };
+        Workspace::init_with(&existing, &options).unwrap();
+        assert_eq!(fs::read_to_string(existing.join("README.md")).unwrap(), "# Team workspace\n");
+    }
+    
+    #[test]
+    fn test_filter_by_section_presence() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        workspace.update_action_status("web", "setup", Status::Design).unwrap();
+        workspace.update_action_status("web", "deploy", Status::Design).unwrap();
+        let project = workspace.projects.get_mut("web").unwrap();
+        project.get_action_mut("deploy").unwrap()
+            .update_section("statement of design", Some("Ship it".to_string()));
+        project.get_action_mut("setup").unwrap()
+            .extra_sections.push(("Risks".to_string(), "Downtime".to_string()));
+        
+        let missing = ActionFilter {
+            status: Some(Status::Design),
+            missing_sections: vec!["Statement of Design".to_string()],
+            ..Default::default()
+        };
+        let titles: Vec<&str> = workspace.filter_actions(&missing).unwrap()
+            .iter().map(|a| a.title()).collect();
+        assert_eq!(titles, ["setup"]);
+        
+        let has = ActionFilter {
+            has_sections: vec!["statement of DESIGN".to_string()],
+            ..Default::default()
+        };
+        let titles: Vec<&str> = workspace.filter_actions(&has).unwrap()
+            .iter().map(|a| a.title()).collect();
+        assert_eq!(titles, ["deploy"]);
+        
+        let titles = |filter: &ActionFilter| -> Vec<String> {
+            workspace.filter_actions(filter).unwrap().iter().map(|a| a.title().to_string()).collect()
+        };
+        assert_eq!(titles(&ActionFilter { has_sections: vec!["risks".to_string()], ..Default::default() }), ["setup"]);
+        assert_eq!(titles(&ActionFilter { missing_sections: vec!["Risks".to_string()], ..Default::default() }), ["deploy"]);
+        assert!(titles(&ActionFilter { has_sections: vec!["Rollout".to_string()], ..Default::default() }).is_empty());
+        
+        let blank = ActionFilter { has_sections: vec![" ".to_string()], ..Default::default() };
+        assert!(workspace.filter_actions(&blank).is_err());
+    }
+    
+    fn link(workspace: &mut Workspace, project: &str, title: &str, targets: &[&str]) {
+        let inputs = targets.iter()
+            .map(|target| format!("- [{}]({})", target, target))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let action = workspace.projects.get_mut(project).unwrap().get_action_mut(title).unwrap();
+        action.update_section("statement of inputs", Some(inputs));
+    }
+    
+    fn titles(graph: &ActionGraph) -> Vec<String> {
+        graph.nodes.iter().map(|a| format!("{}/{}", a.project(), a.title())).collect()
+    }
+    
+    #[test]
+    fn test_subgraph_respects_depth_and_direction() {
+        let (_dir, mut workspace) = workspace_with(&[
+            ("web", "a"), ("web", "b"), ("web", "c"), ("api", "d"),
+        ]);
+        // a -> b -> c -> ../api/d, and d links back to a to form a cycle
+        link(&mut workspace, "web", "a", &["./b.md"]);
+        link(&mut workspace, "web", "b", &["c.md"]);
+        link(&mut workspace, "web", "c", &["../api/d.md", "missing.md"]);
+        link(&mut workspace, "api", "d", &["../web/a.md"]);
+        
+        let start = workspace.get_action("web", "a").unwrap();
+        
+        let one_hop = workspace.subgraph(start, 1, Direction::Forward);
+        assert_eq!(titles(&one_hop), ["web/a", "web/b"]);
+        assert_eq!(one_hop.edges, [(0, 1)]);
+        
+        let everything = workspace.subgraph(start, usize::MAX, Direction::Forward);
+        assert_eq!(titles(&everything), ["web/a", "web/b", "web/c", "api/d"]);
+        assert_eq!(everything.edges.len(), 4);
+        
+        let reverse = workspace.subgraph(start, 2, Direction::Reverse);
+        assert_eq!(titles(&reverse), ["web/a", "api/d", "web/c"]);
+        
+        let dot = one_hop.to_dot(workspace.workflow());
+        assert!(dot.contains("\"web/a\" -> \"web/b\";"));
+    }
+    
+    #[test]
+    fn test_project_graph_marks_self_and_dangling_links() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "a"), ("web", "b"), ("api", "c")]);
+        link(&mut workspace, "web", "a", &["a.md", "b.md", "../api/c.md", "missing.md"]);
+        workspace.update_action_status("web", "b", Status::Published).unwrap();
+        
+        let graph = workspace.project_graph("web").unwrap();
+        assert_eq!(titles(&graph), ["web/a", "web/b"]);
+        assert_eq!(graph.edges, [(0, 0), (0, 1)]);
+        assert_eq!(graph.dangling, [(0, "missing.md".to_string())]);
+        assert!(workspace.project_graph("nope").is_err());
+        
+        let dot = graph.to_dot(workspace.workflow());
+        assert!(dot.contains("\"web/a\" -> \"web/a\" [style=dotted];"), "{}", dot);
+        assert!(dot.contains("\"web/a\" -> \"missing.md\" [style=dotted, color=grey];"), "{}", dot);
+        assert!(dot.contains("\"web/a\" [fillcolor=lightgrey];"), "{}", dot);
+        assert!(dot.contains("\"web/b\" [fillcolor=palegreen];"), "{}", dot);
+    }
+    
+    #[test]
+    fn test_check_reports_parse_errors() {
+        let (dir, _) = workspace_with(&[("web", "setup")]);
+        fs::write(dir.path().join("web/broken.md"), "---\nid: 1\nnot a pair\n---\n").unwrap();
+        fs::write(dir.path().join("web/open.md"), "---\nid: 1\n# Open\n").unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let issues: Vec<ValidationIssue> = workspace.check()
+            .into_iter()
+            .filter(|issue| issue.code.starts_with("parse-"))
+            .collect();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].code, "parse-error");
+        assert!(issues[0].message.contains("line 3"), "{}", issues[0].message);
+        assert_eq!(issues[1].code, "parse-warning");
+        assert_eq!(issues[1].title.as_deref(), Some("open"));
+        assert!(!issues[1].is_error());
+    }
+    
+    #[test]
+    fn test_check_reports_duplicate_and_unknown_frontmatter_keys() {
+        let (dir, _) = workspace_with(&[]);
+        fs::create_dir(dir.path().join("web")).unwrap();
+        fs::write(
+            dir.path().join("web/setup.md"),
+            "---\nid: 550e8400-e29b-41d4-a716-446655440000\nowner: bob\nid: 550e8400-e29b-41d4-a716-446655440001\n---\n",
+        ).unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let codes: Vec<&str> = workspace.check()
+            .into_iter()
+            .filter(|issue| !issue.is_error())
+            .map(|issue| issue.code)
+            .collect();
+        assert_eq!(codes, ["unknown-key", "duplicate-key"]);
+    }
+    
+    #[test]
+    fn test_migrate_meta_graphs_between_layouts() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        let sibling = workspace.create_meta_graph("web", "setup").unwrap();
+        fs::write(sibling.join("sub.md"), "# Sub\n").unwrap();
+        assert_eq!(sibling, dir.path().join("web/setup"));
+        
+        assert_eq!(workspace.migrate_meta_graphs(MetaGraphLayout::Hidden).unwrap(), 1);
+        assert!(!sibling.exists());
+        assert!(dir.path().join("web/.meta/setup/sub.md").exists());
+        
+        // The layout is persisted and honoured on the next load
+        let mut reloaded = Workspace::load(dir.path()).unwrap();
+        let action = reloaded.get_action("web", "setup").unwrap();
+        assert!(action.has_meta_graph());
+        assert_eq!(action.meta_graph_path(), dir.path().join("web/.meta/setup"));
+        assert_eq!(
+            reloaded.create_meta_graph("web", "deploy").unwrap(),
+            dir.path().join("web/.meta/deploy")
+        );
+        assert!(reloaded.check().iter().all(|issue| issue.code != "orphan-meta-graph"));
+        
+        assert_eq!(reloaded.migrate_meta_graphs(MetaGraphLayout::Sibling).unwrap(), 2);
+        assert!(dir.path().join("web/setup/sub.md").exists());
+        assert!(!dir.path().join("web/.meta").exists());
+    }
+    
+    #[test]
+    fn test_migrate_meta_graphs_checks_every_project_first() {
+        let (dir, mut workspace) = workspace_with(&[("api", "auth"), ("web", "setup")]);
+        workspace.create_meta_graph("api", "auth").unwrap();
+        workspace.create_meta_graph("web", "setup").unwrap();
+        fs::create_dir_all(dir.path().join("web/.meta/setup")).unwrap();
+        
+        let error = workspace.migrate_meta_graphs(MetaGraphLayout::Hidden).unwrap_err();
+        assert!(error.to_string().contains("already exists"), "{}", error);
+        assert!(dir.path().join("api/auth").is_dir());
+        assert!(!dir.path().join("api/.meta").exists());
+        assert_eq!(workspace.config().meta_graph_layout, MetaGraphLayout::Sibling);
+    }
+    
+    #[test]
+    fn test_check_reports_orphan_meta_graphs() {
+        let (dir, _) = workspace_with(&[("web", "setup")]);
+        fs::create_dir_all(dir.path().join("web/removed")).unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let orphans: Vec<ValidationIssue> = workspace.check()
+            .into_iter()
+            .filter(|issue| issue.code == "orphan-meta-graph")
+            .collect();
+        assert_eq!(orphans.len(), 1);
+        assert!(!orphans[0].is_error());
+    }
+    
+    #[test]
+    fn test_create_and_validate_typed_actions() {
+        let (dir, mut workspace) = workspace_with(&[]);
+        let bug = CreateOptions { action_type: Some("bug".to_string()), ..Default::default() };
+        let feature = CreateOptions { action_type: Some("feature".to_string()), ..Default::default() };
+        workspace.create_action_with("web", "crash", &bug).unwrap();
+        workspace.create_action_with("web", "login", &feature).unwrap();
+        let unknown = CreateOptions { action_type: Some("epic".to_string()), ..Default::default() };
+        assert!(workspace.create_action_with("web", "big", &unknown).is_err());
+        
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        let crash = workspace.get_action("web", "crash").unwrap();
+        assert_eq!(crash.action_type(), "bug");
+        assert!(crash.statement_of_action().unwrap().contains("reproduce"));
+        assert!(crash.statement_of_design().is_none());
+        let login = workspace.get_action("web", "login").unwrap();
+        assert!(login.statement_of_design().unwrap().contains("### Output"));
+        assert!(workspace.check().is_empty());
+        
+        // Bugs require a Statement of Action even once past discovery
+        for title in ["crash", "login"] {
+            workspace.update_action_status("web", title, Status::Implement).unwrap();
+            let action = workspace.projects.get_mut("web").unwrap().get_action_mut(title).unwrap();
+            action.update_section("statement of action", None);
+            action.update_section("statement of design", Some("Done".to_string()));
+        }
+        let issues = workspace.check();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].title.as_deref(), Some("crash"));
+        assert_eq!(issues[0].code, "missing-section");
+        
+        let filter = ActionFilter { action_type: Some("feature".to_string()), ..Default::default() };
+        let titles: Vec<&str> = workspace.filter_actions(&filter).unwrap()
+            .iter().map(|a| a.title()).collect();
+        assert_eq!(titles, ["login"]);
+    }
+    
+    #[test]
+    fn test_parallel_load_matches_the_files() {
+        let (dir, mut workspace) = workspace_with(&[]);
+        for project in 0..12 {
+            for action in 0..15 {
+                workspace.create_action(&format!("p{}", project), &format!("a{}", action), Priority::None).unwrap();
+            }
+        }
+        fs::write(dir.path().join("p3/broken.md"), "---\nid: 1\nnot a pair\n---\n").unwrap();
+        fs::write(dir.path().join("p7/broken.md"), "---\nid: 1\nnot a pair\n---\n").unwrap();
+        
+        let snapshot = |workspace: &Workspace| -> Vec<(String, String, String)> {
+            workspace.projects()
+                .values()
+                .flat_map(|project| project.list_actions())
+                .map(|action| (action.project().to_string(), action.title().to_string(), action.id.to_string()))
+                .collect()
+        };
+        let expected = snapshot(&workspace);
+        assert_eq!(expected.len(), 180);
+        
+        for _ in 0..5 {
+            let loaded = Workspace::load(dir.path()).unwrap();
+            assert_eq!(snapshot(&loaded), expected);
+            let failed: Vec<&str> = loaded.projects()
+                .values()
+                .filter(|project| !project.load_errors().is_empty())
+                .map(|project| project.name.as_str())
+                .collect();
+            assert_eq!(failed, ["p3", "p7"]);
+        }
+    }
+    
+    #[test]
+    fn test_action_lookup_ignores_case_and_suggests_titles() {
+        let (dir, mut workspace) = workspace_with(&[("web", "Fix Bug"), ("web", "deploy"), ("web", "deploys")]);
+        
+        assert_eq!(workspace.get_action("web", "fix bug").unwrap().title(), "Fix Bug");
+        workspace.set_action_priority("web", "FIX BUG", Priority::High).unwrap();
+        workspace.update_action_status("web", "fix bug", Status::Design).unwrap();
+        let action = workspace.get_action("web", "Fix Bug").unwrap();
+        assert_eq!((action.priority(), action.status()), (Priority::High, &Status::Design));
+        let logged: Vec<String> = history::load(dir.path()).unwrap().into_iter().map(|change| change.title).collect();
+        assert_eq!(logged, ["Fix Bug", "Fix Bug"]);
+        
+        let error = workspace.get_action("web", "deplyo").unwrap_err().to_string();
+        assert_eq!(error, "Action 'deplyo' not found in project 'web'; did you mean: 'deploy', 'deploys'?");
+        let error = workspace.set_action_priority("web", "fixbug", Priority::Low).unwrap_err().to_string();
+        assert!(error.ends_with("did you mean: 'Fix Bug'?"), "{}", error);
+        let error = workspace.get_action("web", "unrelated").unwrap_err().to_string();
+        assert_eq!(error, "Action 'unrelated' not found in project 'web'");
+        
+        // Reloaded titles are file stems; a lazy load finds the file from the
+        // typed title, and reads the whole project to suggest
+        let mut lazy = Workspace::load_lazy(dir.path()).unwrap();
+        assert_eq!(lazy.load_action("web", "Fix Bug").unwrap().title(), "fix_bug");
+        assert!(lazy.load_action("web", "deplo").unwrap_err().to_string().contains("did you mean: 'deploy'"));
+    }
+    
+    #[test]
+    fn test_move_action_with_a_loose_title() {
+        let (dir, mut workspace) = workspace_with(&[("web", "Add Login")]);
+        
+        workspace.move_action("web", "ADD LOGIN", "api").unwrap();
+        assert!(workspace.get_action("web", "Add Login").is_err());
+        let moved = workspace.get_action("api", "add login").unwrap();
+        assert_eq!((moved.title(), moved.project()), ("Add Login", "api"));
+        let content = fs::read_to_string(dir.path().join("api/add_login.md")).unwrap();
+        assert!(content.contains("#project #api "), "{}", content);
+    }
+    
+    #[test]
+    fn test_delete_action_with_a_loose_title() {
+        let (dir, mut workspace) = workspace_with(&[("web", "Third One"), ("web", "deploy")]);
+        
+        assert_eq!(workspace.delete_action("web", "THIRD ONE").unwrap().title(), "Third One");
+        assert!(!dir.path().join("web/third_one.md").exists());
+        assert!(workspace.get_action("web", "third one").is_err());
+        let error = workspace.delete_action("web", "deplyo").unwrap_err().to_string();
+        assert!(error.ends_with("did you mean: 'deploy'?"), "{}", error);
+    }
+    
+    #[test]
+    fn test_rename_action_with_a_loose_title() {
+        let (dir, mut workspace) = workspace_with(&[("web", "Third One")]);
+        workspace.create_meta_graph("web", "third_one.md").unwrap();
+        
+        workspace.rename_action("web", "THIRD ONE", "Fourth").unwrap();
+        assert!(workspace.get_action("web", "Third One").is_err());
+        assert_eq!(workspace.get_action("web", "fourth").unwrap().title(), "Fourth");
+        assert!(dir.path().join("web/fourth.md").is_file());
+        assert!(dir.path().join("web/fourth").is_dir());
+    }
+    
+    #[test]
+    fn test_action_lookup_by_file_name() {
+        let (dir, mut workspace) = workspace_with(&[("web", "Set Up CI"), ("web", "deploy")]);
+        
+        assert_eq!(workspace.get_action("web", "set_up_ci.md").unwrap().title(), "Set Up CI");
+        assert_eq!(workspace.get_action("web", "deploy.md").unwrap().title(), "deploy");
+        workspace.set_action_priority("web", "set_up_ci.md", Priority::High).unwrap();
+        assert!(workspace.get_action("web", "deploy.txt").is_err());
+        
+        let mut lazy = Workspace::load_lazy(dir.path()).unwrap();
+        assert_eq!(lazy.load_action("web", "set_up_ci.md").unwrap().priority(), Priority::High);
+        assert!(lazy.load_action("web", "../web/deploy.md").is_err());
+    }
+    
+    #[test]
+    fn test_load_errors_are_collected_and_reported() {
+        let (dir, _) = workspace_with(&[("web", "setup"), ("api", "auth")]);
+        fs::write(dir.path().join("web/broken.md"), "---\nid: 1\nnot a pair\n---\n").unwrap();
+        fs::write(dir.path().join("api/open.md"), "---\nid: 1\nnot a pair\n---\n").unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let failed: Vec<PathBuf> = workspace.load_errors().into_iter().map(|(path, _)| path).collect();
+        assert_eq!(failed, [dir.path().join("api/open.md"), dir.path().join("web/broken.md")]);
+        
+        let issues = workspace.check();
+        let parse_errors: Vec<&ValidationIssue> = issues.iter().filter(|issue| issue.code == "parse-error").collect();
+        assert_eq!(parse_errors.len(), 2);
+        assert!(parse_errors.iter().all(|issue| issue.severity == Severity::Error));
+        assert!(parse_errors[1].message.contains("broken.md"), "{}", parse_errors[1].message);
+    }
+    
+    #[test]
+    fn test_lazy_load_reads_actions_on_demand() {
+        let (dir, _) = workspace_with(&[("web", "setup"), ("web", "deploy"), ("api", "auth")]);
+        
+        let mut workspace = Workspace::load_lazy(dir.path()).unwrap();
+        assert_eq!(workspace.projects().len(), 2);
+        assert!(workspace.get_action("web", "setup").is_err());
+        
+        assert_eq!(workspace.load_action("web", "setup").unwrap().title(), "setup");
+        assert!(workspace.get_action("web", "setup").is_ok());
+        assert!(workspace.get_action("web", "deploy").is_err());
+        
+        // Served from the cache once loaded
+        fs::remove_file(dir.path().join("web/setup.md")).unwrap();
+        assert!(workspace.load_action("web", "setup").is_ok());
+        
+        assert!(workspace.load_action("web", "missing").is_err());
+        assert!(workspace.load_action("web", "README").is_err());
+        assert!(workspace.load_action("web", "../api/auth").is_err());
+        assert!(workspace.load_action("nope", "setup").is_err());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_load_terminates_with_symlink_cycles() {
+        use std::os::unix::fs::symlink;
+        
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("secret.md"), "# Secret\n").unwrap();
+        
+        let (dir, _) = workspace_with(&[("web", "setup")]);
+        let root = dir.path();
+        symlink(root, root.join("loop")).unwrap();
+        symlink(root.join("web"), root.join("alias")).unwrap();
+        symlink(root.join("web"), root.join("web/nested")).unwrap();
+        symlink(outside.path(), root.join("escape")).unwrap();
+        symlink(outside.path().join("secret.md"), root.join("web/secret.md")).unwrap();
+        
+        let workspace = Workspace::load(root).unwrap();
+        let names: Vec<&String> = workspace.projects().keys().collect();
+        assert_eq!(names, ["web"]);
+        let titles: Vec<&str> = workspace.get_project("web").unwrap()
+            .list_actions().iter().map(|a| a.title()).collect();
+        assert_eq!(titles, ["setup"]);
+        
+        // With symlinks disabled, the alias is skipped quietly as well
+        let config = Config { follow_symlinks: false, ..Default::default() };
+        config.save(root).unwrap();
+        let workspace = Workspace::load(root).unwrap();
+        assert_eq!(workspace.projects().len(), 1);
+    }
+    
+    #[test]
+    fn test_advance_project_respects_gates() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "a"), ("web", "b"), ("web", "c"), ("api", "d")]);
+        {
+            let project = workspace.projects.get_mut("web").unwrap();
+            project.get_action_mut("b").unwrap().update_section("notes", None);
+            project.get_action_mut("c").unwrap().set_status(Status::Published);
+        }
+        
+        let preview = workspace.advance_project("web", true, false).unwrap();
+        assert_eq!(preview, [
+            AdvanceOutcome::Advanced { title: "a".to_string(), from: Status::Discovery, to: Status::Design },
+            AdvanceOutcome::Blocked {
+                title: "b".to_string(),
+                status: Status::Discovery,
+                missing: vec!["Notes".to_string()],
+            },
+            AdvanceOutcome::Finished { title: "c".to_string() },
+        ]);
+        assert_eq!(workspace.get_action("web", "a").unwrap().status(), &Status::Discovery);
+        
+        workspace.advance_project("web", false, false).unwrap();
+        assert_eq!(workspace.get_action("web", "a").unwrap().status(), &Status::Design);
+        assert_eq!(workspace.get_action("web", "b").unwrap().status(), &Status::Discovery);
+        assert_eq!(workspace.get_action("api", "d").unwrap().status(), &Status::Discovery);
+        assert!(workspace.advance_project("missing", false, false).is_err());
+    }
+    
+    #[test]
+    fn test_resolved_links() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy"), ("api", "auth")]);
+        let inputs = "- [Deploy](./deploy.md)\n- [Auth](../api/auth.md)\n- [Rooted](/api/auth.md)\n\
+                      - [Bare](deploy.md)\n- [Gone](./gone.md)\n- [Remote](https://example.com/x.md)";
+        workspace.projects.get_mut("web").unwrap().get_action_mut("setup").unwrap()
+            .update_section("statement of inputs", Some(inputs.to_string()));
+        
+        let setup = workspace.get_action("web", "setup").unwrap();
+        let links = workspace.resolved_links(setup);
+        let resolved: Vec<(&str, Option<String>)> = links.iter()
+            .map(|link| (
+                link.text.as_str(),
+                link.resolved.as_ref().map(|t| format!("{}/{}", t.project, t.title)),
+            ))
+            .collect();
+        
+        assert_eq!(resolved, [
+            ("Deploy", Some("web/deploy".to_string())),
+            ("Auth", Some("api/auth".to_string())),
+            ("Rooted", Some("api/auth".to_string())),
+            ("Bare", Some("web/deploy".to_string())),
+            ("Gone", None),
+            ("Remote", None),
+        ]);
+        assert_eq!(links[0].target, "./deploy.md");
+        assert_eq!(workspace.linked_actions(setup).len(), 4);
+    }
+    
+    #[test]
+    fn test_rollup_uses_least_advanced_child_and_rejects_cycles() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "epic"), ("web", "a"), ("api", "b")]);
+        let set_children = |workspace: &mut Workspace, project: &str, title: &str, children: &str| {
+            workspace.projects.get_mut(project).unwrap().get_action_mut(title).unwrap()
+                .update_section("children", Some(children.to_string()));
+        };
+        set_children(&mut workspace, "web", "epic", "- [A](a.md)\n- [B](../api/b.md)\n- [Gone](gone.md)");
+        workspace.projects.get_mut("web").unwrap().get_action_mut("a").unwrap().set_status(Status::Published);
+        workspace.projects.get_mut("api").unwrap().get_action_mut("b").unwrap().set_status(Status::Test);
+        
+        let rollup = workspace.rollup("web", "epic").unwrap();
+        assert_eq!(rollup.children.len(), 2);
+        assert_eq!(rollup.published(), 1);
+        assert_eq!(rollup.unresolved, ["gone.md"]);
+        assert_eq!(rollup.status, Some(Status::Test));
+        
+        assert_eq!(workspace.rollup("web", "a").unwrap().status, None);
+        
+        set_children(&mut workspace, "api", "b", "[Epic](../web/epic.md)");
+        let error = workspace.rollup("web", "epic").unwrap_err().to_string();
+        assert_eq!(error, "Cycle in children: web/epic -> api/b -> web/epic");
+    }
+    
+    #[test]
+    fn test_concurrent_creates_of_the_same_title_have_one_winner() {
+        let (dir, mut workspace) = workspace_with(&[]);
+        workspace.create_action("web", "seed", Priority::None).unwrap();
+        
+        let handles: Vec<_> = (0..16)
+            .map(|_| {
+                let root = dir.path().to_path_buf();
+                std::thread::spawn(move || {
+                    let mut workspace = Workspace::load(&root).unwrap();
+                    workspace.create_action("web", "race", Priority::None).map_err(|e| e.to_string())
+                })
+            })
+            .collect();
+        
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        for error in results.iter().filter_map(|r| r.as_ref().err()) {
+            assert_eq!(error, "Action 'race' already exists in project 'web'");
+        }
+    }
+    
+    #[test]
+    fn test_blank_actions_have_no_sections() {
+        let (dir, mut workspace) = workspace_with(&[]);
+        let options = CreateOptions { blank: true, ..Default::default() };
+        workspace.create_action_with("web", "setup", &options).unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let setup = workspace.get_action("web", "setup").unwrap();
+        assert!(SECTION_NAMES.iter().all(|name| setup.section(name).is_none()));
+        assert!(workspace.missing_outputs(setup, setup.status()).contains(&"Statement of Action".to_string()));
+    }
+    
+    #[test]
+    fn test_blocked_and_cancelled_actions_leave_the_pipeline() {
+        let (dir, mut workspace) = workspace_with(&[("web", "a"), ("web", "b"), ("web", "c")]);
+        workspace.set_action_lifecycle("web", "b", Lifecycle::Blocked).unwrap();
+        workspace.set_action_lifecycle("web", "c", Lifecycle::Cancelled).unwrap();
+        
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        let titles = |filter: &ActionFilter, workspace: &Workspace| -> Vec<String> {
+            workspace.filter_actions(filter).unwrap().iter().map(|a| a.title().to_string()).collect()
+        };
+        assert_eq!(titles(&ActionFilter::default(), &workspace), ["a", "b"]);
+        let cancelled = ActionFilter { lifecycle: Some(Lifecycle::Cancelled), ..Default::default() };
+        assert_eq!(titles(&cancelled, &workspace), ["c"]);
+        
+        let error = workspace.update_action_status("web", "b", Status::Design).unwrap_err();
+        assert!(error.to_string().contains("is blocked"), "{}", error);
+        
+        let outcomes = workspace.advance_project("web", true, false).unwrap();
+        assert!(outcomes.contains(&AdvanceOutcome::Inactive { title: "b".to_string(), lifecycle: Lifecycle::Blocked }));
+        assert!(outcomes.contains(&AdvanceOutcome::Inactive { title: "c".to_string(), lifecycle: Lifecycle::Cancelled }));
+        
+        workspace.set_action_lifecycle("web", "b", Lifecycle::Active).unwrap();
+        workspace.update_action_status("web", "b", Status::Design).unwrap();
+    }
+    
+    #[test]
+    fn test_meta_graph_markdown_is_not_loaded_as_actions() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        let sibling = workspace.create_meta_graph("web", "setup").unwrap();
+        fs::write(sibling.join("setup.md"), "# Nested\n").unwrap();
+        fs::write(sibling.join("notes.md"), "# Notes\n").unwrap();
+        
+        workspace.migrate_meta_graphs(MetaGraphLayout::Hidden).unwrap();
+        let hidden = workspace.create_meta_graph("web", "deploy").unwrap();
+        fs::write(hidden.join("plan.md"), "# Plan\n").unwrap();
+        fs::create_dir(dir.path().join("web/setup")).unwrap();
+        fs::write(dir.path().join("web/setup/stray.md"), "# Stray\n").unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let titles: Vec<&str> = workspace.get_project("web").unwrap()
+            .list_actions()
+            .iter()
+            .map(|action| action.title())
+            .collect();
+        assert_eq!(titles, ["deploy", "setup"]);
+        assert_eq!(workspace.get_action("web", "setup").unwrap().file_path, dir.path().join("web/setup.md"));
+    }
+    
+    #[test]
+    fn test_project_description_from_readme() {
+        let (dir, _) = workspace_with(&[("web", "setup"), ("api", "auth")]);
+        fs::write(
+            dir.path().join("web/README.md"),
+            "# Web\n\n## About\n\nThe public\nwebsite.\n\nMore detail.\n",
+        ).unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let web = workspace.get_project("web").unwrap();
+        assert_eq!(web.description().as_deref(), Some("The public website."));
+        assert_eq!(web.list_actions().len(), 1, "the README is not an action");
+        assert_eq!(workspace.get_project("api").unwrap().description(), None);
+    }
+    
+    #[test]
+    fn test_load_outside_a_workspace_is_a_typed_error() {
+        let dir = TempDir::new().unwrap();
+        let error = Workspace::load(dir.path()).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<WorkspaceError>(),
+            Some(WorkspaceError::NotAWorkspace(path)) if path == dir.path()
+        ));
+        assert!(error.to_string().starts_with("Not an Action Lite workspace. Run 'action init'"));
+    }
+    
+    #[test]
+    fn test_tag_index_counts_and_co_occurrence() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "a"), ("web", "b"), ("api", "c")]);
+        let tag = |workspace: &mut Workspace, project: &str, title: &str, notes: &str| {
+            workspace.projects.get_mut(project).unwrap().get_action_mut(title).unwrap()
+                .update_section("notes", Some(notes.to_string()));
+        };
+        tag(&mut workspace, "web", "a", "#ui #auth");
+        tag(&mut workspace, "web", "b", "#ui #perf #ui");
+        tag(&mut workspace, "api", "c", "#auth #perf #backend");
+        workspace.projects.get_mut("web").unwrap().get_action_mut("a").unwrap().set_priority(Priority::High);
+        
+        let index = workspace.tag_index();
+        assert_eq!(index.counts(), [("auth", 2), ("perf", 2), ("ui", 2), ("backend", 1)]);
+        assert_eq!(index.actions["ui"], [
+            ("web".to_string(), "a".to_string()),
+            ("web".to_string(), "b".to_string()),
+        ]);
+        assert_eq!(index.related("auth"), [("backend", 1), ("perf", 1), ("ui", 1)]);
+        assert_eq!(index.related("ui"), [("auth", 1), ("perf", 1)]);
+        assert!(index.related("missing").is_empty());
+    }
+    
+    #[test]
+    fn test_meta_graph_links_follow_a_moved_action() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        let meta = workspace.create_meta_graph("web", "setup").unwrap();
+        fs::create_dir(meta.join("notes")).unwrap();
+        fs::write(meta.join("plan.md"), "[Parent](../setup.md) [Deploy](../deploy.md) [Notes](notes/a.md)").unwrap();
+        fs::write(meta.join("notes/a.md"), "[Parent](../../setup.md) [Plan](../plan.md)").unwrap();
+        
+        // Moving between layouts changes the depth of the meta-graph
+        workspace.migrate_meta_graphs(MetaGraphLayout::Hidden).unwrap();
+        let plan = fs::read_to_string(dir.path().join("web/.meta/setup/plan.md")).unwrap();
+        assert_eq!(plan, "[Parent](../../setup.md) [Deploy](../../deploy.md) [Notes](notes/a.md)");
+        
+        // Move the action and its meta-graph to another project by hand
+        let (old_action, new_action) = (dir.path().join("web/setup.md"), dir.path().join("api/setup.md"));
+        let (old_meta, new_meta) = (dir.path().join("web/.meta/setup"), dir.path().join("api/.meta/setup"));
+        fs::create_dir_all(new_meta.parent().unwrap()).unwrap();
+        fs::rename(&old_action, &new_action).unwrap();
+        fs::rename(&old_meta, &new_meta).unwrap();
+        assert_eq!(relink_meta_graph(&old_meta, &new_meta, &old_action, &new_action).unwrap(), 1);
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let sub = Action::from_file(new_meta.join("notes/a.md")).unwrap();
+        let parent = workspace.resolve_link(&sub, "../../../setup.md").unwrap();
+        assert_eq!((parent.project(), parent.title()), ("api", "setup"));
+        
+        let plan = fs::read_to_string(new_meta.join("plan.md")).unwrap();
+        assert_eq!(plan, "[Parent](../../setup.md) [Deploy](../../../web/deploy.md) [Notes](notes/a.md)");
+        assert_eq!(fs::read_to_string(new_meta.join("notes/a.md")).unwrap(), "[Parent](../../../setup.md) [Plan](../plan.md)");
+    }
+    
+    #[test]
+    fn test_filter_excludes_statuses() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy"), ("web", "docs")]);
+        workspace.update_action_status("web", "setup", Status::Published).unwrap();
+        workspace.update_action_status("web", "deploy", Status::Design).unwrap();
+        
+        let titles = |filter: &ActionFilter| -> Vec<String> {
+            workspace.filter_actions(filter).unwrap().iter().map(|a| a.title().to_string()).collect()
+        };
+        
+        let outstanding = ActionFilter { excluded_statuses: vec![Status::Published], ..Default::default() };
+        assert_eq!(titles(&outstanding), ["deploy", "docs"]);
+        
+        let early = ActionFilter {
+            excluded_statuses: vec![Status::Published, Status::Design],
+            ..Default::default()
+        };
+        assert_eq!(titles(&early), ["docs"]);
+        
+        let combined = ActionFilter {
+            status: Some(Status::Design),
+            excluded_statuses: vec![Status::Published],
+            ..Default::default()
+        };
+        assert_eq!(titles(&combined), ["deploy"]);
+        
+        let contradiction = ActionFilter {
+            status: Some(Status::Design),
+            excluded_statuses: vec![Status::Design],
+            ..Default::default()
+        };
+        assert!(workspace.filter_actions(&contradiction).is_err());
+    }
+    
+    #[test]
+    fn test_attachments() {
+        let (dir, workspace) = workspace_with(&[("web", "setup")]);
+        let source = dir.path().join("screenshot.png");
+        fs::write(&source, "png").unwrap();
+        
+        let first = workspace.attach("web", "setup", &source).unwrap();
+        let second = workspace.attach("web", "setup", &source).unwrap();
+        assert_eq!(first.file_name().unwrap(), "screenshot.png");
+        assert_eq!(second.file_name().unwrap(), "screenshot-1.png");
+        
+        // Sub-actions in the meta-graph are not attachments
+        let meta = workspace.create_meta_graph("web", "setup").unwrap();
+        fs::write(meta.join("step.md"), "# step").unwrap();
+        let action = workspace.get_action("web", "setup").unwrap();
+        assert_eq!(action.attachments(), [second, first.clone()]);
+        
+        assert!(workspace.attach("web", "setup", &dir.path().join("missing.png")).is_err());
+        assert!(workspace.attach("web", "setup", &meta.join("step.md")).is_err());
+        
+        assert_eq!(workspace.detach("web", "setup", "screenshot-1.png").unwrap().file_name().unwrap(), "screenshot-1.png");
+        assert!(workspace.detach("web", "setup", "step.md").is_err());
+        assert_eq!(action.attachments(), [first]);
+    }
+    
+    #[test]
+    fn test_filter_by_status_range_is_inclusive() {
+        let statuses = Status::all();
+        let actions: Vec<(&str, String)> = statuses.iter().map(|status| ("web", status.to_string())).collect();
+        let actions: Vec<(&str, &str)> = actions.iter().map(|(project, title)| (*project, title.as_str())).collect();
+        let (_dir, mut workspace) = workspace_with(&actions);
+        for status in &statuses {
+            workspace.update_action_status("web", &status.to_string(), status.clone()).unwrap();
+        }
+        
+        let range = |min: Option<Status>, max: Option<Status>| -> Vec<Status> {
+            let filter = ActionFilter { min_status: min, max_status: max, ..Default::default() };
+            let mut found: Vec<Status> = workspace.filter_actions(&filter).unwrap()
+                .iter().map(|a| a.status().clone()).collect();
+            found.sort();
+            found
+        };
+        
+        for (i, status) in statuses.iter().enumerate() {
+            assert_eq!(range(Some(status.clone()), None), statuses[i..]);
+            assert_eq!(range(None, Some(status.clone())), statuses[..=i]);
+            assert_eq!(range(Some(status.clone()), Some(status.clone())), statuses[i..=i]);
+        }
+        assert_eq!(range(Some(Status::Implement), Some(Status::Document)), [Status::Implement, Status::Test, Status::Document]);
+        
+        let inverted = ActionFilter {
+            min_status: Some(Status::Test),
+            max_status: Some(Status::Design),
+            ..Default::default()
+        };
+        assert!(workspace.filter_actions(&inverted).is_err());
+    }
+    
+    #[test]
+    fn test_duplicate_ids() {
+        let (dir, _) = workspace_with(&[("web", "setup")]);
+        let original = fs::read_to_string(dir.path().join("web/setup.md")).unwrap();
+        let copy: Vec<String> = original.replace("# setup", "# copy")
+            .lines()
+            .map(|line| if line.starts_with("created_at:") {
+                "created_at: 2999-01-01T00:00:00+00:00".to_string()
+            } else {
+                line.to_string()
+            })
+            .collect();
+        fs::write(dir.path().join("web/copy.md"), copy.join("\n")).unwrap();
+        
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        let duplicates = workspace.duplicate_ids();
+        assert_eq!(duplicates.len(), 1);
+        let titles: Vec<&str> = duplicates[0].1.iter().map(|a| a.title()).collect();
+        assert_eq!(titles, ["setup", "copy"], "oldest first");
+        
+        let issues: Vec<ValidationIssue> = workspace.check().into_iter()
+            .filter(|issue| issue.code == "duplicate-id")
+            .collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].title.as_deref(), Some("copy"));
+        assert!(issues[0].message.contains("web/setup.md") && issues[0].message.contains("web/copy.md"));
+        
+        let id = workspace.get_action("web", "setup").unwrap().id.clone();
+        assert_eq!(workspace.fix_duplicate_ids().unwrap(), [("web".to_string(), "copy".to_string())]);
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        assert!(workspace.duplicate_ids().is_empty());
+        assert_eq!(workspace.get_action("web", "setup").unwrap().id, id);
+    }
+    
+    #[test]
+    fn test_entry_gates() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        workspace.update_action_status("web", "setup", Status::Design).unwrap();
+        workspace.update_action_status("web", "deploy", Status::Design).unwrap();
+        workspace.config.entry_requirements.insert("implement".to_string(), vec!["Statement of Design".to_string()]);
+        
+        // Present but still the placeholder text doesn't count
+        let placeholder = template::default_placeholder("Statement of Design").to_string();
+        let project = workspace.projects.get_mut("web").unwrap();
+        project.get_action_mut("setup").unwrap().update_section("statement of design", Some(placeholder));
+        project.get_action_mut("deploy").unwrap().update_section("statement of design", Some("Blue/green".to_string()));
+        
+        let err = workspace.update_action_status("web", "setup", Status::Implement).unwrap_err();
+        assert!(err.to_string().contains("Statement of Design must be filled in"), "{}", err);
+        workspace.update_action_status("web", "deploy", Status::Implement).unwrap();
+        
+        // Moving backwards isn't gated
+        workspace.update_action_status("web", "deploy", Status::Discovery).unwrap();
+        workspace.update_action_status("web", "deploy", Status::Design).unwrap();
+        
+        let outcomes = workspace.advance_project("web", true, false).unwrap();
+        assert!(outcomes.iter().any(|outcome| matches!(outcome,
+            AdvanceOutcome::Blocked { title, missing, .. } if title == "setup" && missing == &["Statement of Design"]
+        )));
+        assert!(outcomes.iter().any(|outcome| matches!(outcome, AdvanceOutcome::Advanced { title, .. } if title == "deploy")));
+    }
+    
+    #[test]
+    fn test_format_actions() {
+        let (dir, workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        assert!(workspace.format_changes(None).unwrap().is_empty(), "files written by the tool are canonical");
+        
+        let path = dir.path().join("web/setup.md");
+        let canonical = fs::read_to_string(&path).unwrap();
+        fs::write(&path, canonical.replace("## Notes\n\n", "## Notes\n\n\n\n")).unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let changes = workspace.format_actions(None, true).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].diff(dir.path()).starts_with("--- a/web/setup.md"));
+        assert_ne!(fs::read_to_string(&path).unwrap(), canonical, "dry run writes nothing");
+        
+        workspace.format_actions(Some("web"), false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), canonical);
+    }
+    
+    #[test]
+    fn test_meta_graph_moves_preview() {
+        let (dir, workspace) = workspace_with(&[("web", "setup")]);
+        let meta = workspace.create_meta_graph("web", "setup").unwrap();
+        fs::write(meta.join("plan.md"), "[Parent](../setup.md)").unwrap();
+        
+        let moves = workspace.meta_graph_moves(MetaGraphLayout::Hidden).unwrap();
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].to, dir.path().join("web/.meta/setup"));
+        assert_eq!(moves[0].changes[0].path, dir.path().join("web/.meta/setup/plan.md"));
+        assert_eq!(moves[0].changes[0].new, "[Parent](../../setup.md)");
+        assert!(meta.join("plan.md").is_file(), "previewing moves nothing");
+    }
+    
+    #[test]
+    fn test_near_duplicates() {
+        let (_dir, mut workspace) = workspace_with(&[
+            ("web", "setup ci pipeline"),
+            ("web", "set up CI pipeline"),
+            ("web", "write docs"),
+            ("api", "setup_ci_pipeline"),
+        ]);
+        let project = workspace.projects.get_mut("web").unwrap();
+        project.get_action_mut("setup ci pipeline").unwrap()
+            .update_section("statement of action", Some("Run tests on every push".to_string()));
+        project.get_action_mut("set up CI pipeline").unwrap()
+            .update_section("statement of action", Some("Run the tests on each push".to_string()));
+        
+        let pairs = workspace.near_duplicates(0.8, false);
+        let names: Vec<(&str, &str)> = pairs.iter().map(|(_, a, b)| (a.title(), b.title())).collect();
+        assert_eq!(names, [("set up CI pipeline", "setup ci pipeline")]);
+        assert!(pairs[0].0 < 1.0);
+        
+        // The untouched api action only has the placeholder statement, so only titles count
+        let pairs = workspace.near_duplicates(0.8, true);
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[0].0, 1.0);
+        assert!(pairs.windows(2).all(|pair| pair[0].0 >= pair[1].0));
+        
+        assert_eq!(workspace.near_duplicates(0.0, true).len(), 6);
+    }
+    
+    #[test]
+    fn test_read_only_workspace_refuses_writes() {
+        let (dir, workspace) = workspace_with(&[("web", "setup")]);
+        fs::write(dir.path().join("shot.png"), "png").unwrap();
+        let before = fs::read_to_string(dir.path().join("web/setup.md")).unwrap();
+        let mut workspace = workspace.with_read_only(true);
+        
+        let is_read_only = |result: Result<()>| {
+            matches!(result.unwrap_err().downcast_ref::<WorkspaceError>(), Some(WorkspaceError::ReadOnly))
+        };
+        assert!(is_read_only(workspace.create_action("web", "deploy", Priority::None).map(|_| ())));
+        assert!(is_read_only(workspace.update_action_status("web", "setup", Status::Design)));
+        assert!(is_read_only(workspace.set_action_lifecycle("web", "setup", Lifecycle::Blocked)));
+        assert!(is_read_only(workspace.set_action_priority("web", "setup", Priority::High)));
+        assert!(is_read_only(workspace.comment_on_action("web", "setup", "bob", "hi")));
+        assert!(is_read_only(workspace.edit_action("web", "setup")));
+        assert!(is_read_only(workspace.create_meta_graph("web", "setup").map(|_| ())));
+        assert!(is_read_only(workspace.attach("web", "setup", &dir.path().join("shot.png")).map(|_| ())));
+        assert!(is_read_only(workspace.detach("web", "setup", "shot.png").map(|_| ())));
+        assert!(is_read_only(workspace.migrate_meta_graphs(MetaGraphLayout::Hidden).map(|_| ())));
+        assert!(is_read_only(workspace.fix_duplicate_ids().map(|_| ())));
+        assert!(is_read_only(workspace.advance_project("web", false, false).map(|_| ())));
+        assert!(is_read_only(workspace.format_actions(None, false).map(|_| ())));
+        
+        // Previews still work
+        assert!(workspace.advance_project("web", true, false).is_ok());
+        assert!(workspace.format_actions(None, true).is_ok());
+        
+        assert_eq!(fs::read_to_string(dir.path().join("web/setup.md")).unwrap(), before);
+        assert!(!dir.path().join("web/deploy.md").exists());
+        assert!(!dir.path().join("web/setup").exists());
+    }
+    
+    #[test]
+    fn test_checked_status_changes_follow_the_workflow() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup")]);
+        
+        let error = workspace.update_action_status_checked("web", "setup", Status::Test, true, false).unwrap_err();
+        assert!(error.to_string().contains("from discovery to test: that skips design, implement"), "{}", error);
+        assert_eq!(workspace.get_action("web", "setup").unwrap().status(), &Status::Discovery);
+        
+        // A fresh action has what discovery needs, so it can move to design
+        let added = workspace.update_action_status_checked("web", "setup", Status::Design, false, false).unwrap();
+        assert!(added.is_empty());
+        let error = workspace.update_action_status_checked("web", "setup", Status::Implement, true, false).unwrap_err();
+        assert!(error.to_string().contains("from design to implement: Statement of Design is missing"), "{}", error);
+        
+        // Leaving design needs its output; moving back needs nothing
+        workspace.set_action_section("web", "setup", "Statement of Design", Some("A small service".to_string())).unwrap();
+        workspace.update_action_status_checked("web", "setup", Status::Implement, true, false).unwrap();
+        workspace.update_action_status("web", "setup", Status::Discovery).unwrap();
+        workspace.update_action_status_checked("web", "setup", Status::Design, true, false).unwrap();
+        
+        // --force skips the entry gates as well
+        workspace.config.entry_requirements.insert("implement".to_string(), vec!["Analysis of Impact".to_string()]);
+        let error = workspace.update_action_status_checked("web", "setup", Status::Implement, true, false).unwrap_err();
+        assert!(error.to_string().contains("Analysis of Impact must be filled in first"), "{}", error);
+        workspace.update_action_status_checked("web", "setup", Status::Implement, true, true).unwrap();
+        workspace.update_action_status_checked("web", "setup", Status::Published, false, true).unwrap();
+        assert_eq!(workspace.get_action("web", "setup").unwrap().status(), &Status::Published);
+    }
+    
+    #[test]
+    fn test_status_changes_scaffold_required_outputs() {
+        for status in Status::all() {
+            let (_dir, mut workspace) = workspace_with(&[]);
+            let options = CreateOptions { blank: true, ..Default::default() };
+            workspace.create_action_with("web", "setup", &options).unwrap();
+            
+            let added = workspace.update_action_status_with("web", "setup", status.clone(), true).unwrap();
+            let expected: Vec<&str> = status.required_outputs()
+                .into_iter()
+                .filter(|section| Action::is_known_section(section))
+                .collect();
+            assert_eq!(added, expected, "{}", status);
+            
+            let action = workspace.get_action("web", "setup").unwrap();
+            assert!(workspace.missing_outputs(action, &status).is_empty(), "{}", status);
+            for section in &added {
+                assert_eq!(action.section(section).unwrap(), template::default_placeholder(section));
+            }
+        }
+    }
+    
+    #[test]
+    fn test_scaffolding_keeps_existing_sections() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup")]);
+        let project = workspace.projects.get_mut("web").unwrap();
+        project.get_action_mut("setup").unwrap()
+            .update_section("statement of design", Some("Already designed".to_string()));
+        
+        assert!(workspace.update_action_status_with("web", "setup", Status::Design, true).unwrap().is_empty());
+        let action = workspace.get_action("web", "setup").unwrap();
+        assert_eq!(action.statement_of_design().unwrap(), "Already designed");
+        
+        workspace.update_action_status("web", "setup", Status::Document).unwrap();
+        assert!(workspace.get_action("web", "setup").unwrap().analysis_of_impact().is_none(), "off unless asked for");
+    }
+    
+    #[test]
+    fn test_retag_filtered_actions() {
+        let (dir, mut workspace) = workspace_with(&[("web", "a"), ("web", "b"), ("api", "c")]);
+        let web = ActionFilter { project: Some("web".to_string()), ..Default::default() };
+        
+        let changed = workspace.retag(&web, None, Some("#q3"), false).unwrap();
+        assert_eq!(changed, [("web".to_string(), "a".to_string()), ("web".to_string(), "b".to_string())]);
+        assert!(workspace.retag(&web, None, Some("q3"), false).unwrap().is_empty(), "already tagged");
+        
+        let preview = workspace.retag(&ActionFilter::default(), Some("q3"), Some("q4"), true).unwrap();
+        assert_eq!(preview.len(), 2, "only actions with the old tag are renamed");
+        assert_eq!(workspace.get_action("web", "a").unwrap().tag_line, ["q3"], "dry run leaves actions alone");
+        
+        workspace.retag(&ActionFilter::default(), Some("q3"), Some("q4"), false).unwrap();
+        let reloaded = Workspace::load(dir.path()).unwrap();
+        assert_eq!(reloaded.get_action("web", "b").unwrap().tag_line, ["q4"]);
+        assert!(reloaded.get_action("api", "c").unwrap().tag_line.is_empty());
+        
+        assert!(workspace.retag(&web, None, Some("bad tag"), false).is_err());
+        assert!(workspace.retag(&web, None, Some("discovery"), false).is_err());
+        assert!(workspace.retag(&web, None, Some("web"), false).is_err());
+    }
+    
+    #[test]
+    fn test_init_writes_gitignore_unless_opted_out() {
+        let dir = TempDir::new().unwrap();
+        Workspace::init(dir.path()).unwrap();
+        let gitignore = fs::read_to_string(dir.path().join(".action-lite/.gitignore")).unwrap();
+        assert!(gitignore.lines().any(|line| line == "journal.jsonl"));
+        
+        let dir = TempDir::new().unwrap();
+        let options = InitOptions { no_gitignore: true, ..Default::default() };
+        let mut workspace = Workspace::init_with(dir.path(), &options).unwrap();
+        assert!(!dir.path().join(".action-lite/.gitignore").exists());
+        assert!(!Config::load(dir.path()).unwrap().gitignore);
+        workspace.migrate_meta_graphs(MetaGraphLayout::Hidden).unwrap();
+        assert!(!dir.path().join(".action-lite/.gitignore").exists());
+    }
+    
+    #[test]
+    fn test_dependencies_and_backlinks() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy"), ("api", "auth"), ("api", "docs")]);
+        let mut set_section = |project: &str, title: &str, section: &str, content: &str| {
+            workspace.projects.get_mut(project).unwrap().get_action_mut(title).unwrap()
+                .update_section(section, Some(content.to_string()));
+        };
+        set_section("web", "setup", "statement of inputs", "- [Auth](../api/auth.md)\n- [Again](/api/auth.md)\n- [Gone](gone.md)");
+        set_section("web", "setup", "notes", "See [Deploy](deploy.md)");
+        set_section("web", "deploy", "notes", "After [Setup](setup.md)");
+        set_section("api", "docs", "statement of inputs", "- [Setup](../web/setup.md)");
+        
+        let names = |actions: Vec<&Action>| -> Vec<String> {
+            actions.iter().map(|a| format!("{}/{}", a.project(), a.title())).collect()
+        };
+        let setup = workspace.get_action("web", "setup").unwrap();
+        assert_eq!(names(workspace.dependencies(setup)), ["api/auth"], "links outside the inputs aren't dependencies");
+        assert_eq!(names(workspace.backlinks(setup)), ["api/docs", "web/deploy"]);
+        
+        let auth = workspace.get_action("api", "auth").unwrap();
+        assert!(workspace.dependencies(auth).is_empty());
+        assert_eq!(names(workspace.backlinks(auth)), ["web/setup"]);
+    }
+    
+    #[test]
+    fn test_id_scheme_applies_to_new_actions_only() {
+        let (dir, mut workspace) = workspace_with(&[("web", "legacy")]);
+        workspace.config.id_scheme = config::IdScheme::Seq;
+        workspace.config.save(dir.path()).unwrap();
+        
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        workspace.create_action("web", "a", Priority::None).unwrap();
+        workspace.create_action("web", "b", Priority::None).unwrap();
+        workspace.create_action("api", "c", Priority::None).unwrap();
+        
+        let reloaded = Workspace::load(dir.path()).unwrap();
+        let id = |project: &str, title: &str| reloaded.get_action(project, title).unwrap().id.to_string();
+        assert_eq!(id("web", "a"), "web-1");
+        assert_eq!(id("web", "b"), "web-2");
+        assert_eq!(id("api", "c"), "api-1");
+        assert!(Uuid::parse_str(&id("web", "legacy")).is_ok(), "existing UUIDs still load");
+    }
+    
+    #[test]
+    fn test_default_sections_expand_placeholders() {
+        let (_dir, mut workspace) = workspace_with(&[]);
+        std::env::set_var("ACTION_LITE_TEST_OWNER", "alice");
+        workspace.config.default_sections = BTreeMap::from([
+            ("notes".to_string(), "Owner: {{env:ACTION_LITE_TEST_OWNER}} ({{project}}/{{ title }}) {{unknown}} {title}".to_string()),
+            ("Statement of Design".to_string(), "TBD".to_string()),
+        ]);
+        
+        let action = workspace.create_action("web", "setup", Priority::None).unwrap();
+        assert_eq!(action.notes().unwrap(), "Owner: alice (web/setup) {{unknown}} {title}");
+        assert_eq!(action.statement_of_design().unwrap(), "TBD");
+        
+        let blank = CreateOptions { blank: true, ..Default::default() };
+        assert!(workspace.create_action_with("web", "bare", &blank).unwrap().notes().is_none());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_on_create_hook_failure_keeps_action_unless_rolled_back() {
+        let (dir, mut workspace) = workspace_with(&[]);
+        workspace.config.hooks.on_create = Some("echo \"$ACTION_TITLE\" >> created.log".to_string());
+        workspace.create_action("web", "a", Priority::None).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("created.log")).unwrap(), "a\n");
+        
+        workspace.config.hooks.on_create = Some("exit 1".to_string());
+        workspace.create_action("web", "b", Priority::None).unwrap();
+        assert!(workspace.get_action("web", "b").is_ok());
+        
+        let rollback = CreateOptions { rollback_on_hook_failure: true, ..Default::default() };
+        let error = workspace.create_action_with("web", "c", &rollback).unwrap_err();
+        assert!(format!("{:#}", error).contains("on_create hook failed"), "{:#}", error);
+        assert!(workspace.get_action("web", "c").is_err());
+        assert!(!dir.path().join("web/c.md").exists());
+    }
+    
+    #[test]
+    fn test_delete_action_removes_file_and_meta_graph() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        let meta_graph = workspace.create_meta_graph("web", "setup").unwrap();
+        fs::write(meta_graph.join("notes.txt"), "x").unwrap();
+        
+        let deleted = workspace.delete_action("web", "setup").unwrap();
+        assert_eq!(deleted.title(), "setup");
+        assert!(!dir.path().join("web/setup.md").exists());
+        assert!(!meta_graph.exists());
+        assert!(workspace.get_action("web", "setup").is_err());
+        
+        let error = workspace.delete_action("web", "setup").unwrap_err();
+        assert!(error.to_string().contains("not found"), "{}", error);
+        assert_eq!(Workspace::load(dir.path()).unwrap().list_actions(None, None, false).unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn test_rename_action_moves_file_and_meta_graph() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        let meta_graph = workspace.create_meta_graph("web", "setup").unwrap();
+        fs::write(meta_graph.join("design.md"), "Back to [setup](../setup.md)\n").unwrap();
+        let id = workspace.get_action("web", "setup").unwrap().id.clone();
+        
+        assert!(workspace.rename_action("web", "setup", "deploy").is_err());
+        workspace.rename_action("web", "setup", "Dev Setup").unwrap();
+        assert!(workspace.rename_action("web", "missing", "other").is_err());
+        
+        let new_file = dir.path().join("web/dev_setup.md");
+        assert!(!dir.path().join("web/setup.md").exists());
+        assert!(fs::read_to_string(&new_file).unwrap().contains("# Dev Setup\n"));
+        let design = fs::read_to_string(dir.path().join("web/dev_setup/design.md")).unwrap();
+        assert_eq!(design, "Back to [setup](../dev_setup.md)\n");
+        
+        let renamed = workspace.get_action("web", "Dev Setup").unwrap();
+        assert_eq!(renamed.file_path, new_file);
+        assert_eq!(renamed.id, id);
+        assert!(Workspace::load(dir.path()).unwrap().get_action("web", "dev_setup").is_ok());
+    }
+    
+    #[test]
+    fn test_move_action_between_projects() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy"), ("api", "deploy")]);
+        let meta_graph = workspace.create_meta_graph("web", "setup").unwrap();
+        fs::write(meta_graph.join("design.md"), "Back to [setup](../setup.md)\n").unwrap();
+        
+        assert!(workspace.move_action("web", "deploy", "api").unwrap_err().to_string().contains("already exists"));
+        assert!(workspace.move_action("web", "setup", "web").is_err());
+        assert!(workspace.move_action("web", "missing", "api").is_err());
+        
+        workspace.move_action("web", "setup", "infra").unwrap();
+        assert!(!dir.path().join("web/setup.md").exists());
+        assert!(!meta_graph.exists());
+        let content = fs::read_to_string(dir.path().join("infra/setup.md")).unwrap();
+        assert!(content.contains("#project #infra #"), "{}", content);
+        assert!(dir.path().join("infra/setup/design.md").exists());
+        
+        let reloaded = Workspace::load(dir.path()).unwrap();
+        assert_eq!(reloaded.get_action("infra", "setup").unwrap().project(), "infra");
+        assert!(reloaded.get_action("web", "setup").is_err());
+    }
+    
+    #[test]
+    fn test_custom_workflow_from_config() {
+        let (dir, _) = workspace_with(&[]);
+        let config_path = Config::path(dir.path());
+        fs::write(&config_path, r#"
+            [[statuses]]
+            name = "todo"
+            required_outputs = ["Notes"]
+            
+            [[statuses]]
+            name = "review"
+            
+            [[statuses]]
+            name = "done"
+        "#).unwrap();
+        
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        let review = workspace.parse_status("review").unwrap();
+        assert!(workspace.parse_status("design").is_err());
+        
+        workspace.create_action("web", "a", Priority::None).unwrap();
+        workspace.create_action("web", "b", Priority::None).unwrap();
+        assert_eq!(workspace.get_action("web", "a").unwrap().status().to_string(), "todo");
+        
+        workspace.set_action_section("web", "b", "Notes", None).unwrap();
+        let outcomes = workspace.advance_project("web", false, false).unwrap();
+        assert_eq!(outcomes[0], AdvanceOutcome::Advanced {
+            title: "a".to_string(),
+            from: Status::named("todo"),
+            to: review.clone(),
+        });
+        assert!(matches!(&outcomes[1], AdvanceOutcome::Blocked { missing, .. } if missing == &["Notes"]));
+        
+        let filter = ActionFilter { min_status: Some(review), ..Default::default() };
+        let titles: Vec<&str> = workspace.filter_actions(&filter).unwrap().iter().map(|a| a.title()).collect();
+        assert_eq!(titles, ["a"]);
+        assert!(workspace.check().iter().all(|issue| issue.code != "unknown-status"));
+        
+        fs::write(&config_path, "[[statuses]]\nname = \"todo\"\n[[statuses]]\nname = \"TODO\"\n").unwrap();
+        let error = Workspace::load(dir.path()).unwrap_err();
+        assert!(format!("{:#}", error).contains("more than once"), "{:#}", error);
+    }
+    
+    #[test]
+    fn test_due_date_filters() {
+        let (dir, mut workspace) = workspace_with(&[("web", "late"), ("web", "later"), ("web", "none")]);
+        let past = crate::action::parse_due("2020-01-01").unwrap();
+        let future = crate::action::parse_due("2999-01-01").unwrap();
+        assert!(workspace.set_action_due("web", "late", Some(past)).unwrap().is_some(), "warns about a past date");
+        assert!(workspace.set_action_due("web", "later", Some(future)).unwrap().is_none());
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let titles = |filter: &ActionFilter| -> Vec<String> {
+            workspace.filter_actions(filter).unwrap().iter().map(|a| a.title().to_string()).collect()
+        };
+        assert_eq!(titles(&ActionFilter { overdue: true, ..Default::default() }), ["late"]);
+        let before = crate::action::parse_due("2100-01-01").unwrap();
+        assert_eq!(titles(&ActionFilter { due_before: Some(before), ..Default::default() }), ["late"]);
+        assert_eq!(titles(&ActionFilter { due_before: Some(future + chrono::Duration::days(1)), ..Default::default() }), ["late", "later"]);
+    }
+    
+    #[test]
+    fn test_assignee_is_saved_and_filtered() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy"), ("api", "auth")]);
+        workspace.set_action_assignee("web", "setup", Some(" Ana ")).unwrap();
+        workspace.set_action_assignee("api", "auth", Some("bo")).unwrap();
+        workspace.set_action_assignee("api", "auth", None).unwrap();
+        assert!(workspace.set_action_assignee("web", "deploy", Some("  ")).is_err());
+        assert!(workspace.set_action_assignee("web", "deploy", Some("Unassigned")).is_err());
+        
+        let content = fs::read_to_string(dir.path().join("web/setup.md")).unwrap();
+        assert!(content.contains("assignee: Ana\n"), "{}", content);
+        let workspace = Workspace::load(dir.path()).unwrap();
+        assert_eq!(workspace.get_action("web", "setup").unwrap().assignee.as_deref(), Some("Ana"));
+        assert!(workspace.get_action("web", "setup").unwrap().parse_warnings.is_empty());
+        
+        let titles = |assignee: &str| -> Vec<String> {
+            let filter = ActionFilter { assignee: Some(assignee.to_string()), ..Default::default() };
+            workspace.filter_actions(&filter).unwrap().iter().map(|a| a.title().to_string()).collect()
+        };
+        assert_eq!(titles("ana"), ["setup"]);
+        assert_eq!(titles(UNASSIGNED), ["auth", "deploy"]);
+        assert!(titles("bo").is_empty());
+    }
+    
+    #[test]
+    fn test_group_actions_by_orders_groups_and_counts_members() {
+        let (_dir, mut workspace) = workspace_with(&[("api", "auth"), ("api", "login"), ("web", "setup"), ("web", "deploy")]);
+        workspace.set_action_assignee("api", "auth", Some("bo")).unwrap();
+        workspace.set_action_assignee("web", "setup", Some("Ana")).unwrap();
+        workspace.set_action_assignee("web", "deploy", Some("Bo")).unwrap();
+        workspace.set_action_priority("web", "setup", Priority::High).unwrap();
+        workspace.update_action_status_with("api", "login", Status::Design, true).unwrap();
+        
+        let groups = |key: GroupKey| -> Vec<(String, usize)> {
+            workspace.group_actions_by(key, &ActionFilter::default()).unwrap()
+                .into_iter()
+                .map(|(name, members)| (name, members.len()))
+                .collect()
+        };
+        let owned = |pairs: &[(&str, usize)]| -> Vec<(String, usize)> {
+            pairs.iter().map(|(name, count)| (name.to_string(), *count)).collect()
+        };
+        assert_eq!(groups(GroupKey::Status), owned(&[("discovery", 3), ("design", 1)]));
+        assert_eq!(groups(GroupKey::Project), owned(&[("api", 2), ("web", 2)]));
+        assert_eq!(groups(GroupKey::Priority), owned(&[("high", 1), ("none", 3)]));
+        assert_eq!(groups(GroupKey::Assignee), owned(&[("bo", 2), ("Ana", 1), (UNASSIGNED, 1)]));
+        assert_eq!("Assignee".parse::<GroupKey>().unwrap(), GroupKey::Assignee);
+        assert!("owner".parse::<GroupKey>().unwrap_err().to_string().contains("assignee"));
+    }
+    
+    #[test]
+    fn test_config_settings_apply_to_the_workspace() {
+        let (dir, mut workspace) = workspace_with(&[]);
+        workspace.set_config("default_status", "design").unwrap();
+        workspace.set_config("default_editor", "my-editor").unwrap();
+        assert!(workspace.set_config("meta_graph_layout", "hidden").is_err());
+        
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        assert_eq!(workspace.editor(), "my-editor");
+        let action = workspace.create_action("web", "setup", Priority::None).unwrap();
+        assert_eq!(action.status(), &Status::Design);
+        
+        workspace.unset_config("default_status").unwrap();
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        assert_eq!(workspace.create_action("web", "deploy", Priority::None).unwrap().status(), &Status::Discovery);
+    }
+    
+    #[test]
+    fn test_auto_commit_records_changes_in_git() {
+        let dir = TempDir::new().unwrap();
+        let options = InitOptions { git: true, ..Default::default() };
+        let mut workspace = Workspace::init_with(dir.path(), &options).unwrap();
+        assert!(dir.path().join(".git").is_dir());
+        let ignored = fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert!(ignored.lines().any(|line| line == ".*.tmp"), "{}", ignored);
+        
+        let git = |args: &[&str]| -> String {
+            let output = Command::new("git").arg("-C").arg(dir.path()).args(args).output().unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+            String::from_utf8(output.stdout).unwrap()
+        };
+        git(&["config", "user.name", "Test"]);
+        git(&["config", "user.email", "test@example.com"]);
+        
+        workspace.create_action("web", "untracked", Priority::None).unwrap();
+        workspace.set_config("auto_commit", "true").unwrap();
+        workspace.create_action("web", "setup", Priority::None).unwrap();
+        workspace.update_action_status("web", "setup", Status::Design).unwrap();
+        workspace.set_action_priority("web", "setup", Priority::High).unwrap();
+        workspace.set_action_priority("web", "setup", Priority::High).unwrap();
+        workspace.undo_last_change().unwrap();
+        
+        let log = git(&["log", "--format=%s"]);
+        assert_eq!(
+            log.lines().collect::<Vec<_>>(),
+            ["undo: web/setup priority -> none", "priority: web/setup -> high", "status: web/setup -> design", "create: web/setup"]
+        );
+        // Only the changed action is committed
+        assert_eq!(git(&["ls-files"]), "web/setup.md\n");
+    }
+    
+    #[test]
+    fn test_search_plain_text_or_regex() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup"), ("api", "auth")]);
+        workspace.set_action_section("api", "auth", "Notes", Some("Rotate keys (v2) monthly".to_string())).unwrap();
+        
+        let hits = workspace.search("KEYS (V2)", false).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].action.title(), hits[0].section), ("auth", "Notes"));
+        
+        assert_eq!(workspace.search(r"^rotate \w+", true).unwrap().len(), 1);
+        assert!(workspace.search("(unclosed", true).is_err());
+    }
+    
+    #[test]
+    fn test_dependencies_block_until_final_status() {
+        let (dir, mut workspace) = workspace_with(&[("web", "deploy"), ("web", "setup"), ("api", "auth")]);
+        assert!(workspace.add_dependency("web", "deploy", "setup").unwrap());
+        assert!(workspace.add_dependency("web", "deploy", "api/auth").unwrap());
+        assert!(!workspace.add_dependency("web", "deploy", "web/setup").unwrap(), "bare titles are qualified");
+        assert!(workspace.add_dependency("web", "deploy", "api/missing").is_err());
+        assert!(workspace.add_dependency("web", "deploy", "deploy").is_err());
+        
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        assert_eq!(workspace.get_action("web", "deploy").unwrap().depends_on, ["web/setup", "api/auth"]);
+        let pending = |workspace: &Workspace| -> Vec<String> {
+            workspace.blocked_actions()
+                .iter()
+                .flat_map(|(action, pending)| pending.iter().map(move |p| format!("{} <- {}/{}", action.title(), p.project(), p.title())))
+                .collect()
+        };
+        assert_eq!(pending(&workspace), ["deploy <- web/setup", "deploy <- api/auth"]);
+        
+        workspace.projects.get_mut("api").unwrap().get_action_mut("auth").unwrap().set_status(Status::Published);
+        assert_eq!(pending(&workspace), ["deploy <- web/setup"]);
+        
+        workspace.delete_action("web", "setup").unwrap();
+        assert!(pending(&workspace).is_empty(), "dangling references don't block");
+        assert!(workspace.check().iter().any(|issue| issue.code == "dangling-dependency"));
+        
+        assert!(workspace.remove_dependency("web", "deploy", "setup").unwrap());
+        assert!(!workspace.remove_dependency("web", "deploy", "setup").unwrap());
+        assert!(workspace.check().iter().all(|issue| issue.code != "dangling-dependency"));
+    }
+    
+    #[test]
+    fn test_broken_links_are_reported_last() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("api", "auth")]);
+        fs::write(dir.path().join("api/notes.md"), "plain file").unwrap();
+        workspace.set_action_section("web", "setup", "Statement of Inputs", Some(
+            "- [Auth](../api/auth.md)\n- [Notes](/api/notes.md)\n- [Gone](../api/gone.md)\n- [Docs](https://example.com/x.md)".to_string(),
+        )).unwrap();
+        workspace.set_action_section("web", "setup", "Notes", Some("See [gone](../api/gone.md) and [old](old.md)".to_string())).unwrap();
+        
+        let setup = workspace.get_action("web", "setup").unwrap();
+        assert_eq!(workspace.broken_links(setup), ["../api/gone.md", "old.md"]);
+        let auth = workspace.get_action("api", "auth").unwrap();
+        assert!(workspace.broken_links(auth).is_empty(), "the template's example link is skipped");
+        
+        let issues = workspace.check();
+        let codes: Vec<&str> = issues.iter().map(|issue| issue.code).collect();
+        assert_eq!(codes.iter().filter(|code| **code == "broken-link").count(), 2);
+        assert!(codes.ends_with(&["broken-link", "broken-link"]), "{:?}", codes);
+        assert!(issues.iter().filter(|issue| issue.code == "broken-link").all(|issue| !issue.is_error()));
+    }
+    
+    #[test]
+    fn test_next_and_previous_status() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "setup")]);
+        assert_eq!(workspace.previous_action_status("web", "setup").unwrap(), None);
+        assert_eq!(workspace.next_action_status("web", "setup").unwrap(), Some(Status::Design));
+        assert_eq!(workspace.get_action("web", "setup").unwrap().status(), &Status::Design);
+        assert_eq!(workspace.previous_action_status("web", "setup").unwrap(), Some(Status::Discovery));
+        
+        workspace.update_action_status("web", "setup", Status::Published).unwrap();
+        assert_eq!(workspace.next_action_status("web", "setup").unwrap(), None);
+        
+        workspace.projects.get_mut("web").unwrap().get_action_mut("setup").unwrap().set_status(Status::named("parked"));
+        assert!(workspace.next_action_status("web", "setup").is_err());
+    }
+    
+    #[test]
+    fn test_tag_single_action_and_filter_by_tag() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup"), ("web", "deploy")]);
+        assert!(workspace.add_action_tag("web", "setup", "#Backend").unwrap());
+        assert!(!workspace.add_action_tag("web", "setup", "Backend").unwrap());
+        assert!(workspace.add_action_tag("web", "setup", "priority").is_err(), "#priority is the flag");
+        assert!(workspace.add_action_tag("web", "setup", "no spaces").is_err());
+        
+        let mut workspace = Workspace::load(dir.path()).unwrap();
+        let tagged = |workspace: &Workspace, tag: &str| -> Vec<String> {
+            let filter = ActionFilter { tag: Some(tag.to_string()), ..Default::default() };
+            workspace.filter_actions(&filter).unwrap().iter().map(|a| a.title().to_string()).collect()
+        };
+        assert_eq!(tagged(&workspace, "backend"), ["setup"]);
+        assert_eq!(tagged(&workspace, "#Backend"), ["setup"]);
+        
+        assert!(workspace.remove_action_tag("web", "setup", "Backend").unwrap());
+        assert!(!workspace.remove_action_tag("web", "setup", "Backend").unwrap());
+        assert!(tagged(&Workspace::load(dir.path()).unwrap(), "backend").is_empty());
+    }
+    
+    #[test]
+    fn test_create_action_with_section_content() {
+        let (_dir, mut workspace) = workspace_with(&[]);
+        let options = CreateOptions {
+            sections: vec![("statement of action".to_string(), "Set up CI".to_string())],
+            ..Default::default()
+        };
+        let action = workspace.create_action_with("web", "setup", &options).unwrap();
+        assert_eq!(action.statement_of_action().unwrap(), "Set up CI");
+        assert_eq!(action.statement_of_inputs().unwrap(), template::default_placeholder("Statement of Inputs"));
+        
+        let blank = CreateOptions { blank: true, ..options };
+        let action = workspace.create_action_with("web", "bare", &blank).unwrap();
+        assert_eq!(action.statement_of_action().unwrap(), "Set up CI");
+        assert!(action.notes().is_none());
+        
+        let unknown = CreateOptions { sections: vec![("Risks".to_string(), "None".to_string())], ..Default::default() };
+        assert!(workspace.create_action_with("web", "risky", &unknown).is_err());
+    }
+    
+    #[test]
+    fn test_undo_last_status_or_priority_change() {
+        let (dir, mut workspace) = workspace_with(&[("web", "setup")]);
+        assert!(workspace.undo_last_change().unwrap().is_none());
+        
+        workspace.update_action_status("web", "setup", Status::Published).unwrap();
+        workspace.set_action_priority("web", "setup", Priority::None).unwrap();
+        workspace.set_action_priority("web", "setup", Priority::Medium).unwrap();
+        let log = fs::read_to_string(dir.path().join(".action-lite").join(history::HISTORY_FILE)).unwrap();
+        assert_eq!(log.lines().count(), 2, "unchanged priority isn't recorded:\n{}", log);
+        
+        let undone = workspace.undo_last_change().unwrap().unwrap();
+        assert_eq!(undone.to_string(), "web/setup priority: none -> medium");
+        assert_eq!(workspace.get_action("web", "setup").unwrap().priority(), Priority::None);
+        let error = workspace.undo_last_change().unwrap_err();
+        assert!(error.to_string().contains("already an undo"), "{}", error);
+        
+        workspace.update_action_status("web", "setup", Status::Design).unwrap();
+        workspace.projects.get_mut("web").unwrap().update_action_status_with("setup", Status::Test, &[]).unwrap();
+        let error = workspace.undo_last_change().unwrap_err();
+        assert!(error.to_string().contains("has status 'test' now"), "{}", error);
+        
+        workspace.update_action_status("web", "setup", Status::Design).unwrap();
+        workspace.undo_last_change().unwrap();
+        let reloaded = Workspace::load(dir.path()).unwrap();
+        assert_eq!(reloaded.get_action("web", "setup").unwrap().status(), &Status::Test);
+        assert_eq!(history::load(dir.path()).unwrap().len(), 6);
+    }
+    
+    #[test]
+    fn test_project_action_template_replaces_type_sections() {
+        let (dir, mut workspace) = workspace_with(&[("web", "existing")]);
+        fs::write(
+            dir.path().join("web").join(project::ACTION_TEMPLATE_FILE),
+            "# Team template\n\n## Notes\n\n{{title}} in {{project}} ({{id}}, {{created_at}}) {{other}}\n\n## Acceptance Criteria\n\n- [ ] Done\n",
+        ).unwrap();
+        
+        let action = workspace.create_action("web", "setup", Priority::None).unwrap();
+        let expected = format!("setup in web ({}, {}) {{{{other}}}}", action.id, action.created_at.to_rfc3339());
+        assert_eq!(action.notes().unwrap(), &expected);
+        assert_eq!(action.section("acceptance criteria").unwrap(), "- [ ] Done");
+        assert!(action.statement_of_action().is_none());
+        
+        let options = CreateOptions { sections: vec![("Notes".to_string(), "Answered".to_string())], ..Default::default() };
+        assert_eq!(workspace.create_action_with("web", "asked", &options).unwrap().notes().unwrap(), "Answered");
+        let blank = CreateOptions { blank: true, ..Default::default() };
+        assert!(workspace.create_action_with("web", "bare", &blank).unwrap().extra_sections.is_empty());
+        
+        let reloaded = Workspace::load(dir.path()).unwrap();
+        assert_eq!(reloaded.get_project("web").unwrap().list_actions().len(), 4, "the template isn't an action");
+        assert_eq!(reloaded.get_action("web", "setup").unwrap().section("Acceptance Criteria").unwrap(), "- [ ] Done");
+    }
+    
+    #[test]
+    fn test_higher_priority_lists_first_within_a_project() {
+        let (dir, mut workspace) = workspace_with(&[("web", "a"), ("web", "b"), ("web", "c"), ("api", "z")]);
+        workspace.set_action_priority("web", "b", Priority::Low).unwrap();
+        workspace.set_action_priority("web", "c", Priority::High).unwrap();
+        workspace.set_action_priority("api", "z", Priority::Medium).unwrap();
+        
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let order: Vec<String> = workspace.list_actions(None, None, false).unwrap()
+            .iter()
+            .map(|action| format!("{}/{}", action.project(), action.title()))
+            .collect();
+        assert_eq!(order, ["api/z", "web/c", "web/b", "web/a"]);
+        
+        let groups = workspace.group_actions_by(GroupKey::Priority, &ActionFilter::default()).unwrap();
+        let names: Vec<&str> = groups.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["high", "medium", "low", "none"]);
+    }
+    
+    #[test]
+    fn test_sort_actions() {
+        let (_dir, mut workspace) = workspace_with(&[("web", "b"), ("web", "a"), ("api", "c")]);
+        workspace.update_action_status("web", "b", Status::Design).unwrap();
+        workspace.update_action_status("api", "c", Status::Design).unwrap();
+        workspace.set_action_priority("web", "a", Priority::Low).unwrap();
+        
+        let titles = |key: SortKey, reverse: bool| -> Vec<String> {
+            let mut actions = workspace.filter_actions(&ActionFilter::default()).unwrap();
+            workspace.sort_actions(&mut actions, key, reverse);
+            actions.iter().map(|action| action.title().to_string()).collect()
+        };
+        assert_eq!(titles(SortKey::Title, false), ["a", "b", "c"]);
+        assert_eq!(titles(SortKey::Title, true), ["c", "b", "a"]);
+        assert_eq!(titles(SortKey::Status, false), ["a", "c", "b"], "ties keep project order");
+        assert_eq!(titles(SortKey::Priority, false), ["a", "c", "b"]);
+        assert_eq!(titles(SortKey::Created, false), ["b", "a", "c"]);
+        assert!("size".parse::<SortKey>().is_err());
+    }
+}
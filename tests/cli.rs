@@ -0,0 +1,89 @@
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn action(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_action"))
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn workspace() -> (TempDir, std::path::PathBuf) {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path().join("ws");
+    let output = action(dir.path(), &["init", "ws"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    (dir, root)
+}
+
+fn assert_fails_with(output: &Output, message: &str) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains(message), "{}", stderr);
+}
+
+#[test]
+fn new_rejects_empty_names() {
+    let (_dir, root) = workspace();
+    assert_fails_with(&action(&root, &["new", "", ""]), "Project name cannot be empty");
+    assert_fails_with(&action(&root, &["new", "web", ""]), "Action title cannot be empty");
+    assert!(!root.join("web").exists(), "nothing is created for a rejected action");
+}
+
+#[test]
+fn new_rejects_long_titles() {
+    let (_dir, root) = workspace();
+    let title = "x".repeat(200);
+    assert_fails_with(
+        &action(&root, &["new", "web", &title]),
+        "Action title cannot be longer than 100 characters",
+    );
+    assert!(!root.join("web").exists());
+}
+
+#[test]
+fn mv_renames_or_moves() {
+    let (_dir, root) = workspace();
+    assert!(action(&root, &["new", "web", "old"]).status.success());
+    
+    let output = action(&root, &["mv", "web", "old", "new"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(root.join("web/new.md").is_file());
+    assert!(!root.join("new").exists(), "a new title renames within the project");
+    
+    let output = action(&root, &["mv", "web", "new", "--to", "api"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(root.join("api/new.md").is_file());
+    assert!(!root.join("web/new.md").exists());
+    
+    assert_fails_with(&action(&root, &["mv", "api", "new"]), "required");
+}
+
+#[test]
+fn init_rejects_hidden_directory_names() {
+    let dir = TempDir::new().unwrap();
+    assert_fails_with(&action(dir.path(), &["init", "visible/.hidden"]), "Project name cannot start with a dot");
+    assert!(!dir.path().join("visible").exists());
+}
+
+#[test]
+fn a_new_action_moves_to_design_without_force() {
+    let (_dir, root) = workspace();
+    assert!(action(&root, &["new", "web", "setup"]).status.success());
+    assert!(action(&root, &["new", "web", "deploy"]).status.success());
+    
+    let output = action(&root, &["next", "web", "setup"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let output = action(&root, &["status", "web", "deploy", "design"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let content = std::fs::read_to_string(root.join("web/deploy.md")).unwrap();
+    assert!(content.contains("status: design\n") && content.contains("## Statement of Design"), "{}", content);
+    
+    assert_fails_with(
+        &action(&root, &["status", "web", "setup", "implement"]),
+        "from design to implement: Statement of Design is missing",
+    );
+}